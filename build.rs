@@ -1,4 +1,4 @@
-const BUILD_CONFIG_ENV_KEYS: [&str; 11] = [
+const BUILD_CONFIG_ENV_KEYS: [&str; 13] = [
     "RECOMMEND_JOB_NAMES",
     "REPO_TEMPLATE",
     "LOCATOR_PATTERN",
@@ -10,6 +10,8 @@ const BUILD_CONFIG_ENV_KEYS: [&str; 11] = [
     "CHECK_EXE_FILE_NAME",
     "JENKINS_URL",
     "QUERY_TOKEN_GITHUB",
+    "P4_DEPOT_PATH",
+    "CREDENTIAL_STORE",
 ];
 
 fn main() {
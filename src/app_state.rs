@@ -111,7 +111,9 @@ impl AppState {
             get_default_db_file_path(&get_path_or_home_path(self.db_path.as_deref()))
         {
             open::that(&path).map_err(|_| {
-                VfpFrontError::OpenDbFailed(path.to_str().map(|str| str.to_string()).unwrap_or_default())
+                VfpFrontError::OpenDbFailed(
+                    path.to_str().map(|str| str.to_string()).unwrap_or_default(),
+                )
             })?;
             colored_println(
                 &mut self.stdout.lock(),
@@ -161,11 +163,14 @@ mod tests {
 
         assert_eq!(
             content,
-            r#"version = 7
+            r#"version = 9
 never_check_version = false
 auto_update_enabled = false
 
-[[job_relative_data_arr]]
+[[profiles]]
+name = "default"
+
+[[profiles.job_relative_data_arr]]
 job_name = "test_job"
 last_inner_version = 1024
 last_player_count = 4
@@ -1,30 +1,61 @@
 use crate::app_state::AppState;
 use crate::constant::log::*;
-use crate::db::db_data_proxy::DbDataProxy;
+use crate::credential::CredentialError;
+use crate::default_config::ConfigLayerKind;
 use crate::distribute::{distribute_pt, infer_blast_root_dir_name};
+use crate::doctor::diagnostic::{Diagnostic, DiagnosticStatus};
+use crate::doctor::{check_blast_path, check_process_tools, check_repo, check_runtime_config};
 use crate::extract::extract_operation_info::{
     ExtractOperationInfo, OperationStatus, OperationStepType,
 };
 use crate::extract::extract_params::ExtractParams;
 use crate::extract::extractor_util::{clean_dir, extract_zip_file, mending_user_ini};
 use crate::interact::{
-    input_ci_for_extract, input_directly_with_default, input_job_name, input_pwd,
-    input_target_path, parse_without_input_with_default,
+    input_build_params, input_ci_for_extract, input_ci_for_watch, input_cl,
+    input_directly_with_default, input_job_name, input_pwd, input_sl, input_target_path,
+    parse_without_input_with_default,
 };
+use crate::jenkins::build::{
+    query_job_config_json, query_job_config_xml, request_abort, request_build,
+    validate_param_pairs, wait_for_queued_run, VfpBuildParamChange, VfpBuildParamSpec,
+    VfpJobBuildParam, VfpJobDefinition,
+};
+use crate::jenkins::diagnose::diagnose_login;
+use crate::jenkins::error_kind::{JenkinsAuthError, JenkinsErrorKind};
+use crate::jenkins::history::{
+    export_history, print_history, print_history_stats, query_history, HistoryStats,
+};
+use crate::jenkins::info::info;
+use crate::jenkins::jenkins_model::reasoned_run_status::ReasonedRunStatus;
+use crate::jenkins::jenkins_model::shelves::Shelves;
+use crate::jenkins::job_path::JobPath;
+use crate::jenkins::jobs::{filter_job_entries, print_jobs, query_jobs};
 use crate::jenkins::query::{
-    try_get_jenkins_async_client_by_api_token, try_get_jenkins_async_client_by_pwd,
-    VfpJenkinsClient,
+    new_jenkins_async_client, query_run_info, try_get_jenkins_async_client_by_api_token,
+    try_get_jenkins_async_client_by_pwd, try_get_jenkins_async_client_by_session,
+    JenkinsCredential, VfpJenkinsClient,
 };
-use crate::jenkins::watch::watch;
-use crate::pretty_log::{colored_println, toast, ThemeColor};
-use crate::run::set_server;
+use crate::jenkins::session::{JenkinsSession, SessionSource};
+use crate::jenkins::util::{get_jenkins_job_url, get_jenkins_workflow_run_url};
+use crate::jenkins::watch::{
+    get_progress_hint, query_reasoned_run_statuses, watch, watch_change_list,
+};
+use crate::p4::{check_shelves, query_have_change_list, query_my_shelved_changes, P4Change, P4Cli};
+use crate::pretty_log::{clean_one_line, colored_println, toast, ThemeColor};
+use crate::run::{set_server, shell_command};
+use crate::service::jenkins_rpc_service::JenkinsRpcService;
 use crate::vfp_error::VfpFrontError;
 use crate::{default_config, pretty_log, run_instance_with_log};
+use chrono::Local;
 use crossterm::execute;
 use crossterm::style::Color;
 use formatx::formatx;
 use inquire::InquireError;
-use std::path::PathBuf;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 /// # cli do extract
 ///
@@ -38,29 +69,26 @@ pub async fn cli_do_extract(
     extract_params: ExtractParams,
     ignore_count_input: bool,
 ) -> Result<(), VfpFrontError> {
-    let runtime_config = default_config::runtime();
-
-    let job_name = {
-        let db = app_state.get_mut_db();
-        let result = input_job_name_with_err_handling(job_name_param, db)?;
-        db.insert_job_name(result.as_str());
-        result
-    };
+    let job_name = input_job_name_with_err_handling(app_state, job_name_param).await?;
+    app_state.get_mut_db().insert_job_name(job_name.as_str());
+    let runtime_config = default_config::runtime_for_job(job_name.as_str());
 
     let db = app_state.get_db();
     let used_extract_repo = parse_without_input_with_default(
         extract_params.build_target_repo_template,
-        db.get_extract_repo().as_ref(),
+        runtime_config.last_used_unless_set_for_job("repo_template", db.get_extract_repo()),
         runtime_config.repo_template.as_str(),
     );
     let used_extract_locator_pattern = parse_without_input_with_default(
         extract_params.main_locator_pattern,
-        db.get_extract_locator_pattern().as_ref(),
+        runtime_config
+            .last_used_unless_set_for_job("locator_pattern", db.get_extract_locator_pattern()),
         runtime_config.locator_pattern.as_str(),
     );
     let used_extract_s_locator_template = parse_without_input_with_default(
         extract_params.secondary_locator_template,
-        db.get_extract_s_locator_template().as_ref(),
+        runtime_config
+            .last_used_unless_set_for_job("locator_template", db.get_extract_s_locator_template()),
         runtime_config.locator_template.as_str(),
     );
     let used_inner_version = input_ci_for_extract(app_state, job_name.as_str(), ci).await?;
@@ -85,10 +113,17 @@ pub async fn cli_do_extract(
     .map_err(|_| VfpFrontError::MissingParam(PARAM_DEST.to_string()))?;
 
     {
+        // the last used values are shared by the jobs, so the overrides of a job are not kept.
         let db = app_state.get_mut_db();
-        db.set_extract_repo(used_extract_repo.into());
-        db.set_extract_locator_pattern(used_extract_locator_pattern.into());
-        db.set_extract_s_locator_template(used_extract_s_locator_template.into());
+        if !runtime_config.is_set_for_job("repo_template") {
+            db.set_extract_repo(used_extract_repo.into());
+        }
+        if !runtime_config.is_set_for_job("locator_pattern") {
+            db.set_extract_locator_pattern(used_extract_locator_pattern.into());
+        }
+        if !runtime_config.is_set_for_job("locator_template") {
+            db.set_extract_s_locator_template(used_extract_s_locator_template.into());
+        }
         db.set_last_inner_version(job_name.as_str(), used_inner_version.into());
         db.set_last_player_count(job_name.as_str(), used_player_count.into());
         db.set_blast_path(job_name.as_str(), used_blast_path.clone().into());
@@ -120,7 +155,7 @@ pub async fn cli_do_extract(
                     .as_path()
                     .join(format!("{}{}", file_name, i));
                 let path_t = path.clone();
-                let mend_file_path_t = runtime_config.mending_file_path.as_str();
+                let mend_file_path_t = runtime_config.mending_file_path.clone();
                 let handle = std::thread::spawn(move || {
                     let clean_res = clean_dir(&dest_with_origin_name);
                     match clean_res {
@@ -144,7 +179,7 @@ pub async fn cli_do_extract(
                                     let mend_res = mending_user_ini(
                                         &dest_with_origin_name,
                                         i,
-                                        mend_file_path_t,
+                                        &mend_file_path_t,
                                     );
 
                                     match mend_res {
@@ -247,6 +282,31 @@ pub async fn cli_do_extract(
     Ok(())
 }
 
+/// # LoginSecret
+///
+/// The secrets of `fp login` from cli params. The login method follows the given one.
+#[derive(Default)]
+pub struct LoginSecret {
+    pub api_token: Option<String>,
+    pub pwd: Option<String>,
+    pub session: Option<String>,
+    pub session_source: Option<SessionSource>,
+}
+
+impl LoginSecret {
+    fn get_login_method(&self) -> Option<crate::LoginMethod> {
+        if self.session.is_some() || self.session_source.is_some() {
+            Some(crate::LoginMethod::Session)
+        } else if self.pwd.is_some() {
+            Some(crate::LoginMethod::Pwd)
+        } else if self.api_token.is_some() {
+            Some(crate::LoginMethod::ApiToken)
+        } else {
+            None
+        }
+    }
+}
+
 /// # cli do log in
 ///
 /// Login to Jenkins server.
@@ -259,15 +319,13 @@ pub async fn cli_do_extract(
 /// * `simplified`: When simplifying, only re-enter the login key (password api-token etc.).
 /// * `url`: jenkins url root from cli param.
 /// * `username`: jenkins username from cli param.
-/// * `api_token`: jenkins api token from cli param.
-/// * `pwd`: jenkins password from cli param.
+/// * `secret`: jenkins api token, password or session from cli params.
 pub async fn cli_do_login(
     app_state: &mut AppState,
     simplified: bool,
     url: Option<impl AsRef<str>>,
     username: Option<impl AsRef<str>>,
-    api_token: Option<impl AsRef<str>>,
-    pwd: Option<impl AsRef<str>>,
+    secret: LoginSecret,
 ) -> Result<VfpJenkinsClient, VfpFrontError> {
     let runtime_config = default_config::runtime();
     let db = app_state.get_mut_db();
@@ -295,14 +353,23 @@ pub async fn cli_do_login(
     app_state.commit(false);
     let db = app_state.get_mut_db();
 
-    let login_method = inquire::Select::new(
-        HINT_SELECT_LOGIN_METHOD,
-        vec![crate::LoginMethod::Pwd, crate::LoginMethod::ApiToken],
-    )
-    .prompt()
-    .unwrap_or(crate::LoginMethod::ApiToken);
+    let login_method = match secret.get_login_method() {
+        Some(login_method) => login_method,
+        None => inquire::Select::new(
+            HINT_SELECT_LOGIN_METHOD,
+            vec![
+                crate::LoginMethod::Pwd,
+                crate::LoginMethod::ApiToken,
+                crate::LoginMethod::Session,
+            ],
+        )
+        .with_starting_cursor(if db.is_session_login() { 2 } else { 0 })
+        .prompt()
+        .unwrap_or(crate::LoginMethod::ApiToken),
+    };
 
-    let client = match login_method {
+    // the secret goes to the credential store, so it is saved only after the login succeeds.
+    let (client, key, credential) = match login_method {
         crate::LoginMethod::ApiToken => {
             let hint = formatx!(
                 HINT_INPUT_JENKINS_API_TOKEN,
@@ -311,56 +378,117 @@ pub async fn cli_do_login(
             )
             .unwrap_or(HINT_JENKINS_API_TOKEN_DOC.to_string());
 
-            db.set_jenkins_api_token(Some(crate::interact::input_directly(
-                api_token.map(|v| v.as_ref().to_string()),
-                db.get_jenkins_api_token().as_ref(),
+            let last_api_token = db.get_jenkins_api_token().ok().flatten();
+            let api_token = crate::interact::input_directly(
+                secret.api_token,
+                last_api_token.as_ref(),
                 false,
                 true,
                 &hint,
                 Some(ERR_NEED_A_JENKINS_API_TOKEN),
-            )?));
+            )?;
 
-            try_get_jenkins_async_client_by_api_token(
+            let client = try_get_jenkins_async_client_by_api_token(
                 db.get_jenkins_url(),
                 db.get_jenkins_username(),
-                db.get_jenkins_api_token(),
+                &Some(api_token.clone()),
+            )
+            .await;
+            if client.is_ok() {
+                db.set_jenkins_api_token(Some(api_token.clone()))
+                    .map_err(credential_save_failed)?;
+            }
+
+            (
+                client,
+                api_token.clone(),
+                Some(JenkinsCredential::ApiToken(api_token)),
             )
-            .await
         }
         crate::LoginMethod::Pwd => {
-            db.set_jenkins_pwd(
-                input_pwd(
-                    pwd.map(|v| v.as_ref().to_string()),
-                    HINT_INPUT_JENKINS_PWD,
-                    Some(ERR_NEED_A_JENKINS_PWD),
-                )
-                .ok(),
-            );
+            let pwd = input_pwd(
+                secret.pwd,
+                HINT_INPUT_JENKINS_PWD,
+                Some(ERR_NEED_A_JENKINS_PWD),
+            )?;
 
-            try_get_jenkins_async_client_by_pwd(
+            let client = try_get_jenkins_async_client_by_pwd(
                 db.get_jenkins_url(),
                 db.get_jenkins_username(),
-                &db.get_jenkins_pwd(),
+                &Some(pwd.clone()),
             )
-            .await
+            .await;
+            if client.is_ok() {
+                db.set_jenkins_pwd(Some(pwd.clone()))
+                    .map_err(credential_save_failed)?;
+            }
+
+            (client, pwd.clone(), Some(JenkinsCredential::Pwd(pwd)))
+        }
+        crate::LoginMethod::Session => {
+            let (session, source) = input_session(secret.session, secret.session_source)?;
+            let key = match &source {
+                Some(SessionSource::File(path)) => path.display().to_string(),
+                Some(SessionSource::Command(cmd)) => cmd.clone(),
+                None => session.clone().unwrap_or_default(),
+            };
+            let session = match (&source, session) {
+                (Some(source), _) => source.read().map(Some),
+                (None, session) => Ok(session),
+            };
+
+            let (client, credential) = match session {
+                Ok(session) => {
+                    let parsed = session.as_deref().and_then(JenkinsSession::parse);
+                    let client = try_get_jenkins_async_client_by_session(
+                        db.get_jenkins_url(),
+                        &parsed,
+                        &source,
+                    )
+                    .await;
+                    let credential =
+                        parsed.map(|parsed| JenkinsCredential::Session(parsed, source.clone()));
+                    if client.is_ok() {
+                        // the source is read on every run, so the session is not kept.
+                        db.set_jenkins_session(session.filter(|_| source.is_none()))
+                            .map_err(credential_save_failed)?;
+                        db.set_jenkins_session_source(source);
+                    }
+                    (client, credential)
+                }
+                Err(e) => (Err(e), None),
+            };
+
+            (client, key, credential)
         }
     };
 
     match client {
         Ok(client) => {
+            // the session takes precedence, so it is dropped when logging in by others.
+            if !matches!(login_method, crate::LoginMethod::Session) && db.is_session_login() {
+                db.set_jenkins_session(None)
+                    .map_err(credential_save_failed)?
+                    .set_jenkins_session_source(None);
+            }
             app_state.commit(false);
             Ok(client)
         }
         Err(e) => {
-            let key = match login_method {
-                crate::LoginMethod::ApiToken => db.get_jenkins_api_token().clone().unwrap(),
-                crate::LoginMethod::Pwd => db.get_jenkins_pwd().clone().unwrap(),
-            };
+            let url = db.get_jenkins_url().clone().unwrap();
+            let username = db.get_jenkins_username().clone().unwrap();
+            if let Some(credential) = credential
+                && let Ok(client) = new_jenkins_async_client(&url, &username, &credential)
+            {
+                for diagnostic in diagnose_login(&url, &username, &client, None).await {
+                    diagnostic.colored_println(&mut app_state.get_stdout());
+                }
+            }
 
             Err(VfpFrontError::JenkinsLoginError {
                 method: login_method,
-                url: db.get_jenkins_url().clone().unwrap(),
-                username: db.get_jenkins_username().clone().unwrap(),
+                url,
+                username,
                 key,
                 e,
             })
@@ -368,6 +496,437 @@ pub async fn cli_do_login(
     }
 }
 
+/// # cli do login check
+///
+/// Check the saved login step by step, and print a line for each check.
+/// Fails if any check failed.
+pub async fn cli_do_login_check(
+    app_state: &AppState,
+    job_name: Option<String>,
+) -> Result<(), VfpFrontError> {
+    let db = app_state.get_db();
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Main,
+        &formatx!(LOGIN_CHECK_TITLE, db.get_profile_name()).unwrap_or_default(),
+    );
+
+    let job_path = job_name
+        .or(db.get_interest_job_name().map(str::to_string))
+        .map(JobPath::from);
+    let diagnostics = db.diagnose_jenkins_login(job_path.as_ref()).await;
+    for diagnostic in diagnostics.iter() {
+        diagnostic.colored_println(&mut app_state.get_stdout());
+    }
+
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.status == DiagnosticStatus::Failed)
+    {
+        return Err(VfpFrontError::Custom(ERR_LOGIN_CHECK_FAILED.to_string()));
+    }
+
+    Ok(())
+}
+
+/// # cli do doctor
+///
+/// Check the environment section by section, and print how to fix the problems found.
+pub async fn cli_do_doctor(
+    app_state: &AppState,
+    job_name: Option<String>,
+) -> Result<(), VfpFrontError> {
+    let runtime_config = default_config::runtime();
+    let db = app_state.get_db();
+    colored_println(&mut app_state.get_stdout(), ThemeColor::Main, DOCTOR_TITLE);
+
+    let job_name = job_name.or(db.get_interest_job_name().map(str::to_string));
+    let job_config = job_name.as_deref().map(default_config::runtime_for_job);
+    let job_config = job_config.as_ref().unwrap_or(runtime_config);
+    let mut sections = vec![
+        (
+            DOCTOR_SECTION_CONFIG,
+            check_runtime_config(&default_config::runtime_config_files(), runtime_config),
+        ),
+        (
+            DOCTOR_SECTION_REPO,
+            check_repo(
+                job_config
+                    .last_used_unless_set_for_job("repo_template", db.get_extract_repo())
+                    .unwrap_or(&job_config.repo_template),
+                job_config
+                    .last_used_unless_set_for_job(
+                        "locator_pattern",
+                        db.get_extract_locator_pattern(),
+                    )
+                    .unwrap_or(&job_config.locator_pattern),
+                job_config
+                    .last_used_unless_set_for_job(
+                        "locator_template",
+                        db.get_extract_s_locator_template(),
+                    )
+                    .unwrap_or(&job_config.locator_template),
+                &job_config.mending_file_path,
+                job_name.as_deref(),
+            ),
+        ),
+        (
+            DOCTOR_SECTION_BLAST_PATH,
+            vec![check_blast_path(
+                job_name
+                    .as_deref()
+                    .and_then(|job_name| db.get_blast_path(job_name))
+                    .map(PathBuf::as_path),
+            )],
+        ),
+        (DOCTOR_SECTION_TOOLS, check_process_tools()),
+    ];
+
+    let jenkins = if db.user_never_login() {
+        vec![
+            Diagnostic::new(DiagnosticStatus::Warn, Ok(DOCTOR_NOT_LOGGED_IN.into()))
+                .with_hint(HINT_DIAG_LOGIN),
+        ]
+    } else {
+        let job_path = job_name.map(JobPath::from);
+        db.diagnose_jenkins_login(job_path.as_ref()).await
+    };
+    sections.push((DOCTOR_SECTION_JENKINS, jenkins));
+
+    let mut stdout = app_state.get_stdout();
+    for (title, diagnostics) in sections.iter() {
+        colored_println(&mut stdout, ThemeColor::Second, title);
+        for diagnostic in diagnostics.iter() {
+            diagnostic.colored_println(&mut stdout);
+        }
+    }
+
+    let count = |status: DiagnosticStatus| {
+        sections
+            .iter()
+            .flat_map(|(_, diagnostics)| diagnostics.iter())
+            .filter(|diagnostic| diagnostic.status == status)
+            .count()
+    };
+    let failed = count(DiagnosticStatus::Failed);
+    colored_println(
+        &mut stdout,
+        ThemeColor::Main,
+        &formatx!(
+            DOCTOR_SUMMARY,
+            count(DiagnosticStatus::Ok),
+            count(DiagnosticStatus::Warn),
+            failed
+        )
+        .unwrap_or_default(),
+    );
+
+    if failed > 0 {
+        return Err(VfpFrontError::Custom(ERR_DOCTOR_FAILED.to_string()));
+    }
+
+    Ok(())
+}
+
+/// # cli do config show
+///
+/// Print every effective value of the runtime config, or of `job_name` with its overrides,
+/// and where it comes from if `origin`.
+pub fn cli_do_config_show(app_state: &AppState, origin: bool, job_name: Option<&str>) {
+    let mut stdout = app_state.get_stdout();
+    for entry in get_runtime_entries(job_name) {
+        let Some(value) = entry.value else {
+            colored_println(
+                &mut stdout,
+                ThemeColor::Second,
+                &formatx!(CONFIG_SHOW_UNSET, &entry.key).unwrap_or_default(),
+            );
+            continue;
+        };
+
+        let line = match entry.origin.filter(|_| origin) {
+            Some(origin) => formatx!(
+                CONFIG_SHOW_ENTRY_WITH_ORIGIN,
+                &entry.key,
+                value,
+                origin.to_string()
+            ),
+            None => formatx!(CONFIG_SHOW_ENTRY, &entry.key, value),
+        };
+        colored_println(&mut stdout, ThemeColor::Main, &line.unwrap_or_default());
+    }
+}
+
+/// # cli do config get
+///
+/// Print the effective value of `key`, or of every key under it like `http`.
+pub fn cli_do_config_get(
+    app_state: &AppState,
+    key: &str,
+    job_name: Option<&str>,
+) -> Result<(), VfpFrontError> {
+    let prefix = format!("{}.", key);
+    let entries = get_runtime_entries(job_name)
+        .into_iter()
+        .filter(|entry| entry.key == key || entry.key.starts_with(&prefix))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        default_config::check_config_key(key).map_err(VfpFrontError::Custom)?;
+    }
+
+    let mut stdout = app_state.get_stdout();
+    for entry in entries {
+        match (entry.value, entry.origin) {
+            (Some(value), Some(origin)) => colored_println(
+                &mut stdout,
+                ThemeColor::Main,
+                &formatx!(
+                    CONFIG_SHOW_ENTRY_WITH_ORIGIN,
+                    &entry.key,
+                    value,
+                    origin.to_string()
+                )
+                .unwrap_or_default(),
+            ),
+            (Some(value), None) => colored_println(
+                &mut stdout,
+                ThemeColor::Main,
+                &formatx!(CONFIG_SHOW_ENTRY, &entry.key, value).unwrap_or_default(),
+            ),
+            (None, _) => colored_println(
+                &mut stdout,
+                ThemeColor::Second,
+                &formatx!(CONFIG_SHOW_UNSET, &entry.key).unwrap_or_default(),
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// # cli do config set
+///
+/// Validate `value` of `key`, and write it to the file of `layer`.
+pub fn cli_do_config_set(
+    app_state: &AppState,
+    key: &str,
+    value: &str,
+    layer: ConfigLayerKind,
+    force: bool,
+) -> Result<(), VfpFrontError> {
+    let toml_value =
+        default_config::validate_config_value(key, value, force).map_err(VfpFrontError::Custom)?;
+    if layer == ConfigLayerKind::Project && default_config::is_ignored_in_project(key) {
+        return Err(VfpFrontError::Custom(
+            formatx!(ERR_CONFIG_NOT_IN_PROJECT, key).unwrap_or_default(),
+        ));
+    }
+    let path = get_config_layer_path(layer)?;
+    default_config::set_config_value(&path, key, &toml_value).map_err(VfpFrontError::Custom)?;
+
+    let mut stdout = app_state.get_stdout();
+    colored_println(
+        &mut stdout,
+        ThemeColor::Success,
+        &formatx!(
+            CONFIG_SET_SUCCESS,
+            key,
+            &toml_value,
+            path.display().to_string()
+        )
+        .unwrap_or_default(),
+    );
+    warn_if_config_overridden(app_state, key, layer);
+    Ok(())
+}
+
+/// # cli do config unset
+///
+/// Remove `key` from the file of `layer`.
+pub fn cli_do_config_unset(
+    app_state: &AppState,
+    key: &str,
+    layer: ConfigLayerKind,
+) -> Result<(), VfpFrontError> {
+    default_config::check_config_key(key).map_err(VfpFrontError::Custom)?;
+    let path = get_config_layer_path(layer)?;
+    let path_str = path.display().to_string();
+    let mut stdout = app_state.get_stdout();
+    if default_config::unset_config_value(&path, key).map_err(VfpFrontError::Custom)? {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Success,
+            &formatx!(CONFIG_UNSET_SUCCESS, key, path_str).unwrap_or_default(),
+        );
+        warn_if_config_overridden(app_state, key, layer);
+    } else {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Warn,
+            &formatx!(WARN_CONFIG_NOT_IN_FILE, key, path_str).unwrap_or_default(),
+        );
+    }
+    Ok(())
+}
+
+/// # cli do config edit
+///
+/// Open the file of `layer` with `$VISUAL` or `$EDITOR` and check it after the editor exits.
+/// Without them, open it with the default app and leave the check to `fp doctor`.
+pub fn cli_do_config_edit(
+    app_state: &AppState,
+    layer: ConfigLayerKind,
+) -> Result<(), VfpFrontError> {
+    let path = get_config_layer_path(layer)?;
+    let path_str = path.display().to_string();
+    if !path.is_file() {
+        path.parent()
+            .map(std::fs::create_dir_all)
+            .transpose()
+            .and_then(|_| std::fs::write(&path, ""))
+            .map_err(|e| VfpFrontError::Custom(e.to_string()))?;
+    }
+
+    let mut stdout = app_state.get_stdout();
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty());
+    let Some(editor) = editor else {
+        open::that(&path).map_err(|e| {
+            VfpFrontError::Custom(
+                formatx!(ERR_CONFIG_EDITOR_FAILED, e.to_string()).unwrap_or_default(),
+            )
+        })?;
+        colored_println(
+            &mut stdout,
+            ThemeColor::Success,
+            &formatx!(CONFIG_EDIT_OPENED, path_str).unwrap_or_default(),
+        );
+        return Ok(());
+    };
+
+    // `$EDITOR` may have args like `code --wait`, so it runs in the shell,
+    // unless it is the path of the editor, which may have spaces like `C:\Program Files\...`.
+    let mut command = if Path::new(&editor).is_file() {
+        let mut command = std::process::Command::new(&editor);
+        command.arg(&path);
+        command
+    } else {
+        shell_command(&format!("{} \"{}\"", editor, path_str))
+    };
+    command.status().map_err(|e| {
+        VfpFrontError::Custom(formatx!(ERR_CONFIG_EDITOR_FAILED, e.to_string()).unwrap_or_default())
+    })?;
+
+    default_config::check_runtime_config_file(&path).map_err(|e| {
+        VfpFrontError::Custom(formatx!(ERR_CONFIG_FILE_INVALID, &path_str, e).unwrap_or_default())
+    })?;
+    let unknown = default_config::find_unknown_keys_in_file(&path);
+    for e in &unknown {
+        colored_println(&mut stdout, ThemeColor::Warn, e);
+    }
+    if unknown.is_empty() {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Success,
+            &formatx!(CONFIG_EDIT_VALID, path_str).unwrap_or_default(),
+        );
+    }
+    Ok(())
+}
+
+fn get_runtime_entries(job_name: Option<&str>) -> Vec<default_config::RuntimeConfigEntry> {
+    match job_name {
+        Some(job_name) => default_config::runtime_entries_for_job(job_name),
+        None => default_config::runtime_entries(),
+    }
+}
+
+fn get_config_layer_path(layer: ConfigLayerKind) -> Result<PathBuf, VfpFrontError> {
+    layer
+        .get_path()
+        .ok_or(VfpFrontError::Custom(ERR_CONFIG_LAYER_PATH.to_string()))
+}
+
+/// the runtime config is loaded before the change, so a higher layer which sets `key` still wins.
+fn warn_if_config_overridden(app_state: &AppState, key: &str, layer: ConfigLayerKind) {
+    let origin = default_config::runtime_entries()
+        .into_iter()
+        .find(|entry| entry.key == key)
+        .and_then(|entry| entry.origin)
+        .filter(|origin| layer.is_overridden_by(origin));
+    if let Some(origin) = origin {
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Warn,
+            &formatx!(WARN_CONFIG_OVERRIDDEN, origin.to_string()).unwrap_or_default(),
+        );
+    }
+}
+
+fn credential_save_failed(e: CredentialError) -> VfpFrontError {
+    VfpFrontError::Custom(formatx!(ERR_CREDENTIAL_SAVE_FAILED, e).unwrap_or_default())
+}
+
+/// # input session
+///
+/// Paste the session, or give the file or the command to read it from.
+///
+/// ### Returns
+///
+/// the pasted session, or the source.
+fn input_session(
+    session: Option<String>,
+    session_source: Option<SessionSource>,
+) -> Result<(Option<String>, Option<SessionSource>), VfpFrontError> {
+    if session.is_some() || session_source.is_some() {
+        return Ok((session, session_source));
+    }
+
+    let source_kind = inquire::Select::new(
+        HINT_SELECT_SESSION_SOURCE,
+        vec![
+            HINT_SESSION_SOURCE_PASTE,
+            HINT_SESSION_SOURCE_FILE,
+            HINT_SESSION_SOURCE_COMMAND,
+        ],
+    )
+    .prompt()?;
+
+    Ok(match source_kind {
+        HINT_SESSION_SOURCE_FILE => {
+            let path = crate::interact::input_directly(
+                None,
+                None::<&String>,
+                false,
+                true,
+                HINT_INPUT_JENKINS_SESSION_FILE,
+                Some(ERR_NEED_A_JENKINS_SESSION),
+            )?;
+            (None, Some(SessionSource::File(PathBuf::from(path))))
+        }
+        HINT_SESSION_SOURCE_COMMAND => {
+            let cmd = crate::interact::input_directly(
+                None,
+                None::<&String>,
+                false,
+                true,
+                HINT_INPUT_JENKINS_SESSION_COMMAND,
+                Some(ERR_NEED_A_JENKINS_SESSION),
+            )?;
+            (None, Some(SessionSource::Command(cmd)))
+        }
+        _ => {
+            let session = input_pwd(
+                None,
+                HINT_INPUT_JENKINS_SESSION,
+                Some(ERR_NEED_A_JENKINS_SESSION),
+            )?;
+            (Some(session), None)
+        }
+    })
+}
+
 /// # ci do watch
 ///
 /// Watch jenkins run task status.
@@ -377,17 +936,28 @@ pub async fn cli_do_watch(
     app_state: &mut AppState,
     job_name: Option<String>,
     ci: Option<u32>,
+    min_cl: Option<u32>,
+    show_stage_transitions: bool,
 ) -> Result<(Option<String>, Option<u32>), VfpFrontError> {
-    let db = app_state.get_db();
-    let client = db
-        .try_get_jenkins_async_client(&mut app_state.get_stdout(), true)
-        .await
-        .map_err(|_| VfpFrontError::JenkinsClientInvalid)?;
+    let client = cli_get_jenkins_client(app_state).await?;
 
-    let db = app_state.get_mut_db();
-    let used_job_name = Some(input_job_name_with_err_handling(job_name, db)?);
+    let used_job_name = Some(input_job_name_with_err_handling(app_state, job_name).await?);
 
-    let result = watch(app_state, client, &used_job_name.clone().unwrap(), ci).await;
+    let result = match min_cl {
+        Some(min_cl) => {
+            watch_change_list(app_state, client, &used_job_name.clone().unwrap(), min_cl).await
+        }
+        None => {
+            watch(
+                app_state,
+                client,
+                &used_job_name.clone().unwrap(),
+                ci,
+                show_stage_transitions,
+            )
+            .await
+        }
+    };
 
     let success_build_number = match result {
         Ok(build_number) => {
@@ -411,90 +981,1394 @@ pub async fn cli_do_watch(
     Ok((used_job_name, success_build_number))
 }
 
-pub async fn cli_try_first_login(
+/// # cli do info
+///
+/// Show the summary of a jenkins run task.
+///
+/// Contains Inquire(input requests) and console output.
+pub async fn cli_do_info(
     app_state: &mut AppState,
-    silence: bool,
+    job_name: Option<String>,
+    ci: Option<u32>,
+    show_stages: bool,
 ) -> Result<(), VfpFrontError> {
-    let db = app_state.get_db();
-    if db.user_never_login() {
-        match cli_do_login(
-            app_state,
-            false,
-            None::<String>,
-            None::<String>,
-            None::<String>,
-            None::<String>,
-        )
-        .await
-        {
-            Ok(_) => {
-                if !silence {
-                    colored_println(
-                        &mut app_state.get_stdout(),
-                        ThemeColor::Success,
-                        JENKINS_LOGIN_RESULT,
-                    );
-                }
-                Ok(())
-            }
-            Err(e) => Err(e),
-        }
-    } else {
-        Ok(())
-    }
+    let client = cli_get_jenkins_client(app_state).await?;
+
+    let used_job_name = input_job_name_with_err_handling(app_state, job_name).await?;
+    let build_number = input_ci_for_watch(app_state, &used_job_name, ci).await?;
+
+    info(
+        app_state,
+        &client,
+        &used_job_name,
+        build_number,
+        show_stages,
+    )
+    .await
 }
 
-pub async fn cli_do_distribute(
+/// # cli do history
+///
+/// Show the recent run tasks of a job, with the statistics or an export if required.
+///
+/// Contains Inquire(input requests) and console output.
+pub async fn cli_do_history(
     app_state: &mut AppState,
-    job_name: &str,
-    src_distr_index: u32,
+    job_name: Option<String>,
+    mine: bool,
+    count: u32,
+    show_stats: bool,
+    export: Option<PathBuf>,
 ) -> Result<(), VfpFrontError> {
-    let runtime_config = default_config::runtime();
-    let db = app_state.get_db();
+    let client = cli_get_jenkins_client(app_state).await?;
 
-    let blast_path = db
-        .get_blast_path(job_name)
-        .ok_or(VfpFrontError::DistributeError(
-            ERR_BLAST_PATH_NOT_FOUND.to_string(),
-        ))?;
+    let used_job_name = input_job_name_with_err_handling(app_state, job_name).await?;
 
-    let (prefix, dirs) = infer_blast_root_dir_name(blast_path.as_path()).ok_or(
-        VfpFrontError::DistributeError(ERR_BLAST_PATH_NOT_FOUND_ANY_BLAST_PACKAGE.to_string()),
-    )?;
+    let username = if mine {
+        Some(
+            app_state
+                .get_db()
+                .get_jenkins_username()
+                .clone()
+                .ok_or(VfpFrontError::MissingParam(PARAM_USERNAME.to_string()))?,
+        )
+    } else {
+        None
+    };
 
-    let src_pt_path = blast_path
-        .join(format!("{}{}", prefix, src_distr_index))
-        .join(
-            runtime_config
-                .pt_relative_path
-                .trim_start_matches(['/', '\\']),
-        );
+    let entries = query_history(
+        Arc::new(client),
+        &used_job_name,
+        count,
+        username.as_deref(),
+        show_stats || export.is_some(),
+    )
+    .await?;
 
-    let limit = db.get_last_player_count(job_name);
-    let src_dir_name = format!("{}{}", prefix, src_distr_index);
-    let mut filtered_dirs = dirs
-        .into_iter()
-        .filter(|dir| dir != &src_dir_name)
-        .collect::<Vec<_>>();
+    print_history(app_state, &used_job_name, &entries);
 
-    let selected_dirs = if let Some(limit) = limit {
-        let take_count = limit.max(1).saturating_sub(1) as usize;
+    if show_stats {
+        print_history_stats(app_state, &HistoryStats::from_entries(&entries));
+    }
 
-        filtered_dirs.sort_by(|a, b| {
-            let parse_suffix = |dir: &str| {
-                dir.strip_prefix(prefix.as_str())
-                   .and_then(|suffix| suffix.parse::<u32>().ok())
-            };
+    if let Some(path) = export {
+        export_history(&path, &entries)?;
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Success,
+            &formatx!(HISTORY_EXPORTED, path.display()).unwrap_or_default(),
+        );
+    }
 
-            match (parse_suffix(a), parse_suffix(b)) {
-                (Some(av), Some(bv)) => av.cmp(&bv).then_with(|| a.cmp(b)),
-                (Some(_), None) => std::cmp::Ordering::Less,
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                (None, None) => a.cmp(b),
-            }
+    Ok(())
+}
+
+/// # cli do jobs
+///
+/// List the jobs on Jenkins with the status of their last build, and refresh the job cache.
+///
+/// Contains console output.
+pub async fn cli_do_jobs(
+    app_state: &mut AppState,
+    filter: Option<String>,
+) -> Result<(), VfpFrontError> {
+    let client = cli_get_jenkins_client(app_state).await?;
+
+    let entries = query_jobs(&client).await?;
+    app_state
+        .get_mut_db()
+        .set_server_job_paths(entries.iter().map(|entry| entry.path.clone()).collect());
+
+    let entries = filter_job_entries(entries, filter.as_deref())?;
+    print_jobs(app_state, &entries);
+
+    Ok(())
+}
+
+/// # cli do abort
+///
+/// Abort a jenkins run task. Your latest in-progress run task by default.
+///
+/// Contains Inquire(input requests) and console output.
+pub async fn cli_do_abort(
+    app_state: &mut AppState,
+    job_name: Option<String>,
+    ci: Option<u32>,
+) -> Result<(), VfpFrontError> {
+    let client = cli_get_jenkins_client_with_crumb(app_state).await?;
+
+    let job_name = input_job_name_with_err_handling(app_state, job_name).await?;
+
+    let (client, build_number) = match ci {
+        Some(ci) => (client, ci),
+        None => {
+            let username = app_state
+                .get_db()
+                .get_jenkins_username()
+                .clone()
+                .ok_or(VfpFrontError::MissingParam(PARAM_USERNAME.to_string()))?;
+            let arc_client = Arc::new(client);
+            let latest_info =
+                JenkinsRpcService::query_user_latest_info(arc_client.clone(), &job_name, &username)
+                    .await?;
+
+            let build_number =
+                latest_info
+                    .in_progress
+                    .map(|run| run.number)
+                    .ok_or(VfpFrontError::Custom(
+                        NO_IN_PROGRESS_RUN_TASK_OF_USER.to_string(),
+                    ))?;
+
+            (
+                Arc::try_unwrap(arc_client).map_err(|_| VfpFrontError::JenkinsClientInvalid)?,
+                build_number,
+            )
+        }
+    };
+
+    request_abort(&client, &job_name, build_number)
+        .await
+        .map_err(|e| VfpFrontError::jenkins_with_context(ERR_REQUEST_ABORT_FAILED, e))?;
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Success,
+        &formatx!(REQUEST_ABORT_SUCCESS, build_number, &job_name).unwrap_or_default(),
+    );
+
+    Ok(())
+}
+
+/// # cli do rebuild
+///
+/// Re-trigger a jenkins run task with the exact params it used. Your latest run task by default.
+///
+/// Contains Inquire(input requests) and console output.
+pub async fn cli_do_rebuild(
+    app_state: &mut AppState,
+    job_name: Option<String>,
+    ci: Option<u32>,
+    param_pairs: Vec<(String, Value)>,
+) -> Result<(), VfpFrontError> {
+    let client = cli_get_jenkins_client_with_crumb(app_state).await?;
+
+    let job_name = input_job_name_with_err_handling(app_state, job_name).await?;
+
+    let (client, build_number) = match ci {
+        Some(ci) => (client, ci),
+        None => {
+            let username = app_state
+                .get_db()
+                .get_jenkins_username()
+                .clone()
+                .ok_or(VfpFrontError::MissingParam(PARAM_USERNAME.to_string()))?;
+            let arc_client = Arc::new(client);
+            let latest_info =
+                JenkinsRpcService::query_user_latest_info(arc_client.clone(), &job_name, &username)
+                    .await?;
+
+            let build_number = [
+                latest_info.in_progress,
+                latest_info.latest_success,
+                latest_info.failed,
+            ]
+            .into_iter()
+            .flatten()
+            .map(|run| run.number)
+            .max()
+            .ok_or(VfpFrontError::Custom(NO_RUN_TASK_OF_USER.to_string()))?;
+
+            (
+                Arc::try_unwrap(arc_client).map_err(|_| VfpFrontError::JenkinsClientInvalid)?,
+                build_number,
+            )
+        }
+    };
+
+    let run_info = query_run_info(&client, &job_name, build_number).await?;
+    let mut build_params = run_info.get_build_params();
+    param_pairs.into_iter().for_each(|(k, v)| {
+        build_params.params.insert(k, v);
+    });
+
+    let dropped_params = run_info.get_unrepeatable_build_params();
+    if !dropped_params.is_empty() {
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Warn,
+            &formatx!(WARN_REBUILD_PARAMS_DROPPED, dropped_params.join(", ")).unwrap_or_default(),
+        );
+    }
+
+    let queue_id = request_build(&client, &job_name, &build_params, &[])
+        .await
+        .map_err(|e| VfpFrontError::jenkins_with_context(ERR_REQUEST_BUILD_FAILED, e))?;
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Success,
+        &formatx!(REQUEST_REBUILD_SUCCESS, build_number, &job_name).unwrap_or_default(),
+    );
+    print_build_params(app_state, &build_params);
+
+    let new_build_number = match queue_id {
+        Some(queue_id) => wait_for_queued_run(&client, queue_id).await,
+        None => None,
+    };
+    let jenkins_url = app_state
+        .get_db()
+        .get_jenkins_url()
+        .clone()
+        .unwrap_or_default();
+    let url = match new_build_number {
+        Some(new_build_number) => {
+            get_jenkins_workflow_run_url(&jenkins_url, &job_name, new_build_number)
+        }
+        None => {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Warn,
+                WARN_QUEUED_RUN_NOT_FOUND,
+            );
+            get_jenkins_job_url(&jenkins_url, &job_name)
+        }
+    };
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Second,
+        &format!("{} {}", URL_OUTPUT, url),
+    );
+
+    Ok(())
+}
+
+/// # BuildParamOptions
+///
+/// How the build params are resolved, shared by every job of a build.
+pub struct BuildParamOptions {
+    pub cl: Option<u32>,
+    pub sl: Option<Shelves>,
+    pub param_pairs: Vec<(String, Value)>,
+    pub force_param_pairs: Vec<(String, Value)>,
+    pub preset: Option<String>,
+    pub interactive: bool,
+
+    /// skip the `--param` not defined by the job instead of failing,
+    /// since it may be defined by the other jobs of a fan-out build.
+    pub skip_undefined_params: bool,
+}
+
+/// # ResolvedBuild
+///
+/// The build params of a job resolved exactly as they will be sent.
+pub struct ResolvedBuild {
+    pub job_name: String,
+    pub build_params: VfpJobBuildParam,
+    pub template: VfpJobBuildParam,
+    pub param_specs: Vec<VfpBuildParamSpec>,
+
+    /// change list of the last build in memory.
+    last_cl: Option<u32>,
+
+    /// shelved change list of the last build in memory.
+    last_sl: Option<Shelves>,
+}
+
+impl ResolvedBuild {
+    /// # get params to save
+    ///
+    /// the params differing from the job defaults, with the last change list kept
+    /// if the build uses the latest one.
+    fn get_params_to_save(&self) -> VfpJobBuildParam {
+        let mut params_to_save = self.build_params.clone();
+        params_to_save
+            .retain_differing_params(&self.template)
+            .remove_secret_params(&self.param_specs);
+
+        if params_to_save.get_change_list().is_none() {
+            params_to_save.set_change_list(self.last_cl);
+        }
+        if params_to_save.get_shelve_changes().is_none() {
+            params_to_save.set_shelve_changes(self.last_sl.clone());
+        }
+
+        params_to_save
+    }
+}
+
+/// # cli query job definition
+///
+/// Query the job definition by json api, and fallback to config.xml.
+/// A default one is returned if both failed.
+pub async fn cli_query_job_definition(
+    app_state: &AppState,
+    client: &VfpJenkinsClient,
+    job_name: &str,
+) -> VfpJobDefinition {
+    let config_params_result = query_job_config_json(client, job_name)
+        .await
+        .inspect_err(|e| {
+            #[cfg(debug_assertions)]
+            {
+                colored_println(
+                    &mut app_state.get_stdout(),
+                    ThemeColor::Warn,
+                    &e.to_string(),
+                );
+            }
+        });
+
+    let config_params_result = if config_params_result.is_ok() {
+        config_params_result
+    } else {
+        let config_param_from_xml = query_job_config_xml(client, job_name)
+            .await
+            .inspect_err(|e| {
+                #[cfg(debug_assertions)]
+                {
+                    colored_println(
+                        &mut app_state.get_stdout(),
+                        ThemeColor::Warn,
+                        &e.to_string(),
+                    );
+                }
+            });
+
+        // a 403 of config.xml tells more than the json api, with the hint of the permissions.
+        match config_param_from_xml {
+            Ok(_) | Err(VfpFrontError::JobConfigMissingPermission) => config_param_from_xml,
+            Err(_) => config_params_result,
+        }
+    };
+
+    if let Err(ref e) = config_params_result {
+        e.colored_println(&mut app_state.get_stdout());
+    }
+
+    config_params_result.unwrap_or_default()
+}
+
+/// # cli resolve build params
+///
+/// Resolve the build params of a job from its definition, the memory or a preset,
+/// the change lists and the `--param` overrides.
+///
+/// ### Arguments
+///
+/// * `changes`: the change list and shelved change list. asked once and shared by later jobs.
+///
+/// Contains Inquire(input requests) and console output.
+pub async fn cli_resolve_build_params(
+    app_state: &mut AppState,
+    client: &VfpJenkinsClient,
+    job_name: String,
+    options: &BuildParamOptions,
+    changes: &mut Option<(Option<u32>, Option<Shelves>)>,
+) -> Result<ResolvedBuild, VfpFrontError> {
+    let VfpJobDefinition {
+        template,
+        param_specs,
+    } = cli_query_job_definition(app_state, client, &job_name).await;
+
+    let param_pairs = if template.from_default {
+        if !options.param_pairs.is_empty() {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Warn,
+                BUILD_PARAM_VALIDATION_SKIPPED,
+            );
+        }
+        options.param_pairs.clone()
+    } else {
+        let param_pairs = options
+            .param_pairs
+            .iter()
+            .filter(|(name, _)| {
+                !options.skip_undefined_params || param_specs.iter().any(|spec| &spec.name == name)
+            })
+            .cloned()
+            .collect();
+        validate_param_pairs(&param_specs, param_pairs).map_err(VfpFrontError::BuildParamInvalid)?
+    };
+
+    let db = app_state.get_db();
+    let mut build_params = template.clone();
+
+    let db_latest_build_param = db.get_jenkins_build_param(job_name.as_ref());
+    let last_cl = db_latest_build_param.and_then(|build_param| build_param.get_change_list());
+    let last_sl = db_latest_build_param.and_then(|build_param| build_param.get_shelve_changes());
+
+    let used_preset = match options.preset {
+        Some(ref name) => Some(db.get_build_preset(job_name.as_ref(), name).ok_or(
+            VfpFrontError::Custom(formatx!(ERR_PRESET_NOT_FOUND, name).unwrap_or_default()),
+        )?),
+        None => None,
+    };
+
+    if let Some(preset) = used_preset {
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Main,
+            &formatx!(BUILD_PRESET_APPLIED, &preset.name).unwrap_or_default(),
+        );
+    }
+
+    if let Some(db_params) = used_preset
+        .map(|preset| &preset.params)
+        .or(db_latest_build_param)
+    {
+        if build_params.from_default {
+            build_params.merge_from(db_params);
+
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Warn,
+                DB_BUILD_PARAM_DIRECTLY_ADOPTED,
+            );
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Second,
+                HINT_USE_PARAM_OPERATION,
+            );
+        } else {
+            // params without a default are not in the template, but still defined.
+            let (defined, excluded): (Vec<_>, Vec<_>) = build_params
+                .exclusive_merge_from(db_params)
+                .into_iter()
+                .partition(|(k, _)| param_specs.iter().any(|spec| &spec.name == k));
+            build_params.params.extend(defined);
+
+            if !excluded.is_empty() {
+                colored_println(
+                    &mut app_state.get_stdout(),
+                    ThemeColor::Warn,
+                    DB_BUILD_PARAM_NOT_IN_USED,
+                );
+
+                let mut stdout = app_state.get_stdout();
+                excluded.iter().for_each(|(k, v)| {
+                    colored_println(&mut stdout, ThemeColor::Second, &format!("{}: {}", k, v))
+                })
+            }
+        }
+    }
+
+    let (cl, sl) = match changes {
+        Some(changes) => changes.clone(),
+        None => {
+            let (p4_have, p4_shelves) = cli_query_p4_changes(app_state, options);
+            let cl = input_cl(options.cl, &last_cl, p4_have)?;
+            let sl = input_sl(options.sl.clone(), &last_sl, &p4_shelves)?;
+            if let Some(ref sl) = sl {
+                cli_check_shelves(app_state, sl);
+            }
+
+            changes.insert((cl, sl)).clone()
+        }
+    };
+    build_params.set_change_list(cl);
+    build_params.set_shelve_changes(sl);
+
+    param_pairs
+        .into_iter()
+        .chain(options.force_param_pairs.iter().cloned())
+        .for_each(|(k, v)| {
+            build_params.params.insert(k, v);
+        });
+
+    if options.interactive {
+        input_build_params(&param_specs, &mut build_params)?;
+    }
+
+    Ok(ResolvedBuild {
+        job_name,
+        build_params,
+        template,
+        param_specs,
+        last_cl,
+        last_sl,
+    })
+}
+
+/// # cli query p4 changes
+///
+/// Query the have change list and the shelved change lists of the user by Perforce,
+/// only for those not given by command line. Failures are warned and ignored.
+fn cli_query_p4_changes(
+    app_state: &AppState,
+    options: &BuildParamOptions,
+) -> (Option<u32>, Vec<P4Change>) {
+    let depot_path = &default_config::runtime().p4_depot_path;
+    if depot_path.is_empty() {
+        return (None, Vec::new());
+    }
+
+    let p4_have = if options.cl.is_none() {
+        query_have_change_list(&P4Cli, depot_path)
+            .inspect_err(|e| {
+                colored_println(
+                    &mut app_state.get_stdout(),
+                    ThemeColor::Warn,
+                    &e.to_string(),
+                )
+            })
+            .unwrap_or_default()
+    } else {
+        None
+    };
+
+    // skip the shelves if p4 is not available at all.
+    let p4_shelves = if options.sl.is_none() && (p4_have.is_some() || options.cl.is_some()) {
+        query_my_shelved_changes(&P4Cli)
+            .inspect_err(|e| {
+                colored_println(
+                    &mut app_state.get_stdout(),
+                    ThemeColor::Warn,
+                    &e.to_string(),
+                )
+            })
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    (p4_have, p4_shelves)
+}
+
+/// # cli check shelves
+///
+/// Warn the shelved change lists that do not exist or are already submitted.
+fn cli_check_shelves(app_state: &AppState, shelves: &Shelves) {
+    if default_config::runtime().p4_depot_path.is_empty() {
+        return;
+    }
+
+    match check_shelves(&P4Cli, shelves) {
+        Ok(problems) => problems.iter().for_each(|problem| {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Warn,
+                &problem.to_string(),
+            )
+        }),
+        Err(e) => colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Warn,
+            &e.to_string(),
+        ),
+    }
+}
+
+/// # cli print dry run
+///
+/// Print the resolved build params and their changes from the job defaults and the last build.
+pub fn cli_print_dry_run(app_state: &AppState, resolved: &ResolvedBuild) {
+    let mut params_for_show = resolved.build_params.clone();
+    params_for_show.remove_secret_params(&resolved.param_specs);
+    let mut template_for_show = resolved.template.clone();
+    template_for_show.remove_secret_params(&resolved.param_specs);
+
+    print_build_params(app_state, &params_for_show);
+    print_build_param_changes(
+        app_state,
+        DRY_RUN_CHANGES_FROM_DEFAULT,
+        &params_for_show.get_changes_from(&template_for_show),
+    );
+
+    // the last build is resolved on the template, as the memory keeps only the changes.
+    match app_state
+        .get_db()
+        .get_jenkins_build_param(resolved.job_name.as_ref())
+    {
+        Some(db_params) => {
+            let mut last_build_params = template_for_show;
+            last_build_params.merge_from(db_params);
+            print_build_param_changes(
+                app_state,
+                DRY_RUN_CHANGES_FROM_LAST,
+                &params_for_show.get_changes_from(&last_build_params),
+            );
+        }
+        None => {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Main,
+                DRY_RUN_CHANGES_FROM_LAST,
+            );
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Second,
+                DRY_RUN_NO_LAST_BUILD,
+            );
+        }
+    }
+}
+
+/// # cli save build params
+///
+/// Save the resolved build params into memory, and as a preset if `save_preset` is given.
+pub fn cli_save_build_params(
+    app_state: &mut AppState,
+    resolved: &ResolvedBuild,
+    save_preset: Option<&str>,
+) {
+    let build_params_to_save = resolved.get_params_to_save();
+
+    let db = app_state.get_mut_db();
+    if let Some(name) = save_preset {
+        let mut preset_params = build_params_to_save.clone();
+        preset_params.remove_change_params();
+        db.set_build_preset(resolved.job_name.as_ref(), name, preset_params);
+    }
+    db.set_jenkins_build_param(resolved.job_name.as_ref(), Some(build_params_to_save));
+    app_state.commit(false);
+
+    if let Some(name) = save_preset {
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Success,
+            &formatx!(BUILD_PRESET_SAVED, name).unwrap_or_default(),
+        );
+    }
+}
+
+/// # cli request build
+///
+/// Trigger the build with the resolved build params, and print them.
+/// Returns the id of the queue item if jenkins tells it.
+pub async fn cli_request_build(
+    app_state: &AppState,
+    client: &VfpJenkinsClient,
+    resolved: &ResolvedBuild,
+) -> Result<Option<u64>, VfpFrontError> {
+    let queue_id = request_build(
+        client,
+        &resolved.job_name,
+        &resolved.build_params,
+        &resolved.param_specs,
+    )
+    .await
+    .map_err(|e| VfpFrontError::jenkins_with_context(ERR_REQUEST_BUILD_FAILED, e))?;
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Success,
+        &formatx!(REQUEST_BUILD_SUCCESS, &resolved.job_name).unwrap_or_default(),
+    );
+    print_build_params(
+        app_state,
+        resolved
+            .build_params
+            .clone()
+            .remove_secret_params(&resolved.param_specs),
+    );
+
+    Ok(queue_id)
+}
+
+/// # cli fetch used change list
+///
+/// Remember the change list a run task actually used, when it was built with the latest one.
+pub async fn cli_fetch_used_change_list(
+    app_state: &mut AppState,
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    build_number: u32,
+) {
+    let mut trial_count = 2;
+    loop {
+        match query_run_info(client, job_name, build_number).await {
+            Ok(workflow_run) => {
+                if let Some(changelist) = workflow_run.get_change_list_in_build_meta_data() {
+                    let db = app_state.get_mut_db();
+                    if let Some(params) = db.get_mut_jenkins_build_param(job_name) {
+                        params.set_change_list(Some(changelist));
+                    }
+                    colored_println(
+                        &mut app_state.get_stdout(),
+                        ThemeColor::Second,
+                        &formatx!(AUTO_FETCH_LATEST_USED_CL, changelist).unwrap_or_default(),
+                    );
+                    app_state.commit(false);
+                    break;
+                } else if trial_count == 0 {
+                    colored_println(
+                        &mut app_state.get_stdout(),
+                        ThemeColor::Warn,
+                        AUTO_FETCH_LATEST_USED_CL_FAILED,
+                    );
+                    break;
+                }
+            }
+            Err(e) => {
+                #[cfg(debug_assertions)]
+                {
+                    colored_println(
+                        &mut app_state.get_stdout(),
+                        ThemeColor::Error,
+                        &format!("Query build info failed because: {}", e),
+                    );
+                }
+            }
+        }
+
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Second,
+            AUTO_FETCH_LATEST_USED_CL_FAILED_AND_RETRY,
+        );
+
+        trial_count -= 1;
+        tokio::time::sleep(Duration::from_secs_f32(0.5)).await;
+    }
+}
+
+/// # cli do build dry run
+///
+/// Resolve the build params of the jobs and print them without triggering the builds.
+/// Nothing is saved, not even the jobs as the recently used ones.
+///
+/// Contains Inquire(input requests) and console output.
+pub async fn cli_do_build_dry_run(
+    app_state: &mut AppState,
+    client: &VfpJenkinsClient,
+    job_names: Vec<String>,
+    options: &BuildParamOptions,
+) -> Result<(), VfpFrontError> {
+    let fan_out = job_names.len() > 1;
+    let (resolved_builds, _) = cli_resolve_builds(app_state, client, job_names, options).await?;
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Warn,
+        DRY_RUN_NOT_TRIGGERED,
+    );
+    for resolved in resolved_builds.iter() {
+        if fan_out {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Main,
+                &formatx!(FAN_OUT_JOB_TITLE, &resolved.job_name).unwrap_or_default(),
+            );
+        }
+        cli_print_dry_run(app_state, resolved);
+    }
+
+    Ok(())
+}
+
+/// # cli resolve builds
+///
+/// Resolve the build params of the jobs, which share the change lists asked once.
+/// With several jobs, every `--param` should be defined by one of them at least.
+///
+/// Contains Inquire(input requests) and console output.
+async fn cli_resolve_builds(
+    app_state: &mut AppState,
+    client: &VfpJenkinsClient,
+    job_names: Vec<String>,
+    options: &BuildParamOptions,
+) -> Result<(Vec<ResolvedBuild>, Option<(Option<u32>, Option<Shelves>)>), VfpFrontError> {
+    let fan_out = job_names.len() > 1;
+    let mut changes = None;
+    let mut resolved_builds = Vec::new();
+    for job_name in job_names {
+        if fan_out {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Main,
+                &formatx!(FAN_OUT_JOB_TITLE, &job_name).unwrap_or_default(),
+            );
+        }
+        resolved_builds.push(
+            cli_resolve_build_params(app_state, client, job_name, options, &mut changes).await?,
+        );
+    }
+
+    // params skipped by every job are not defined anywhere.
+    if fan_out
+        && resolved_builds
+            .iter()
+            .all(|resolved| !resolved.template.from_default)
+    {
+        let all_specs: Vec<VfpBuildParamSpec> = resolved_builds
+            .iter()
+            .flat_map(|resolved| resolved.param_specs.iter().cloned())
+            .collect();
+        validate_param_pairs(&all_specs, options.param_pairs.clone())
+            .map_err(VfpFrontError::BuildParamInvalid)?;
+    }
+
+    Ok((resolved_builds, changes))
+}
+
+/// # cli do build fan out
+///
+/// Build several jobs with the shared change lists and params,
+/// then watch them concurrently and extract each success package into the blast path of its job.
+///
+/// Contains Inquire(input requests) and console output.
+#[allow(clippy::too_many_arguments)]
+pub async fn cli_do_build_fan_out(
+    app_state: &mut AppState,
+    client: VfpJenkinsClient,
+    job_names: Vec<String>,
+    options: BuildParamOptions,
+    save_preset: Option<String>,
+    no_watch_and_extract: bool,
+    no_extract: bool,
+    extract_params: ExtractParams,
+) -> Result<(), VfpFrontError> {
+    let (resolved_builds, changes) =
+        cli_resolve_builds(app_state, &client, job_names, &options).await?;
+
+    // params of a job are saved only if its build is triggered.
+    let mut queue_ids = Vec::new();
+    for resolved in resolved_builds.iter() {
+        app_state
+            .get_mut_db()
+            .insert_job_name(resolved.job_name.as_str());
+        queue_ids.push(cli_request_build(app_state, &client, resolved).await?);
+        cli_save_build_params(app_state, resolved, save_preset.as_deref());
+    }
+
+    if no_watch_and_extract {
+        return Ok(());
+    }
+
+    let need_query_used_cl = changes.is_some_and(|(cl, _)| cl.is_none());
+    let arc_client = Arc::new(client);
+    let mut runs = Vec::new();
+    for (resolved, queue_id) in resolved_builds.into_iter().zip(queue_ids) {
+        match cli_find_triggered_run(app_state, &arc_client, &resolved.job_name, queue_id).await {
+            Some(build_number) => runs.push((resolved.job_name, build_number)),
+            None => colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Warn,
+                &formatx!(FAN_OUT_RUN_TASK_NOT_FOUND, &resolved.job_name).unwrap_or_default(),
+            ),
+        }
+    }
+
+    cli_do_watch_all(
+        app_state,
+        arc_client,
+        runs,
+        need_query_used_cl,
+        (!no_extract).then_some(extract_params),
+    )
+    .await
+}
+
+/// # cli find triggered run
+///
+/// Find the run task of the build just triggered by its queue item.
+/// It waits for a while, since the build may be still in the queue.
+pub async fn cli_find_triggered_run(
+    app_state: &AppState,
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    queue_id: Option<u64>,
+) -> Option<u32> {
+    let build_number = wait_for_queued_run(client, queue_id?).await?;
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Second,
+        &format!(
+            "{} {}",
+            URL_OUTPUT,
+            get_jenkins_workflow_run_url(
+                app_state.get_db().get_jenkins_url().as_ref().unwrap(),
+                job_name,
+                build_number,
+            )
+        ),
+    );
+
+    Some(build_number)
+}
+
+/// # cli do watch all
+///
+/// Watch several run tasks concurrently.
+/// Each success package is extracted into the blast path of its job as soon as it finishes.
+///
+/// Contains Inquire(input requests) and console output.
+pub async fn cli_do_watch_all(
+    app_state: &mut AppState,
+    client: Arc<VfpJenkinsClient>,
+    runs: Vec<(String, u32)>,
+    need_query_used_cl: bool,
+    extract_params: Option<ExtractParams>,
+) -> Result<(), VfpFrontError> {
+    let mut pending = runs;
+    let mut failed_job_names = Vec::new();
+    let mut query_failure_counts: HashMap<String, u32> = HashMap::new();
+    let mut progress_line_count = 0;
+
+    while !pending.is_empty() {
+        let statuses = query_reasoned_run_statuses(client.clone(), &pending).await;
+
+        for _ in 0..progress_line_count {
+            clean_one_line(&mut app_state.get_stdout());
+        }
+
+        let mut in_progress = Vec::new();
+        for ((job_name, build_number), status) in pending.into_iter().zip(statuses) {
+            if status.is_ok() {
+                query_failure_counts.remove(&job_name);
+            }
+            match status {
+                Ok(ReasonedRunStatus::Processing(progress)) => {
+                    in_progress.push((job_name, build_number, progress));
+                }
+                Ok(ReasonedRunStatus::Success) => {
+                    colored_println(
+                        &mut app_state.get_stdout(),
+                        ThemeColor::Success,
+                        &formatx!(WATCHING_RUN_TASK_SUCCESS, build_number, &job_name)
+                            .unwrap_or_default(),
+                    );
+                    toast("Watch", vec![RUN_TASK_COMPLETED]);
+
+                    if need_query_used_cl {
+                        cli_fetch_used_change_list(app_state, &client, &job_name, build_number)
+                            .await;
+                    }
+
+                    if let Some(ref extract_params) = extract_params {
+                        // the blast path of each job is used, instead of a shared dest.
+                        let extract_params = ExtractParams {
+                            dest: None,
+                            ..extract_params.clone()
+                        };
+                        if let Err(e) = cli_do_extract(
+                            app_state,
+                            Some(job_name),
+                            Some(build_number),
+                            extract_params,
+                            true,
+                        )
+                        .await
+                        {
+                            e.colored_println(&mut app_state.get_stdout());
+                        }
+                    }
+                }
+                Ok(ReasonedRunStatus::Failure(failure)) => {
+                    VfpFrontError::RunTaskBuildFailed {
+                        build_number,
+                        run_url: get_jenkins_workflow_run_url(
+                            app_state.get_db().get_jenkins_url().as_ref().unwrap(),
+                            &job_name,
+                            build_number,
+                        ),
+                        job_name: job_name.clone(),
+                        failed_stage: failure.failed_stage,
+                        log: failure.log,
+                    }
+                    .colored_println(&mut app_state.get_stdout());
+                    failed_job_names.push(job_name);
+                }
+                Err(e) => {
+                    let failure_count = query_failure_counts.entry(job_name.clone()).or_default();
+                    *failure_count += 1;
+                    if *failure_count < default_config::WATCH_MAX_QUERY_FAILURE_COUNT {
+                        // query again in the next round.
+                        in_progress.push((job_name, build_number, None));
+                    } else {
+                        colored_println(
+                            &mut app_state.get_stdout(),
+                            ThemeColor::Error,
+                            &formatx!(
+                                ERR_WATCH_RUN_TASK_QUERY_FAILED,
+                                build_number,
+                                &job_name,
+                                *failure_count,
+                                e
+                            )
+                            .unwrap_or_default(),
+                        );
+                        failed_job_names.push(job_name);
+                    }
+                }
+            }
+        }
+
+        progress_line_count = in_progress.len();
+        pending = in_progress
+            .into_iter()
+            .map(|(job_name, build_number, progress)| {
+                colored_println(
+                    &mut app_state.get_stdout(),
+                    ThemeColor::Warn,
+                    &(formatx!(
+                        WATCHING_RUN_TASK_IN_PROGRESS,
+                        build_number,
+                        &job_name,
+                        Local::now().format("%Y-%m-%d %H:%M:%S")
+                    )
+                    .unwrap_or_default()
+                        + get_progress_hint(progress.as_ref(), None).as_str()),
+                );
+                (job_name, build_number)
+            })
+            .collect();
+
+        if !pending.is_empty() {
+            tokio::time::sleep(Duration::from_secs(default_config::WATCH_INTERVAL)).await;
+        }
+    }
+
+    if failed_job_names.is_empty() {
+        Ok(())
+    } else {
+        Err(VfpFrontError::Custom(
+            formatx!(ERR_FAN_OUT_RUN_TASKS_FAILED, failed_job_names.join(", ")).unwrap_or_default(),
+        ))
+    }
+}
+
+/// # cli get jenkins client with crumb
+///
+/// Get a logged in jenkins client which is able to send POST requests.
+/// A crumb is attached if the client is authentic by password.
+pub async fn cli_get_jenkins_client_with_crumb(
+    app_state: &mut AppState,
+) -> Result<VfpJenkinsClient, VfpFrontError> {
+    let client = cli_get_jenkins_client(app_state).await?;
+    let crumb_result = match client {
+        VfpJenkinsClient::PwdClient(ref client) => client.attach_crumb().await,
+        VfpJenkinsClient::SessionClient(ref client) if client.needs_crumb() => {
+            client.attach_crumb().await
+        }
+        _ => Ok(()),
+    };
+    crumb_result.map_err(|e| {
+        VfpFrontError::Custom(
+            formatx!(ERR_JENKINS_CLIENT_GET_CRUMB_FAILED, e.to_string()).unwrap_or_default(),
+        )
+    })?;
+
+    Ok(client)
+}
+
+/// # parse param pairs
+///
+/// Parse the repeated `--param <PARAM_NAME> <PARAM_VALUE>` into build params.
+pub fn parse_param_pairs(params: Vec<String>) -> Result<Vec<(String, Value)>, VfpFrontError> {
+    if !params.len().is_multiple_of(2) {
+        return Err(VfpFrontError::Custom(ERR_NEED_EVEN_PARAM.to_string()));
+    }
+
+    Ok(params
+        .chunks(2)
+        .map(|chunk| (chunk[0].clone(), chunk[1].clone()))
+        .map(|(k, v)| {
+            if let Ok(val) = v.parse::<bool>() {
+                (k, Value::Bool(val))
+            } else {
+                (k, Value::String(v))
+            }
+        })
+        .collect())
+}
+
+/// # parse min change list
+///
+/// Parse the `--cl` of watch like `>=532097`. a bare number means the same.
+pub fn parse_min_change_list(value: &str) -> Result<u32, String> {
+    value
+        .trim()
+        .trim_start_matches(">=")
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| ERR_INVALID_MIN_CHANGE_LIST.to_string())
+}
+
+/// # print build param changes
+///
+/// print the added, removed and changed params under the title.
+pub fn print_build_param_changes(
+    app_state: &AppState,
+    title: &str,
+    changes: &[VfpBuildParamChange],
+) {
+    let mut stdout = app_state.get_stdout();
+    colored_println(&mut stdout, ThemeColor::Main, title);
+
+    if changes.is_empty() {
+        colored_println(&mut stdout, ThemeColor::Second, DRY_RUN_NO_CHANGE);
+        return;
+    }
+
+    for change in changes {
+        let (color, line) = match change {
+            VfpBuildParamChange::Added { name, value } => (
+                ThemeColor::Success,
+                formatx!(BUILD_PARAM_ADDED, name, value),
+            ),
+            VfpBuildParamChange::Removed { name, value } => (
+                ThemeColor::Error,
+                formatx!(BUILD_PARAM_REMOVED, name, value),
+            ),
+            VfpBuildParamChange::Changed { name, from, to } => (
+                ThemeColor::Warn,
+                formatx!(BUILD_PARAM_CHANGED, name, from, to),
+            ),
+        };
+        colored_println(&mut stdout, color, &line.unwrap_or_default());
+    }
+}
+
+/// # print build params
+///
+/// Print the build params sorted by type then name.
+pub fn print_build_params(app_state: &AppState, build_params: &VfpJobBuildParam) {
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Main,
+        BUILD_USED_PARAMS,
+    );
+
+    let mut sorted_params_for_show: Vec<(&String, &Value)> = build_params.params.iter().collect();
+
+    sorted_params_for_show.sort_by(|&(lk, lv), &(rk, rv)| {
+        if (lv.is_string() && rv.is_string()) || (lv.is_boolean() && rv.is_boolean()) {
+            lk.cmp(rk)
+        } else if lv.is_string() {
+            std::cmp::Ordering::Less
+        } else if rv.is_string() {
+            std::cmp::Ordering::Greater
+        } else if lv.is_boolean() {
+            std::cmp::Ordering::Less
+        } else if rv.is_boolean() {
+            std::cmp::Ordering::Greater
+        } else {
+            lk.cmp(rk)
+        }
+    });
+    sorted_params_for_show.iter().for_each(|(k, v)| {
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Main,
+            &format!("{}: {}", k, v),
+        );
+    });
+}
+
+/// # cli do preset list
+///
+/// List build param presets of a job.
+pub async fn cli_do_preset_list(
+    app_state: &mut AppState,
+    job_name: Option<String>,
+) -> Result<(), VfpFrontError> {
+    let job_name = input_job_name_with_err_handling(app_state, job_name).await?;
+
+    let db = app_state.get_db();
+    let presets = db.get_build_presets(&job_name);
+    let mut stdout = app_state.get_stdout();
+    if presets.is_empty() {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Warn,
+            &formatx!(NO_PRESET_OF_JOB, &job_name).unwrap_or_default(),
+        );
+        return Ok(());
+    }
+
+    colored_println(
+        &mut stdout,
+        ThemeColor::Main,
+        &formatx!(PRESET_LIST_TITLE, &job_name).unwrap_or_default(),
+    );
+    presets.iter().for_each(|preset| {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Second,
+            &formatx!(PRESET_LIST_ITEM, &preset.name, preset.params.params.len())
+                .unwrap_or_default(),
+        );
+    });
+
+    Ok(())
+}
+
+/// # cli do preset show
+///
+/// Show params of a build param preset.
+pub async fn cli_do_preset_show(
+    app_state: &mut AppState,
+    job_name: Option<String>,
+    name: &str,
+) -> Result<(), VfpFrontError> {
+    let job_name = input_job_name_with_err_handling(app_state, job_name).await?;
+
+    let preset =
+        app_state
+            .get_db()
+            .get_build_preset(&job_name, name)
+            .ok_or(VfpFrontError::Custom(
+                formatx!(ERR_PRESET_NOT_FOUND, name).unwrap_or_default(),
+            ))?;
+
+    print_build_params(app_state, &preset.params);
+
+    Ok(())
+}
+
+/// # cli do preset rm
+///
+/// Remove a build param preset.
+pub async fn cli_do_preset_rm(
+    app_state: &mut AppState,
+    job_name: Option<String>,
+    name: &str,
+) -> Result<(), VfpFrontError> {
+    let job_name = input_job_name_with_err_handling(app_state, job_name).await?;
+
+    let db = app_state.get_mut_db();
+    if !db.remove_build_preset(&job_name, name) {
+        return Err(VfpFrontError::Custom(
+            formatx!(ERR_PRESET_NOT_FOUND, name).unwrap_or_default(),
+        ));
+    }
+    app_state.commit(false);
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Success,
+        &formatx!(PRESET_REMOVED, name).unwrap_or_default(),
+    );
+
+    Ok(())
+}
+
+pub async fn cli_try_first_login(
+    app_state: &mut AppState,
+    silence: bool,
+) -> Result<(), VfpFrontError> {
+    if app_state.get_db().user_never_login() {
+        cli_do_login(
+            app_state,
+            false,
+            None::<String>,
+            None::<String>,
+            LoginSecret::default(),
+        )
+        .await?;
+        if !silence {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Success,
+                &formatx!(JENKINS_LOGIN_RESULT, app_state.get_db().get_profile_name())
+                    .unwrap_or_default(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// # cli get jenkins client
+///
+/// The checked client of the profile in use.
+/// An expired session cannot be renewed by fp, so log in again then.
+async fn cli_get_jenkins_client(
+    app_state: &mut AppState,
+) -> Result<VfpJenkinsClient, VfpFrontError> {
+    let client = app_state
+        .get_db()
+        .try_get_jenkins_async_client(&mut app_state.get_stdout(), true)
+        .await;
+
+    match client {
+        Ok(client) => Ok(client),
+        Err(e) if JenkinsErrorKind::from(&e) == JenkinsErrorKind::SessionExpired => {
+            if let Some(e @ JenkinsAuthError::SessionSourceFailed(_)) =
+                JenkinsAuthError::from_error(&e)
+            {
+                colored_println(
+                    &mut app_state.get_stdout(),
+                    ThemeColor::Warn,
+                    &e.to_string(),
+                );
+            }
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Warn,
+                WARN_JENKINS_SESSION_EXPIRED,
+            );
+            cli_do_login(
+                app_state,
+                false,
+                None::<String>,
+                None::<String>,
+                LoginSecret::default(),
+            )
+            .await
+        }
+        Err(_) => Err(VfpFrontError::JenkinsClientInvalid),
+    }
+}
+
+pub async fn cli_do_distribute(
+    app_state: &mut AppState,
+    job_name: &str,
+    src_distr_index: u32,
+) -> Result<(), VfpFrontError> {
+    let runtime_config = default_config::runtime_for_job(job_name);
+    let db = app_state.get_db();
+
+    let blast_path = db
+        .get_blast_path(job_name)
+        .ok_or(VfpFrontError::DistributeError(
+            ERR_BLAST_PATH_NOT_FOUND.to_string(),
+        ))?;
+
+    let (prefix, dirs) = infer_blast_root_dir_name(blast_path.as_path()).ok_or(
+        VfpFrontError::DistributeError(ERR_BLAST_PATH_NOT_FOUND_ANY_BLAST_PACKAGE.to_string()),
+    )?;
+
+    let src_pt_path = blast_path
+        .join(format!("{}{}", prefix, src_distr_index))
+        .join(
+            runtime_config
+                .pt_relative_path
+                .trim_start_matches(['/', '\\']),
+        );
+
+    let limit = db.get_last_player_count(job_name);
+    let src_dir_name = format!("{}{}", prefix, src_distr_index);
+    let mut filtered_dirs = dirs
+        .into_iter()
+        .filter(|dir| dir != &src_dir_name)
+        .collect::<Vec<_>>();
+
+    let selected_dirs = if let Some(limit) = limit {
+        let take_count = limit.max(1).saturating_sub(1) as usize;
+
+        filtered_dirs.sort_by(|a, b| {
+            let parse_suffix = |dir: &str| {
+                dir.strip_prefix(prefix.as_str())
+                    .and_then(|suffix| suffix.parse::<u32>().ok())
+            };
+
+            match (parse_suffix(a), parse_suffix(b)) {
+                (Some(av), Some(bv)) => av.cmp(&bv).then_with(|| a.cmp(b)),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => a.cmp(b),
+            }
         });
 
-        filtered_dirs.into_iter().take(take_count).collect::<Vec<_>>()
+        filtered_dirs
+            .into_iter()
+            .take(take_count)
+            .collect::<Vec<_>>()
     } else {
         filtered_dirs
     };
@@ -502,13 +2376,11 @@ pub async fn cli_do_distribute(
     let dest_pt_paths = selected_dirs
         .into_iter()
         .map(|path| {
-            blast_path
-                .join(path)
-                .join(
-                    runtime_config
-                        .pt_relative_path
-                        .trim_start_matches(['/', '\\']),
-                )
+            blast_path.join(path).join(
+                runtime_config
+                    .pt_relative_path
+                    .trim_start_matches(['/', '\\']),
+            )
         })
         .collect::<Vec<_>>();
 
@@ -518,7 +2390,7 @@ pub async fn cli_do_distribute(
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn cli_do_run(
+pub async fn cli_do_run(
     app_state: &mut AppState,
     job_name: Option<String>,
     dest: Option<PathBuf>,
@@ -530,11 +2402,10 @@ pub fn cli_do_run(
     force: bool,
     server: Option<String>,
 ) -> Result<(), VfpFrontError> {
-    let runtime_config = default_config::runtime();
+    let job_name = input_job_name_with_err_handling(app_state, job_name).await?;
+    let runtime_config = default_config::runtime_for_job(job_name.as_str());
     let db = app_state.get_db();
 
-    let job_name = input_job_name_with_err_handling(job_name, db)?;
-
     let dest = input_target_path(
         dest,
         db.get_blast_path(job_name.as_str()),
@@ -542,9 +2413,9 @@ pub fn cli_do_run(
         HINT_SET_PACKAGE_NEED_EXTRACT_HOME_PATH,
         Some(ERR_INVALID_PATH),
     )
-        .map_err(|_| {
-            VfpFrontError::MissingParam(formatx!(ERR_NEED_PARAM, PARAM_DEST).unwrap_or_default())
-        })?;
+    .map_err(|_| {
+        VfpFrontError::MissingParam(formatx!(ERR_NEED_PARAM, PARAM_DEST).unwrap_or_default())
+    })?;
 
     let single = index.is_some();
 
@@ -565,8 +2436,11 @@ pub fn cli_do_run(
         None,
         runtime_config.package_file_stem.as_str(),
     );
-    let exe_file_name =
-        parse_without_input_with_default(exe_file_name, None, runtime_config.exe_file_name.as_str());
+    let exe_file_name = parse_without_input_with_default(
+        exe_file_name,
+        None,
+        runtime_config.exe_file_name.as_str(),
+    );
     let check_exe_file_name = parse_without_input_with_default(
         check_exe_file_name,
         None,
@@ -576,12 +2450,12 @@ pub fn cli_do_run(
     if single {
         if let Some(server) = server
             && let Err(e) = set_server(
-            &dest,
-            &package_file_name,
-            count_or_index,
-            runtime_config.mending_file_path.as_str(),
-            &server,
-        )
+                &dest,
+                &package_file_name,
+                count_or_index,
+                runtime_config.mending_file_path.as_str(),
+                &server,
+            )
         {
             colored_println(&mut app_state.get_stdout(), ThemeColor::Error, e.as_str());
         }
@@ -598,12 +2472,12 @@ pub fn cli_do_run(
         for i in 1..count_or_index + 1 {
             if let Some(server) = server.clone()
                 && let Err(e) = set_server(
-                &dest,
-                &package_file_name,
-                i,
-                runtime_config.mending_file_path.as_str(),
-                &server,
-            )
+                    &dest,
+                    &package_file_name,
+                    i,
+                    runtime_config.mending_file_path.as_str(),
+                    &server,
+                )
             {
                 colored_println(&mut app_state.get_stdout(), ThemeColor::Error, e.as_str());
             }
@@ -622,12 +2496,92 @@ pub fn cli_do_run(
     Ok(())
 }
 
-pub fn input_job_name_with_err_handling(
+/// # input job name with err handling
+///
+/// Input the job name. The jobs on Jenkins are queried for the prompt if the cache expired.
+pub async fn input_job_name_with_err_handling(
+    app_state: &mut AppState,
     param_val: Option<String>,
-    db: &DbDataProxy,
 ) -> Result<String, VfpFrontError> {
-    input_job_name(param_val, db).map_err(|e| match e {
+    if param_val.is_none() {
+        cli_refresh_server_job_cache(app_state).await;
+    }
+
+    input_job_name(param_val, app_state.get_db()).map_err(|e| match e {
         InquireError::OperationCanceled | InquireError::OperationInterrupted => e.into(),
         _ => VfpFrontError::MissingParam(PARAM_JOB_NAME.to_string()),
     })
 }
+
+/// # cli refresh server job cache
+///
+/// Query the jobs on Jenkins if the cache expired. Failures are ignored,
+/// the prompt then works with the expired cache.
+async fn cli_refresh_server_job_cache(app_state: &mut AppState) {
+    let db = app_state.get_db();
+    if db.user_never_login() || !db.is_server_job_cache_expired() {
+        return;
+    }
+
+    let Ok(client) = db
+        .try_get_jenkins_async_client(&mut app_state.get_stdout(), false)
+        .await
+    else {
+        return;
+    };
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Second,
+        JOBS_FETCHING,
+    );
+    if let Ok(entries) = query_jobs(&client).await {
+        app_state
+            .get_mut_db()
+            .set_server_job_paths(entries.into_iter().map(|entry| entry.path).collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jenkins::query::{new_jenkins_async_client, JenkinsCredential};
+
+    #[tokio::test]
+    async fn test_dry_run_saves_nothing() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        let mut db = crate::db::db_data_proxy::DbDataProxy::default();
+        for i in 0..default_config::MAX_JOB_RELATIVE_DATA_COUNT {
+            db.set_last_inner_version(&format!("job_{}", i), Some(1024));
+        }
+        db.save(temp_file.path()).unwrap();
+        let content = std::fs::read_to_string(temp_file.path()).unwrap();
+
+        // nothing listens there, so the job definition falls back to the defaults.
+        let credential = JenkinsCredential::ApiToken("token".to_string());
+        let client =
+            new_jenkins_async_client("http://127.0.0.1:9", "somebody", &credential).unwrap();
+        let options = BuildParamOptions {
+            cl: Some(1024),
+            sl: Some(Shelves(vec![2048])),
+            param_pairs: vec![],
+            force_param_pairs: vec![],
+            preset: None,
+            interactive: false,
+            skip_undefined_params: false,
+        };
+
+        let mut app_state = AppState::new(Some(temp_file.path()));
+        cli_do_build_dry_run(
+            &mut app_state,
+            &client,
+            vec!["new_job".to_string()],
+            &options,
+        )
+        .await
+        .unwrap();
+        app_state.commit(false);
+
+        assert_eq!(std::fs::read_to_string(temp_file.path()).unwrap(), content);
+    }
+}
@@ -24,33 +24,94 @@ pub const ERR_NEED_A_JENKINS_URL: &str = "Need a jenkins url.";
 pub const ERR_NEED_A_JENKINS_USERNAME: &str = "Need your jenkins username.";
 pub const ERR_NEED_A_JENKINS_API_TOKEN: &str = "Need your jenkins api token.";
 pub const ERR_NEED_A_JENKINS_PWD: &str = "Need your jenkins password.";
+pub const ERR_NEED_A_JENKINS_SESSION: &str = "Need your jenkins session cookie or bearer token.";
 pub const ERR_JENKINS_CLIENT_INVALID: &str =
     "Cannot connect to jenkins. Maybe you should check your password or api token.";
-pub const ERR_JENKINS_TIMEOUT: &str = "Jenkins timeout. Please check your network connection or jenkins server status.";
+pub const ERR_JENKINS_TIMEOUT: &str =
+    "Jenkins timeout. Please check your network connection or jenkins server status.";
 pub const ERR_JENKINS_CLIENT_INVALID_SIMPLE: &str = "Cannot connect to jenkins.";
 pub const ERR_JENKINS_CLIENT_INVALID_MAY_BE_API_TOKEN_INVALID: &str =
     "Url: {}.\nUsername: {}\nApi-Token: {}\nMaybe you should check your api token.";
 pub const ERR_JENKINS_CLIENT_INVALID_MAY_BE_PWD_INVALID: &str =
     "Url: {}.\nUsername: {}\nPassword: {}\nMaybe you should check your password.";
+pub const ERR_JENKINS_CLIENT_INVALID_MAY_BE_SESSION_INVALID: &str =
+    "Url: {}.\nUsername: {}\nSession: {}\nMaybe the session is expired. Copy a new one from the browser.";
 pub const ERR_JENKINS_CLIENT_GET_CRUMB_FAILED: &str = "Failed to get crumb. {}";
+pub const ERR_JENKINS_AUTH: &str = "Jenkins rejected the credentials.";
+pub const ERR_JENKINS_LOGIN_PAGE: &str = "Jenkins returned a login page instead of the data.";
+pub const ERR_JENKINS_PERMISSION: &str = "Permission denied by Jenkins: {}";
+pub const ERR_JENKINS_JOB_NOT_FOUND: &str = "Job {} is not found on Jenkins.";
+pub const ERR_JENKINS_BUILD_NOT_FOUND: &str = "Run task {} of {} is not found on Jenkins.";
+pub const ERR_JENKINS_NOT_FOUND: &str = "Not found on Jenkins: {}";
+pub const ERR_JENKINS_SERVER: &str = "Jenkins server error {}.";
+pub const ERR_JENKINS_NETWORK: &str = "Cannot reach Jenkins. {}";
+pub const ERR_JENKINS_PARSE: &str = "Unexpected response from Jenkins. {}";
+pub const ERR_JENKINS_SESSION_EXPIRED: &str = "The Jenkins session is expired.";
+pub const ERR_JENKINS_SESSION_SOURCE_FAILED: &str = "Failed to read the Jenkins session from {}";
+pub const ERR_JENKINS_UNEXPECTED_STATUS: &str = "Jenkins responded {} to {}";
+pub const HINT_JENKINS_AUTH: &str =
+    "The password or api token may be changed or expired. Use `fp login` to log in again.";
+pub const HINT_JENKINS_LOGIN_PAGE: &str =
+    "The session is expired, or an SSO is in front of Jenkins. Use `fp login` with an api token, or with the session copied from the browser by `--session`, `--session-file` or `--session-cmd`.";
+pub const HINT_JENKINS_SESSION_EXPIRED: &str =
+    "Copy a new session cookie from the browser and use `fp login`, or update the cookie file or command.";
+pub const HINT_JENKINS_PERMISSION: &str =
+    "Ask the Jenkins admin for the permission, like Job/Read to query or Job/Build to build.";
+pub const HINT_JENKINS_JOB_NOT_FOUND: &str =
+    "Check the job name, or use `fp jobs` to list the jobs. A job in a folder is like `Folder/Job`.";
+pub const HINT_JENKINS_BUILD_NOT_FOUND: &str =
+    "The run task may be discarded by Jenkins. Use `fp history` to list the run tasks.";
+pub const HINT_JENKINS_SERVER: &str = "Jenkins may be restarting or overloaded. Try again later.";
+pub const HINT_JENKINS_NETWORK: &str =
+    "Check the network, the VPN and the [http] proxy of the runtime config.";
+pub const HINT_JENKINS_PARSE: &str = "The Jenkins or its plugins may be of an unsupported version.";
+pub const HINT_JOB_CONFIG_JSON_FALLBACK: &str =
+    "config.xml needs the Job/Configure permission. The params are read from the json api first, which needs Job/Read only. Check Job/Read if that failed as well, or use --force-param.";
 pub const ERR_TOAST_SHOW_FAILED: &str = "Failed to show toast notification.";
 pub const ERR_NEED_EVEN_PARAM: &str = "You must provide sufficient parameters.";
-pub const ERR_QUERY_JOB_CONFIG_MISSING_PERMISSION: &str = "Failed to query job config. Missing permission.";
+pub const ERR_QUERY_JOB_CONFIG_MISSING_PERMISSION: &str =
+    "Failed to query job config. Missing permission.";
 pub const ERR_QUERY_JOB_CONFIG: &str = "Failed to query job config. {}";
 pub const ERR_REQUEST_BUILD_FAILED: &str = "Failed to request a build task. {}";
+pub const ERR_BUILD_PARAM_INVALID: &str = "Invalid build params, the build is not triggered:";
+pub const ERR_BUILD_PARAM_UNKNOWN: &str = "  `{}` is not a param of the job.";
+pub const ERR_BUILD_PARAM_NOT_BOOL: &str = "  `{}` needs true or false, but got \"{}\".";
+pub const ERR_BUILD_PARAM_NOT_IN_CHOICES: &str = "  `{}` does not accept \"{}\". choices: {}.";
+pub const ERR_BUILD_PARAM_NOT_RUN: &str =
+    "  `{}` needs a run like \"project#42\" or a build number, but got \"{}\".";
+pub const ERR_BUILD_PARAM_FILE_NOT_FOUND: &str =
+    "  `{}` needs a file to upload, but \"{}\" is not found.";
+pub const ERR_BUILD_PARAM_FILE_READ_FAILED: &str = "Failed to read file {} to upload: {}";
+pub const ERR_BUILD_PARAM_SUGGESTION: &str = " Did you mean: {}?";
+pub const HINT_USE_FORCE_PARAM: &str =
+    "Use --force-param <PARAM_NAME> <PARAM_VALUE> to pass a param without validation.";
+pub const BUILD_PARAM_VALIDATION_SKIPPED: &str =
+    "The job definition is not available, --param values are not validated.";
+pub const ERR_REQUEST_ABORT_FAILED: &str = "Failed to abort the run task. {}";
 pub const ERR_VERSION_PARSE_FAILED: &str = "Failed to parse version. {}";
 pub const ERR_VERSION_NOT_SUPPORT_UPDATE: &str =
     "This version does not support update. Please upgrade to a newer version great than {}.";
 pub const ERR_UPDATE_FAILED: &str = "Update failed. {}";
 pub const ERR_OPEN_FILE_FAILED: &str = "Failed to open file at {} .";
 pub const ERR_DISTRIBUTE_FAILED: &str = "Failed to distribute file. {}";
+pub const ERR_HTTP_CONFIG_INVALID: &str = "Invalid `{}` in the [http] of the runtime config. {}";
+pub const ERR_CREDENTIAL_STORE_UNAVAILABLE: &str = "Credential store is unavailable. {}";
+pub const ERR_CREDENTIAL_DECRYPT_FAILED: &str =
+    "Failed to decrypt the credential file. Maybe the passphrase is wrong.";
+pub const ERR_CREDENTIAL_FILE_FAILED: &str = "Failed to access the credential file. {}";
+pub const WARN_JENKINS_SESSION_EXPIRED: &str =
+    "The Jenkins session is expired. Please log in again.";
+pub const ERR_CREDENTIAL_SAVE_FAILED: &str =
+    "Failed to save the secret to the credential store. {} Set `credential_store = \"plaintext\"` in the runtime config to keep it in the db file.";
 pub const ERR_BLAST_PATH_NOT_FOUND: &str =
     "Failed to get blast path by job name, you should use fp extract first.";
 pub const ERR_BLAST_PATH_NOT_FOUND_ANY_BLAST_PACKAGE: &str =
     "Failed to locate blast package in path.";
 pub const ERR_SRC_PT_NOT_EXIST: &str = "Source pt path {} does not exist.";
-pub const ERR_COPY_FOR_DISTRIBUTE_FAILED: &str = "Failed to copy pt file to {}. The file might be in use.\n{}";
-pub const ERR_DEST_PATH_NOT_EXIST: &str = "Destination path `{}` does not exist or is not a directory. Skipping.";
+pub const ERR_COPY_FOR_DISTRIBUTE_FAILED: &str =
+    "Failed to copy pt file to {}. The file might be in use.\n{}";
+pub const ERR_DEST_PATH_NOT_EXIST: &str =
+    "Destination path `{}` does not exist or is not a directory. Skipping.";
 
 pub const HINT_JOB_NAME: &str = "use job:";
 pub const HINT_PLAYER_COUNT: &str = "use player count: ";
@@ -61,6 +122,7 @@ pub const HINT_NO_MY_LATEST_CI_SUFFIX: &str = "there is no success run task in {
 pub const HINT_MY_LATEST_IN_PROGRESS_CI_SUFFIX: &str = "({} in progress)";
 pub const HINT_MY_LATEST_FAIL_CI_SUFFIX: &str = "({} failed)";
 pub const HINT_LAST_USED_SUFFIX: &str = "last used";
+pub const HINT_P4_HAVE_SUFFIX: &str = "p4 have";
 pub const HINT_CUSTOM: &str = "Custom (tap enter to input)";
 pub const HINT_NOT_SET: &str = "Do not set";
 pub const HINT_SELECT_CI: &str = "use ci:";
@@ -74,6 +136,16 @@ pub const HINT_INPUT_JENKINS_USERNAME: &str = "input your jenkins username(someb
 pub const HINT_INPUT_JENKINS_API_TOKEN: &str =
     "get your jenkins api token at {}/user/{}/configure\ncopy to here:";
 pub const HINT_INPUT_JENKINS_PWD: &str = "input your jenkins password: ";
+pub const HINT_SELECT_SESSION_SOURCE: &str = "where to get the session: ";
+pub const HINT_SESSION_SOURCE_PASTE: &str = "Paste it now";
+pub const HINT_SESSION_SOURCE_FILE: &str = "Read it from a file on every run";
+pub const HINT_SESSION_SOURCE_COMMAND: &str = "Run a command printing it on every run";
+pub const HINT_INPUT_JENKINS_SESSION: &str =
+    "paste the session cookie like `JSESSIONID.abcd=xyz`, or `Bearer <TOKEN>`: ";
+pub const HINT_INPUT_JENKINS_SESSION_FILE: &str = "input the path of the file with the session: ";
+pub const HINT_INPUT_JENKINS_SESSION_COMMAND: &str = "input the command printing the session: ";
+pub const HINT_INPUT_CREDENTIAL_PASSPHRASE: &str =
+    "input the passphrase of the local credential file: ";
 pub const HINT_INPUT_SRC_BLAST_INDEX: &str = "input the index of source blast(Start from 1{}{}):";
 pub const HINT_INPUT_SRC_BLAST_INDEX_LAST_EXTRACT_COUNT: &str = ", last extracted count {}";
 pub const HINT_INPUT_SRC_BLAST_INDEX_LAST_USED: &str = ", last used {}";
@@ -81,6 +153,8 @@ pub const HINT_JENKINS_API_TOKEN_DOC: &str =
     "https://www.jenkins.io/doc/book/using/remote-access-api/";
 pub const HINT_SELECT_CL: &str = "use change list: ";
 pub const HINT_SELECT_SL: &str = "use shelved changes: ";
+pub const HINT_SELECT_P4_SHELVES: &str =
+    "select your shelved changes (select none to input manually): ";
 pub const HINT_UPGRADABLE: &str = "There is a new version available. Latest: {}. Current: {}";
 pub const HINT_WHATS_NEW: &str = "What's new: {}";
 pub const HINT_UPGRADE_OPERATION: &str = "Use `fp update` to upgrade to the latest version.";
@@ -95,6 +169,144 @@ pub const HINT_NO_VALID_PATH: &str = "no valid path";
 
 pub const LOGIN_SUCCESS_BY_PWD: &str = "Login success by password!";
 pub const LOGIN_SUCCESS_BY_API_TOKEN: &str = "Login success by api token!";
+pub const LOGIN_SUCCESS_BY_SESSION: &str = "Login success by session!";
+pub const LOGIN_CHECK_TITLE: &str = "Checking the login of profile {} ...";
+pub const ERR_LOGIN_CHECK_FAILED: &str = "The login check failed.";
+pub const DIAG_TAG_OK: &str = "[ OK ]";
+pub const DIAG_TAG_WARN: &str = "[WARN]";
+pub const DIAG_TAG_FAILED: &str = "[FAIL]";
+pub const DIAG_TAG_SKIPPED: &str = "[SKIP]";
+pub const DIAG_REST_SKIPPED: &str = "The rest checks are skipped.";
+pub const DIAG_SERVER_REACHABLE: &str = "Server {} is reachable. Jenkins {}.";
+pub const DIAG_SERVER_NOT_JENKINS: &str =
+    "Server {} responded {}, but not as Jenkins. An SSO or a proxy may be in front of it.";
+pub const DIAG_SERVER_UNREACHABLE: &str = "Server {} is unreachable. {}";
+pub const DIAG_CREDENTIAL_VALID: &str = "The credentials are valid. Authenticated as {}.";
+pub const DIAG_CREDENTIAL_ANONYMOUS: &str =
+    "The credentials are not accepted. Jenkins treats the requests as anonymous.";
+pub const DIAG_CREDENTIAL_INVALID: &str = "The credentials are invalid. {}";
+pub const DIAG_USER_EXISTS: &str = "User {} exists.";
+pub const DIAG_USER_NOT_FOUND: &str = "User {} is not found on Jenkins.";
+pub const DIAG_USER_MISMATCH: &str = "Authenticated as {}, but the username of the profile is {}.";
+pub const DIAG_USER_UNKNOWN: &str = "Cannot check user {}. {}";
+pub const DIAG_JOB_SKIPPED: &str =
+    "The job permissions are not checked without a job. Use `fp login --check -j <JOB_NAME>`.";
+pub const DIAG_JOB_READ_OK: &str = "Job/Read of {} is granted.";
+pub const DIAG_JOB_READ_MISSING: &str = "Job/Read of {} is missing, or the job does not exist. {}";
+pub const DIAG_JOB_BUILD_OK: &str = "{} is buildable.";
+pub const DIAG_JOB_BUILD_MISSING: &str = "{} is not buildable. It may be disabled, or be a folder.";
+pub const DIAG_JOB_BUILD_UNKNOWN: &str = "Cannot check whether {} is buildable. {}";
+pub const DIAG_JOB_CONFIGURE_OK: &str = "Job/Configure of {} is granted.";
+pub const DIAG_JOB_CONFIGURE_MISSING: &str =
+    "Job/Configure of {} is missing. The job definition is read from the json api only.";
+pub const DIAG_CRUMB_OK: &str = "The crumb issuer is available.";
+pub const DIAG_CRUMB_DISABLED: &str = "The crumb issuer is disabled. No crumb is needed.";
+pub const DIAG_CRUMB_FAILED: &str = "The crumb issuer failed. {}";
+pub const DIAG_TAG_INDENT: &str = "      ";
+pub const HINT_DIAG_LOGIN: &str = "Use `fp login` to log in.";
+pub const DOCTOR_TITLE: &str = "Checking the environment of fp ...";
+pub const DOCTOR_SECTION_CONFIG: &str = "== Runtime config ==";
+pub const DOCTOR_SECTION_REPO: &str = "== Repo ==";
+pub const DOCTOR_SECTION_BLAST_PATH: &str = "== Blast path ==";
+pub const DOCTOR_SECTION_TOOLS: &str = "== Process tools ==";
+pub const DOCTOR_SECTION_JENKINS: &str = "== Jenkins ==";
+pub const DOCTOR_SUMMARY: &str = "{} passed, {} warnings, {} failed.";
+pub const ERR_DOCTOR_FAILED: &str = "Some checks failed. Fix them by the hints above.";
+pub const DOCTOR_CONFIG_LOADED: &str = "Runtime config {} is loaded.";
+pub const DOCTOR_CONFIG_NOT_FOUND: &str =
+    "No runtime config is found in {}. The built-in defaults are used.";
+pub const DOCTOR_CONFIG_INVALID: &str = "Runtime config {} is invalid and ignored. {}";
+pub const HINT_DOCTOR_CONFIG_FILE: &str =
+    "Put `fp-config.toml` next to fp, or `~/.config/fp/config.toml`. See the README for the keys.";
+pub const HINT_DOCTOR_CONFIG_UNKNOWN_KEY: &str =
+    "The unknown key is ignored. Fix it with `fp config edit`, or check the keys with `fp config list`.";
+pub const DOCTOR_CONFIG_KEYS_SET: &str = "The keys of extract and run are set.";
+pub const DOCTOR_CONFIG_KEYS_MISSING: &str = "The keys are not set: {}.";
+pub const HINT_DOCTOR_CONFIG_KEYS: &str =
+    "Set them in `fp-config.toml`, or ask the maintainer of fp for a build with them.";
+pub const DOCTOR_LOCATOR_PATTERN_NO_ID: &str =
+    "`locator_pattern` {} has no `{{ID}}`, so no ci can be found by it.";
+pub const HINT_DOCTOR_LOCATOR_PATTERN: &str =
+    "Fix `locator_pattern`, where `{ID}` is the ci number and `{*}` matches anything.";
+pub const DOCTOR_REPO_NOT_SET: &str = "`repo_template` is not set.";
+pub const DOCTOR_REPO_ACCESSIBLE: &str = "Repo {} is accessible.";
+pub const DOCTOR_REPO_INACCESSIBLE: &str = "Repo {} is not accessible. {}";
+pub const HINT_DOCTOR_REPO_INACCESSIBLE: &str =
+    "Check the share is reachable and you have access to it, like by opening it in the explorer.";
+pub const DOCTOR_JOB_SKIPPED: &str =
+    "The directories of the job are not checked without a job. Use `fp doctor -j <JOB_NAME>`.";
+pub const DOCTOR_JOB_REPO_NOT_FOUND: &str = "Directory {} of the job is not found.";
+pub const HINT_DOCTOR_JOB_REPO_NOT_FOUND: &str =
+    "Check the job name, and `repo_template` which is the parent of the directories of the jobs.";
+pub const DOCTOR_LOCATOR_MATCHED: &str =
+    "{} directories in {} match `locator_pattern` {}. The latest ci is {}.";
+pub const DOCTOR_LOCATOR_MISMATCH_EXAMPLE: &str =
+    "No directory in {} matches `locator_pattern` {}, like {}.";
+pub const DOCTOR_LOCATOR_MISMATCH_EMPTY: &str = "No directory is in {}.";
+pub const DOCTOR_PACKAGE_FOUND: &str = "Package {} is found.";
+pub const DOCTOR_PACKAGE_NOT_FOUND: &str = "Package {} is not found.";
+pub const HINT_DOCTOR_PACKAGE_NOT_FOUND: &str =
+    "Fix `locator_template`, the path of the package in the directory of a ci, like `\\app.zip`.";
+pub const DOCTOR_PACKAGE_INVALID: &str = "Package {} cannot be read. {}";
+pub const DOCTOR_MENDING_FILE_FOUND: &str = "`mending_file_path` {} is in the package.";
+pub const DOCTOR_MENDING_FILE_NOT_FOUND: &str = "`mending_file_path` {} is not in package {}.";
+pub const HINT_DOCTOR_MENDING_FILE: &str =
+    "Fix `mending_file_path`, the path of the user ini in the package.";
+pub const DOCTOR_BLAST_PATH_NOT_SET: &str =
+    "No blast path is used by the job yet. It is asked by `fp extract`.";
+pub const DOCTOR_BLAST_PATH_NOT_FOUND: &str = "Blast path {} does not exist.";
+pub const DOCTOR_BLAST_PATH_WRITABLE: &str = "Blast path {} is writable.";
+pub const DOCTOR_BLAST_PATH_READONLY: &str = "Blast path {} is not writable. {}";
+pub const HINT_DOCTOR_BLAST_PATH: &str =
+    "Use another blast path by `fp extract -d <PATH>`, or fix the permission of it.";
+pub const DOCTOR_TOOLS_SKIPPED: &str = "The process tools are only used on Windows.";
+pub const DOCTOR_TOOL_FOUND: &str = "{} is found at {}.";
+pub const DOCTOR_TOOL_NOT_FOUND: &str = "{} is not found in PATH.";
+pub const HINT_DOCTOR_WMIC: &str =
+    "wmic is an optional feature of newer Windows. Add it in Settings > Optional features.";
+pub const HINT_DOCTOR_TASKKILL: &str =
+    "taskkill is in `C:\\Windows\\System32`. Check it is in PATH.";
+pub const DOCTOR_NOT_LOGGED_IN: &str = "Not logged in to Jenkins.";
+pub const CONFIG_ORIGIN_BUILD: &str = "built-in";
+pub const CONFIG_ORIGIN_ENV: &str = "env {}";
+pub const CONFIG_ORIGIN_CLI: &str = "--config";
+pub const CONFIG_SHOW_ENTRY: &str = "{} = {}";
+pub const CONFIG_SHOW_ENTRY_WITH_ORIGIN: &str = "{} = {}  ({})";
+pub const CONFIG_SHOW_UNSET: &str = "{} is not set.";
+pub const ERR_INVALID_CONFIG_OVERRIDE: &str = "Expect KEY=VALUE, like `repo_template=D:\\builds`.";
+pub const ERR_UNKNOWN_CONFIG_KEY: &str = "Unknown key `{}` of the runtime config.";
+pub const ERR_UNKNOWN_CONFIG_KEY_SUGGESTIONS: &str =
+    "Unknown key `{}` of the runtime config. Did you mean: {}?";
+pub const ERR_INVALID_CONFIG_KEY: &str =
+    "Invalid key `{}`. A nested key is like `http.timeout_secs`.";
+pub const ERR_CONFIG_REPO_NOT_FOUND: &str =
+    "Repo {} does not exist or is not accessible. Use `--force` to set it anyway.";
+pub const ERR_CONFIG_LOCATOR_PATTERN_ID: &str =
+    "`locator_pattern` {} should have exactly one `{{ID}}` for the ci number.";
+pub const ERR_CONFIG_LOCATOR_PATTERN_PLACEHOLDER: &str =
+    "Unknown placeholder {} in `locator_pattern` {}. Only `{{ID}}` and `{{*}}` are supported.";
+pub const ERR_CONFIG_INVALID_URL: &str =
+    "Invalid url {}. It should be like `https://jenkins.example.com`.";
+pub const ERR_CONFIG_INVALID_CREDENTIAL_STORE: &str =
+    "Invalid credential store {}. It should be one of {}.";
+pub const ERR_CONFIG_NOT_PER_JOB: &str = "`{}` cannot be set per job. Only these keys can: {}.";
+pub const ERR_CONFIG_NOT_IN_PROJECT: &str =
+    "`{}` is ignored in .fp.toml of a project. Set it in the user layer with `--layer user`.";
+pub const WARN_CONFIG_LAYER_SKIPPED: &str = "Runtime config {} is invalid and ignored. {}";
+pub const WARN_CONFIG_UNKNOWN_KEY_IN_FILE: &str = "{} It is ignored in {}.";
+pub const WARN_CONFIG_IGNORED_IN_PROJECT: &str =
+    "`{}` in {} is ignored. It can be set outside of the project only, like in ~/.config/fp/config.toml.";
+pub const ERR_CONFIG_NOT_TABLE: &str = "`{}` is not a table in the runtime config file.";
+pub const ERR_CONFIG_FILE_INVALID: &str = "Runtime config {} is invalid. {}";
+pub const ERR_CONFIG_LAYER_PATH: &str = "Cannot find the path of the runtime config of this layer.";
+pub const ERR_CONFIG_EDITOR_FAILED: &str = "Failed to open the editor. {}";
+pub const CONFIG_SET_SUCCESS: &str = "Set {} = {} in {}.";
+pub const CONFIG_UNSET_SUCCESS: &str = "Removed {} from {}.";
+pub const WARN_CONFIG_NOT_IN_FILE: &str = "{} is not set in {}.";
+pub const WARN_CONFIG_OVERRIDDEN: &str = "It is overridden by {} now.";
+pub const CONFIG_EDIT_OPENED: &str = "Opened {}. Run `fp doctor` to check it after editing.";
+pub const CONFIG_EDIT_VALID: &str = "{} is valid.";
+pub const ERR_INVALID_CONFIG_VALUE: &str = "Invalid value {} of `{}`.";
 pub const OPERATION_TITLE: &str = "Work at index {}.";
 pub const OPERATION_FINISHED: &str = "Finished at index {}.";
 pub const OPERATION_FAILED: &str = "Failed at index {}.";
@@ -108,23 +320,111 @@ pub const RESULT_MEND: &str = "Mend {}ms.";
 pub const OPERATION_RUN_CHECK: &str = "Checking {}";
 pub const RESULT_RUN: &str = "Instance {} is created.";
 pub const OPERATION_KILL_AND_RETRY: &str = "Killing and retrying...";
-pub const JENKINS_LOGIN_RESULT: &str = "Jenkins login success!";
+pub const JENKINS_LOGIN_RESULT: &str = "Jenkins login success! Profile: {}";
+pub const ERR_PROFILE_NOT_FOUND: &str =
+    "Server profile `{}` does not exist. Create it by `fp login --profile {}`.";
+pub const PROFILE_SELECTED_BY_URL: &str = "Using server profile `{}` for the url.";
 pub const QUERYING_USER_LATEST_CI: &str = "Querying user latest ci (It might take some time)...";
 pub const WATCHING_RUN_TASK_IN_PROGRESS_PREPARE: &str =
     "Prepare to watching run task {} of {} in progress...";
 pub const WATCHING_RUN_TASK_IN_PROGRESS: &str =
     "Watching run task {} of {} in progress...(last check at {})";
+pub const WATCHING_RUN_TASK_PROGRESS: &str = " {} {}% | elapsed {} | remaining {}";
+pub const WATCHING_RUN_TASK_PROGRESS_OVERTIME: &str = " {} {}% | elapsed {} | overtime {}";
+pub const WATCHING_RUN_TASK_PROGRESS_NO_ESTIMATE: &str = " | elapsed {}";
+pub const WATCHING_RUN_TASK_STAGE: &str = " | stage: {}";
 pub const NO_IN_PROGRESS_RUN_TASK_OF_USER: &str = "There is no in progress run task of yours.";
+pub const NO_RUN_TASK_OF_USER: &str = "There is no recent run task of yours.";
+pub const WATCHING_CHANGE_LIST_PREPARE: &str =
+    "Prepare to watching run tasks of {} until one with CL >= {} succeeds...";
+pub const WATCHING_CHANGE_LIST_IN_PROGRESS: &str =
+    "Waiting for a success run task with CL >= {} of {}. {} in progress...(last check at {})";
+pub const ERR_INVALID_MIN_CHANGE_LIST: &str = "Need a change list like >=532097 or 532097.";
 pub const WATCHING_RUN_TASK_SUCCESS: &str = "Run task {} of {} finished with SUCCESS.";
 pub const WATCHING_RUN_TASK_FAILURE: &str = "Run task {} of {} finished with FAILURE.";
+pub const WATCHING_RUN_TASK_FAILURE_AT_STAGE: &str =
+    "Run task {} of {} finished with FAILURE at stage `{}`.";
+pub const WATCHING_STAGE_STARTED: &str = "Stage `{}` started.";
+pub const WATCHING_STAGE_FINISHED: &str = "Stage `{}` finished with {} in {}.";
+pub const STAGE_ERROR_MESSAGE: &str = "{}\n";
+pub const STAGE_FLOW_NODE_LOG_TITLE: &str = "[{}] {}\n";
+pub const STAGE_FLOW_NODE_LOG_TRUNCATED: &str = "\n... (truncated, see the console output)\n";
+pub const INFO_RUN_TASK_TITLE: &str = "Run task {} of {}";
+pub const INFO_RUN_TASK_STATUS: &str = "Status: {}";
+pub const INFO_STATUS_SUCCESS: &str = "SUCCESS";
+pub const INFO_STATUS_FAILURE: &str = "FAILURE";
+pub const INFO_STATUS_IN_PROGRESS: &str = "IN PROGRESS";
+pub const INFO_RUN_TASK_USER: &str = "Started by: {}";
+pub const INFO_RUN_TASK_STARTED_AT: &str = "Started at: {}";
+pub const INFO_RUN_TASK_DURATION: &str = "Duration: {}";
+pub const INFO_RUN_TASK_ELAPSED: &str = "Elapsed: {}";
+pub const INFO_RUN_TASK_CL: &str = "Change list: {}";
+pub const INFO_STAGES_TITLE: &str = "Stages:";
+pub const HISTORY_TITLE: &str = "History of {} ({} run tasks):";
+pub const HISTORY_EMPTY: &str = "  (no run task)";
+pub const HISTORY_STATS_TITLE: &str = "Statistics of finished run tasks:";
+pub const HISTORY_STATS_SUCCESS_RATE: &str = "  Success rate: {} ({}/{})";
+pub const HISTORY_STATS_MEDIAN_DURATION: &str = "  Median duration: {}";
+pub const HISTORY_STATS_MOST_FAILED_STAGE: &str = "  Most failed stage: {} ({} times)";
+pub const HISTORY_EXPORTED: &str = "History exported to {}.";
+pub const ERR_HISTORY_EXPORT_UNKNOWN_FORMAT: &str =
+    "Cannot export history to {}. Use a .csv or .json file.";
+pub const ERR_HISTORY_EXPORT_FAILED: &str = "Failed to export history. {}";
+pub const JOBS_TITLE: &str = "Jobs on Jenkins ({} jobs):";
+pub const JOBS_EMPTY: &str = "  (no job)";
+pub const JOBS_STATUS_UNSTABLE: &str = "UNSTABLE";
+pub const JOBS_STATUS_ABORTED: &str = "ABORTED";
+pub const JOBS_STATUS_NOT_BUILT: &str = "NOT BUILT";
+pub const JOBS_STATUS_DISABLED: &str = "DISABLED";
+pub const JOBS_BUILDING_SUFFIX: &str = "(building)";
+pub const JOBS_FETCHING: &str = "Fetching jobs from Jenkins...";
+pub const ERR_INVALID_JOB_FILTER: &str = "Invalid job filter `{}`. {}";
+pub const INFO_STAGE_ITEM: &str = "  {} {} ({})";
+pub const INFO_STAGE_VIEW_UNAVAILABLE: &str =
+    "Stage view is not available. The run task may not be a pipeline.";
 pub const RUN_TASK_CONSOLE_OUTPUT_URL: &str = "Console output url: {}";
 pub const RUN_TASK_COMPLETED: &str = "Run Task Completed with Success.";
 pub const EXTRACT_TASK_COMPLETED: &str = "All Extract Completed.";
 pub const DB_BUILD_PARAM_NOT_IN_USED: &str = "Some build param from memery not in used.";
 pub const DB_BUILD_PARAM_DIRECTLY_ADOPTED: &str =
     "Build param directly adopted because of template is invalid.";
-pub const REQUEST_BUILD_SUCCESS: &str = "Request build success.";
+pub const REQUEST_BUILD_SUCCESS: &str = "Request build of {} success.";
+pub const REQUEST_ABORT_SUCCESS: &str = "Run task {} of {} aborted.";
+pub const REQUEST_REBUILD_SUCCESS: &str = "Request rebuild of run task {} of {} success.";
+pub const WARN_REBUILD_PARAMS_DROPPED: &str =
+    "Password and file params can not be sent again, they are dropped: {}";
+pub const WARN_QUEUED_RUN_NOT_FOUND: &str =
+    "The run task is still waiting in the queue. Showing the job url instead.";
 pub const BUILD_USED_PARAMS: &str = "Build used params:";
+pub const DRY_RUN_NOT_TRIGGERED: &str = "Dry run, the build is not triggered and nothing is saved.";
+pub const DRY_RUN_CHANGES_FROM_DEFAULT: &str = "Changes from the job defaults:";
+pub const DRY_RUN_CHANGES_FROM_LAST: &str = "Changes from the last build:";
+pub const DRY_RUN_NO_CHANGE: &str = "  (no change)";
+pub const DRY_RUN_NO_LAST_BUILD: &str = "  (no last build params in memory)";
+pub const BUILD_PARAM_ADDED: &str = "  + {}: {}";
+pub const BUILD_PARAM_REMOVED: &str = "  - {}: {}";
+pub const BUILD_PARAM_CHANGED: &str = "  ~ {}: {} -> {}";
+pub const HINT_INPUT_NECESSARY_PARAM: &str = "{} (necessary)";
+pub const HINT_FILE_NOT_FOUND: &str = "File not found.";
+pub const BUILD_PRESET_APPLIED: &str = "Preset `{}` applied.";
+pub const BUILD_PRESET_SAVED: &str = "Preset `{}` saved.";
+pub const PRESET_LIST_TITLE: &str = "Presets of {}:";
+pub const PRESET_LIST_ITEM: &str = "  {} ({} params)";
+pub const PRESET_REMOVED: &str = "Preset `{}` removed.";
+pub const NO_PRESET_OF_JOB: &str = "There is no preset of {}.";
+pub const ERR_PRESET_NOT_FOUND: &str = "Preset `{}` not found.";
+pub const ERR_P4_NOT_AVAILABLE: &str = "Perforce is not available. {}";
+pub const ERR_P4_COMMAND_FAILED: &str = "Perforce command failed. {}";
+pub const WARN_SHELVE_NOT_FOUND: &str = "Shelved change {} does not exist.";
+pub const WARN_SHELVE_ALREADY_SUBMITTED: &str = "Shelved change {} is already submitted.";
+pub const WARN_SHELVE_NOT_SHELVED: &str = "Change {} has no shelved files.";
+pub const ERR_JOB_GROUP_NOT_FOUND: &str = "Job group `{}` not found in the runtime config.";
+pub const ERR_FAN_OUT_RUN_TASKS_FAILED: &str = "Run tasks of some jobs failed: {}";
+pub const FAN_OUT_JOB_TITLE: &str = "[{}]";
+pub const ERR_WATCH_RUN_TASK_QUERY_FAILED: &str =
+    "Stop watching run task {} of {} after {} failed queries. {}";
+pub const FAN_OUT_RUN_TASK_NOT_FOUND: &str =
+    "Cannot find the triggered run task of {}. Watch it later by `fp watch`.";
 pub const AUTO_FETCH_LATEST_USED_CL: &str = "Auto fetch your latest used CL: {}";
 pub const AUTO_FETCH_LATEST_USED_CL_FAILED: &str =
     "Auto fetch used CL in latest build failed. The data may be invalid";
@@ -138,7 +438,8 @@ pub const CURRENT_VERSION_UP_TO_DATE: &str = "Current version is up to date.";
 pub const DISABLE_AUTO_UPDATE_BECAUSE_OF_UPDATE_FAILED: &str = "Auto-Update disabled.";
 pub const NEVER_CHECK_VERSION: &str = "Never check version.";
 pub const OPEN_DB_SUCCESS: &str = "Open memory file success.";
-pub const BLAST_COUNT_TOO_LOW_WHEN_DISTRIBUTE: &str = "Blast count must more than 1 when distribute.";
+pub const BLAST_COUNT_TOO_LOW_WHEN_DISTRIBUTE: &str =
+    "Blast count must more than 1 when distribute.";
 pub const DISTRIBUTE_SUCCESS: &str = "Distribute success from {} to {}";
 
 pub const PARAM_JOB_NAME: &str = "job name";
@@ -53,6 +53,92 @@ pub(crate) fn bring_element_to_first<E>(vec: &mut [E], idx: usize) {
     }
 }
 
+/// # get readable duration
+///
+/// convert milliseconds to a string like "1h02m03s", "2m03s" or "3s".
+pub(crate) fn get_readable_duration(millis: u64) -> String {
+    let secs = millis / 1000;
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+
+    if hours > 0 {
+        format!("{}h{:02}m{:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// # strip html tags
+///
+/// keep only the text of a rich text like `<span style='color:red'>text</span>`.
+pub(crate) fn strip_html_tags(str: &str) -> String {
+    let mut result = String::with_capacity(str.len());
+    let mut in_tag = false;
+    for c in str.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    result.trim().to_string()
+}
+
+/// # get similar strings
+///
+/// candidates that look like the target, ordered by similarity.
+/// compared case-insensitively by edit distance, or by containing each other.
+pub(crate) fn get_similar_strings<'a>(
+    target: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let target = target.to_lowercase();
+    let max_distance = (target.chars().count() / 3).max(2);
+
+    let mut similar: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let distance = get_edit_distance(&target, &lower);
+            if distance <= max_distance
+                || (!target.is_empty()
+                    && !lower.is_empty()
+                    && (lower.contains(&target) || target.contains(&lower)))
+            {
+                Some((distance, candidate))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    similar.sort_by_key(|(distance, _)| *distance);
+    similar
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            curr[j + 1] = (prev[j] + usize::from(ca != *cb))
+                .min(prev[j + 1] + 1)
+                .min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+
+    prev[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -87,4 +173,41 @@ mod tests {
         super::bring_element_to_first(&mut vec_out_of_range, 5);
         assert_eq!(vec_out_of_range, vec![1, 2]);
     }
+
+    #[test]
+    fn test_get_readable_duration() {
+        assert_eq!(super::get_readable_duration(999), "0s");
+        assert_eq!(super::get_readable_duration(3_000), "3s");
+        assert_eq!(super::get_readable_duration(123_000), "2m03s");
+        assert_eq!(super::get_readable_duration(3_723_000), "1h02m03s");
+    }
+
+    #[test]
+    fn test_strip_html_tags() {
+        assert_eq!(
+            super::strip_html_tags("<span style='color:red'> Please select TestType.</span>"),
+            "Please select TestType."
+        );
+        assert_eq!(super::strip_html_tags("a < b"), "a");
+        assert_eq!(super::strip_html_tags("plain"), "plain");
+    }
+
+    #[test]
+    fn test_get_similar_strings() {
+        let candidates = ["CustomServer", "SetCustomServer", "Compile", "Changelist"];
+
+        assert_eq!(
+            super::get_similar_strings("customserver", candidates),
+            vec!["CustomServer", "SetCustomServer"]
+        );
+        assert_eq!(
+            super::get_similar_strings("CustomSever", candidates),
+            vec!["CustomServer"]
+        );
+        assert_eq!(
+            super::get_similar_strings("compile", candidates),
+            vec!["Compile"]
+        );
+        assert!(super::get_similar_strings("Publish", candidates).is_empty());
+    }
 }
@@ -0,0 +1,176 @@
+use crate::constant::log::HINT_INPUT_CREDENTIAL_PASSPHRASE;
+use crate::credential::{CredentialError, CredentialStore};
+use crate::interact::input_pwd;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// env var of the passphrase. asked on the first use if it is not set.
+pub const PASSPHRASE_ENV_KEY: &str = "FP_CREDENTIAL_PASSPHRASE";
+
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// # EncryptedFileStore
+///
+/// Secrets in a local file, encrypted by AES-256-GCM with a key derived from a passphrase.
+/// Used when the OS keyring is unavailable.
+///
+/// The file is the base64 of `salt | nonce | ciphertext`, and the plaintext is a json map.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    passphrase: OnceLock<String>,
+}
+
+impl EncryptedFileStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            passphrase: OnceLock::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_passphrase(path: PathBuf, passphrase: &str) -> Self {
+        Self {
+            path,
+            passphrase: OnceLock::from(passphrase.to_string()),
+        }
+    }
+
+    fn get_passphrase(&self) -> Result<&str, CredentialError> {
+        if let Some(passphrase) = self.passphrase.get() {
+            return Ok(passphrase);
+        }
+
+        let passphrase = match std::env::var(PASSPHRASE_ENV_KEY) {
+            Ok(passphrase) if !passphrase.is_empty() => passphrase,
+            _ => input_pwd(None, HINT_INPUT_CREDENTIAL_PASSPHRASE, None)
+                .map_err(|e| CredentialError::Unavailable(e.to_string()))?,
+        };
+
+        Ok(self.passphrase.get_or_init(|| passphrase))
+    }
+
+    fn derive_key(&self, salt: &[u8]) -> Result<LessSafeKey, CredentialError> {
+        let mut key = [0u8; 32];
+        ring::pbkdf2::derive(
+            ring::pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(PBKDF2_ITERATIONS).unwrap(),
+            salt,
+            self.get_passphrase()?.as_bytes(),
+            &mut key,
+        );
+
+        UnboundKey::new(&AES_256_GCM, &key)
+            .map(LessSafeKey::new)
+            .map_err(|_| CredentialError::DecryptFailed)
+    }
+
+    fn read_secrets(&self) -> Result<HashMap<String, String>, CredentialError> {
+        if !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|e| CredentialError::FileFailed(e.to_string()))?;
+        let mut data = base64::prelude::BASE64_STANDARD
+            .decode(content.trim())
+            .map_err(|_| CredentialError::DecryptFailed)?;
+        if data.len() < SALT_LEN + NONCE_LEN {
+            return Err(CredentialError::DecryptFailed);
+        }
+
+        let mut ciphertext = data.split_off(SALT_LEN + NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&data[SALT_LEN..])
+            .map_err(|_| CredentialError::DecryptFailed)?;
+        let plaintext = self
+            .derive_key(&data[..SALT_LEN])?
+            .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+            .map_err(|_| CredentialError::DecryptFailed)?;
+
+        serde_json::from_slice(plaintext).map_err(|_| CredentialError::DecryptFailed)
+    }
+
+    fn write_secrets(&self, secrets: &HashMap<String, String>) -> Result<(), CredentialError> {
+        let rng = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut salt)
+            .and_then(|_| rng.fill(&mut nonce))
+            .map_err(|_| CredentialError::FileFailed("no random source.".to_string()))?;
+
+        let mut ciphertext =
+            serde_json::to_vec(secrets).map_err(|e| CredentialError::FileFailed(e.to_string()))?;
+        self.derive_key(&salt)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut ciphertext,
+            )
+            .map_err(|_| CredentialError::FileFailed("encryption failed.".to_string()))?;
+
+        let data = [salt.as_slice(), nonce.as_slice(), ciphertext.as_slice()].concat();
+        std::fs::write(&self.path, base64::prelude::BASE64_STANDARD.encode(data))
+            .map_err(|e| CredentialError::FileFailed(e.to_string()))
+    }
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn get(&self, key: &str) -> Result<Option<String>, CredentialError> {
+        Ok(self.read_secrets()?.remove(key))
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<(), CredentialError> {
+        let mut secrets = self.read_secrets()?;
+        secrets.insert(key.to_string(), secret.to_string());
+        self.write_secrets(&secrets)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), CredentialError> {
+        let mut secrets = self.read_secrets()?;
+        if secrets.remove(key).is_some() {
+            self.write_secrets(&secrets)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials");
+
+        let store = EncryptedFileStore::with_passphrase(path.clone(), "passphrase");
+        assert_eq!(store.get("default/api_token"), Ok(None));
+        store.set("default/api_token", "some-token").unwrap();
+        store.set("partner/pwd", "some-pwd").unwrap();
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("some-token"));
+
+        let store = EncryptedFileStore::with_passphrase(path.clone(), "passphrase");
+        assert_eq!(
+            store.get("default/api_token"),
+            Ok(Some("some-token".to_string()))
+        );
+        store.delete("default/api_token").unwrap();
+        assert_eq!(store.get("default/api_token"), Ok(None));
+        assert_eq!(store.get("partner/pwd"), Ok(Some("some-pwd".to_string())));
+
+        let store = EncryptedFileStore::with_passphrase(path, "wrong");
+        assert_eq!(
+            store.get("partner/pwd"),
+            Err(CredentialError::DecryptFailed)
+        );
+    }
+}
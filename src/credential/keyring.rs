@@ -0,0 +1,271 @@
+use crate::credential::{CredentialError, CredentialStore, CREDENTIAL_SERVICE};
+
+/// # KeyringStore
+///
+/// Secrets in the OS keyring. The Credential Manager on Windows, the Keychain on macOS,
+/// and the Secret Service by `secret-tool` on Linux.
+pub struct KeyringStore;
+
+impl KeyringStore {
+    /// # is available
+    ///
+    /// whether the keyring of this machine can be used, by looking up a secret.
+    pub fn is_available() -> bool {
+        !matches!(
+            platform::get(CREDENTIAL_SERVICE),
+            Err(CredentialError::Unavailable(_))
+        )
+    }
+}
+
+impl CredentialStore for KeyringStore {
+    fn get(&self, key: &str) -> Result<Option<String>, CredentialError> {
+        platform::get(key)
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<(), CredentialError> {
+        platform::set(key, secret)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), CredentialError> {
+        platform::delete(key)
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use crate::credential::{CredentialError, CREDENTIAL_SERVICE};
+    use std::ptr;
+    use windows_sys::Win32::Foundation::{GetLastError, ERROR_NOT_FOUND, FILETIME};
+    use windows_sys::Win32::Security::Credentials::{
+        CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE,
+        CRED_TYPE_GENERIC,
+    };
+
+    /// like `vertical-fire-platform/default/api_token`, nul terminated.
+    fn get_target_name(key: &str) -> Vec<u16> {
+        format!("{}/{}", CREDENTIAL_SERVICE, key)
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect()
+    }
+
+    fn last_error(api: &str) -> CredentialError {
+        // SAFETY: GetLastError has no precondition.
+        let code = unsafe { GetLastError() };
+        CredentialError::Unavailable(format!("{} failed with {}.", api, code))
+    }
+
+    pub fn get(key: &str) -> Result<Option<String>, CredentialError> {
+        let target_name = get_target_name(key);
+        let mut credential: *mut CREDENTIALW = ptr::null_mut();
+
+        // SAFETY: the target name is nul terminated,
+        // and the credential is read before it is freed by CredFree.
+        unsafe {
+            if CredReadW(target_name.as_ptr(), CRED_TYPE_GENERIC, 0, &mut credential) == 0 {
+                return match GetLastError() {
+                    ERROR_NOT_FOUND => Ok(None),
+                    _ => Err(last_error("CredReadW")),
+                };
+            }
+
+            let blob = std::slice::from_raw_parts(
+                (*credential).CredentialBlob,
+                (*credential).CredentialBlobSize as usize,
+            );
+            let secret = String::from_utf8(blob.to_vec());
+            CredFree(credential as *const _);
+
+            secret
+                .map(Some)
+                .map_err(|e| CredentialError::Unavailable(e.to_string()))
+        }
+    }
+
+    pub fn set(key: &str, secret: &str) -> Result<(), CredentialError> {
+        let mut target_name = get_target_name(key);
+        let mut blob = secret.as_bytes().to_vec();
+        let credential = CREDENTIALW {
+            Flags: 0,
+            Type: CRED_TYPE_GENERIC,
+            TargetName: target_name.as_mut_ptr(),
+            Comment: ptr::null_mut(),
+            LastWritten: FILETIME {
+                dwLowDateTime: 0,
+                dwHighDateTime: 0,
+            },
+            CredentialBlobSize: blob.len() as u32,
+            CredentialBlob: blob.as_mut_ptr(),
+            Persist: CRED_PERSIST_LOCAL_MACHINE,
+            AttributeCount: 0,
+            Attributes: ptr::null_mut(),
+            TargetAlias: ptr::null_mut(),
+            UserName: ptr::null_mut(),
+        };
+
+        // SAFETY: the pointers in the credential live until CredWriteW returns.
+        if unsafe { CredWriteW(&credential, 0) } == 0 {
+            return Err(last_error("CredWriteW"));
+        }
+
+        Ok(())
+    }
+
+    pub fn delete(key: &str) -> Result<(), CredentialError> {
+        let target_name = get_target_name(key);
+
+        // SAFETY: the target name is nul terminated.
+        unsafe {
+            if CredDeleteW(target_name.as_ptr(), CRED_TYPE_GENERIC, 0) == 0
+                && GetLastError() != ERROR_NOT_FOUND
+            {
+                return Err(last_error("CredDeleteW"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use crate::credential::{CredentialError, CREDENTIAL_SERVICE};
+    use security_framework::passwords::{
+        delete_generic_password, get_generic_password, set_generic_password,
+    };
+
+    /// errSecItemNotFound of the Security framework.
+    const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+    pub fn get(key: &str) -> Result<Option<String>, CredentialError> {
+        match get_generic_password(CREDENTIAL_SERVICE, key) {
+            Ok(secret) => String::from_utf8(secret)
+                .map(Some)
+                .map_err(|e| CredentialError::Unavailable(e.to_string())),
+            Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+            Err(e) => Err(CredentialError::Unavailable(e.to_string())),
+        }
+    }
+
+    pub fn set(key: &str, secret: &str) -> Result<(), CredentialError> {
+        set_generic_password(CREDENTIAL_SERVICE, key, secret.as_bytes())
+            .map_err(|e| CredentialError::Unavailable(e.to_string()))
+    }
+
+    pub fn delete(key: &str) -> Result<(), CredentialError> {
+        match delete_generic_password(CREDENTIAL_SERVICE, key) {
+            Err(e) if e.code() != ERR_SEC_ITEM_NOT_FOUND => {
+                Err(CredentialError::Unavailable(e.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod platform {
+    use crate::credential::{CredentialError, CREDENTIAL_SERVICE};
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    /// run `secret-tool` of libsecret with the secret as stdin.
+    fn run_secret_tool(args: &[&str], stdin: Option<&str>) -> Result<Output, CredentialError> {
+        let mut child = Command::new("secret-tool")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| CredentialError::Unavailable(e.to_string()))?;
+
+        if let Some(stdin) = stdin
+            && let Some(mut pipe) = child.stdin.take()
+        {
+            pipe.write_all(stdin.as_bytes())
+                .map_err(|e| CredentialError::Unavailable(e.to_string()))?;
+        }
+
+        child
+            .wait_with_output()
+            .map_err(|e| CredentialError::Unavailable(e.to_string()))
+    }
+
+    fn get_stderr(output: &Output) -> String {
+        String::from_utf8_lossy(&output.stderr).trim().to_string()
+    }
+
+    pub fn get(key: &str) -> Result<Option<String>, CredentialError> {
+        let output = run_secret_tool(
+            &["lookup", "service", CREDENTIAL_SERVICE, "account", key],
+            None,
+        )?;
+
+        // a missing secret exits with 1 and prints nothing.
+        let stderr = get_stderr(&output);
+        if !output.status.success() {
+            return match stderr.is_empty() {
+                true => Ok(None),
+                false => Err(CredentialError::Unavailable(stderr)),
+            };
+        }
+
+        Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
+    }
+
+    pub fn set(key: &str, secret: &str) -> Result<(), CredentialError> {
+        let label = format!("--label={} {}", CREDENTIAL_SERVICE, key);
+        let output = run_secret_tool(
+            &[
+                "store",
+                &label,
+                "service",
+                CREDENTIAL_SERVICE,
+                "account",
+                key,
+            ],
+            Some(secret),
+        )?;
+
+        if !output.status.success() {
+            return Err(CredentialError::Unavailable(get_stderr(&output)));
+        }
+
+        Ok(())
+    }
+
+    pub fn delete(key: &str) -> Result<(), CredentialError> {
+        let output = run_secret_tool(
+            &["clear", "service", CREDENTIAL_SERVICE, "account", key],
+            None,
+        )?;
+
+        let stderr = get_stderr(&output);
+        if !output.status.success() && !stderr.is_empty() {
+            return Err(CredentialError::Unavailable(stderr));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(any(windows, unix)))]
+mod platform {
+    use crate::credential::CredentialError;
+
+    fn unsupported() -> CredentialError {
+        CredentialError::Unavailable(std::env::consts::OS.to_string())
+    }
+
+    pub fn get(_key: &str) -> Result<Option<String>, CredentialError> {
+        Err(unsupported())
+    }
+
+    pub fn set(_key: &str, _secret: &str) -> Result<(), CredentialError> {
+        Err(unsupported())
+    }
+
+    pub fn delete(_key: &str) -> Result<(), CredentialError> {
+        Err(unsupported())
+    }
+}
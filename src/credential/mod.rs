@@ -0,0 +1,154 @@
+mod encrypted_file;
+mod keyring;
+
+pub use encrypted_file::EncryptedFileStore;
+pub use keyring::KeyringStore;
+
+use crate::constant::log::*;
+use dirs::home_dir;
+use formatx::formatx;
+use std::fmt::{Display, Formatter};
+use std::sync::LazyLock;
+
+/// service name of the secrets in the OS keyring.
+pub const CREDENTIAL_SERVICE: &str = "vertical-fire-platform";
+
+/// file name of the encrypted credential file in the home directory.
+pub const CREDENTIAL_FILE_NAME: &str = ".vf-credentials";
+
+/// secret name of the Jenkins api token.
+pub const API_TOKEN_SECRET: &str = "api_token";
+
+/// secret name of the Jenkins password.
+pub const PWD_SECRET: &str = "pwd";
+
+/// secret name of the Jenkins session cookie or bearer token.
+pub const SESSION_SECRET: &str = "session";
+
+/// # CredentialStore
+///
+/// Where the Jenkins secrets are kept out of the db file.
+pub trait CredentialStore: Send + Sync {
+    fn get(&self, key: &str) -> Result<Option<String>, CredentialError>;
+
+    fn set(&self, key: &str, secret: &str) -> Result<(), CredentialError>;
+
+    /// delete the secret. deleting a missing secret is not an error.
+    fn delete(&self, key: &str) -> Result<(), CredentialError>;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CredentialError {
+    /// the store cannot be used on this machine, like no keyring service is running.
+    Unavailable(String),
+
+    /// the encrypted file cannot be decrypted, mostly by a wrong passphrase.
+    DecryptFailed,
+
+    /// the encrypted file cannot be read or written.
+    FileFailed(String),
+}
+
+impl Display for CredentialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            CredentialError::Unavailable(e) => formatx!(ERR_CREDENTIAL_STORE_UNAVAILABLE, e),
+            CredentialError::DecryptFailed => Ok(ERR_CREDENTIAL_DECRYPT_FAILED.to_string()),
+            CredentialError::FileFailed(e) => formatx!(ERR_CREDENTIAL_FILE_FAILED, e),
+        };
+
+        write!(f, "{}", str.unwrap_or_default())
+    }
+}
+
+/// # CredentialStoreKind
+///
+/// The `credential_store` of the runtime config.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CredentialStoreKind {
+    /// the OS keyring, or the encrypted file if the keyring is unavailable.
+    Keyring,
+
+    /// the encrypted file only.
+    File,
+
+    /// in the db file like the old versions.
+    Plaintext,
+}
+
+/// the values of `credential_store`.
+pub const CREDENTIAL_STORE_VALUES: [&str; 3] = ["keyring", "file", "plaintext"];
+
+impl CredentialStoreKind {
+    /// unknown values fall back to the keyring.
+    pub fn from_config(value: &str) -> Self {
+        Self::parse(value).unwrap_or(CredentialStoreKind::Keyring)
+    }
+
+    /// one of [CREDENTIAL_STORE_VALUES] in any case, or none.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "keyring" => Some(CredentialStoreKind::Keyring),
+            "file" => Some(CredentialStoreKind::File),
+            "plaintext" => Some(CredentialStoreKind::Plaintext),
+            _ => None,
+        }
+    }
+}
+
+static CREDENTIAL_STORE: LazyLock<Option<Box<dyn CredentialStore>>> = LazyLock::new(|| {
+    let kind = CredentialStoreKind::from_config(&crate::default_config::runtime().credential_store);
+    let file_store = || {
+        Box::new(EncryptedFileStore::new(
+            home_dir().unwrap_or_default().join(CREDENTIAL_FILE_NAME),
+        )) as Box<dyn CredentialStore>
+    };
+
+    match kind {
+        CredentialStoreKind::Keyring if KeyringStore::is_available() => {
+            Some(Box::new(KeyringStore))
+        }
+        CredentialStoreKind::Keyring => Some(file_store()),
+        CredentialStoreKind::File => Some(file_store()),
+        CredentialStoreKind::Plaintext => None,
+    }
+});
+
+/// # get credential store
+///
+/// The store chosen by the runtime config. None in the plaintext mode.
+pub fn get_credential_store() -> Option<&'static dyn CredentialStore> {
+    CREDENTIAL_STORE.as_deref()
+}
+
+/// # get secret key
+///
+/// like `default/api_token`.
+pub fn get_secret_key(profile_name: &str, secret_name: &str) -> String {
+    format!("{}/{}", profile_name, secret_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_store_kind() {
+        assert_eq!(
+            CredentialStoreKind::from_config("File"),
+            CredentialStoreKind::File
+        );
+        assert_eq!(
+            CredentialStoreKind::from_config(" plaintext "),
+            CredentialStoreKind::Plaintext
+        );
+        assert_eq!(
+            CredentialStoreKind::from_config(""),
+            CredentialStoreKind::Keyring
+        );
+        assert_eq!(
+            get_secret_key("default", API_TOKEN_SECRET),
+            "default/api_token"
+        );
+    }
+}
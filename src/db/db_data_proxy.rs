@@ -1,24 +1,52 @@
-use crate::constant::log::{LOGIN_SUCCESS_BY_API_TOKEN, LOGIN_SUCCESS_BY_PWD};
+use crate::constant::log::{
+    ERR_DB_SAVE_FAILURE, ERR_JENKINS_CLIENT_INVALID_SIMPLE, HINT_DIAG_LOGIN,
+    LOGIN_SUCCESS_BY_API_TOKEN, LOGIN_SUCCESS_BY_PWD, LOGIN_SUCCESS_BY_SESSION,
+};
 use crate::constant::util::bring_element_to_first;
+use crate::credential::{
+    get_credential_store, get_secret_key, CredentialError, CredentialStore, API_TOKEN_SECRET,
+    PWD_SECRET, SESSION_SECRET,
+};
 use crate::db::db_struct::db_status::DBStatus;
-use crate::db::db_struct::fp_db_v7::JobRelativeData;
+use crate::db::db_struct::fp_db_v8::{
+    BuildPreset, JobBuildPresets, JobRelativeData, ServerJobCache, ServerProfile,
+    DEFAULT_PROFILE_NAME,
+};
+use crate::db::db_struct::fp_db_v9::VERSION_FP_DB_V9;
 use crate::db::db_struct::version_only::VersionOnly;
-use crate::db::db_struct::{parse_content_with_upgrade, LatestVersionData};
+use crate::db::db_struct::{parse_content_with_upgrade, LatestVersionData, LATEST_VERSION};
+use crate::default_config;
+use crate::doctor::diagnostic::{Diagnostic, DiagnosticStatus};
 use crate::extract::repo_decoration::RepoDecoration;
 use crate::jenkins::build::VfpJobBuildParam;
-use crate::jenkins::query::{try_get_jenkins_async_client, VfpJenkinsClient};
+use crate::jenkins::diagnose::diagnose_login;
+use crate::jenkins::error_kind::JenkinsErrorKind;
+use crate::jenkins::job_path::JobPath;
+use crate::jenkins::query::{
+    new_jenkins_async_client, try_get_jenkins_async_client,
+    try_get_jenkins_async_client_by_session, JenkinsCredential, VfpJenkinsClient,
+};
+use crate::jenkins::session::{JenkinsSession, SessionSource};
 use crate::pretty_log::{colored_println, ThemeColor};
 use base64::Engine;
+use formatx::formatx;
 use jenkins_sdk::JenkinsError;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
 
 #[derive(Debug, Default)]
 pub struct DbDataProxy {
     data: LatestVersionData,
 
+    /// name of the profile selected for this run. not saved.
+    profile_name: Option<String>,
+
+    /// secrets read from the credential store, by the secret key. not saved.
+    cached_secrets: Mutex<HashMap<String, Option<String>>>,
+
     cached_repo_decoration: OnceLock<RepoDecoration>,
 }
 
@@ -26,26 +54,53 @@ impl From<LatestVersionData> for DbDataProxy {
     fn from(data: LatestVersionData) -> Self {
         Self {
             data,
+            profile_name: None,
+            cached_secrets: Mutex::new(HashMap::new()),
             cached_repo_decoration: OnceLock::new(),
         }
     }
 }
 
 impl DbDataProxy {
+    /// # try get jenkins async client
+    ///
+    /// the checked client of the profile in use.
+    /// if `verbose`, print the login method on success, and the diagnostics on failure,
+    /// except for network failures and expired sessions, which the diagnostics cannot tell
+    /// more about.
     pub async fn try_get_jenkins_async_client<W: Write>(
         &self,
         stdout: &mut W,
-        show_client_type: bool,
+        verbose: bool,
     ) -> Result<VfpJenkinsClient, JenkinsError> {
-        let client = try_get_jenkins_async_client(
-            self.get_jenkins_url(),
-            self.get_jenkins_username(),
-            &self.get_jenkins_pwd(),
-            self.get_jenkins_api_token(),
-        )
-        .await;
-
-        if show_client_type && let Ok(ref client) = client {
+        let client = if let Some(JenkinsCredential::Session(session, source)) =
+            self.get_jenkins_credential()?
+        {
+            try_get_jenkins_async_client_by_session(self.get_jenkins_url(), &Some(session), &source)
+                .await
+        } else {
+            try_get_jenkins_async_client(
+                self.get_jenkins_url(),
+                self.get_jenkins_username(),
+                &self.get_jenkins_pwd().map_err(credential_failed)?,
+                &self.get_jenkins_api_token().map_err(credential_failed)?,
+            )
+            .await
+        };
+
+        if verbose
+            && let Err(ref e) = client
+            && !matches!(
+                JenkinsErrorKind::from(e),
+                JenkinsErrorKind::Network(_) | JenkinsErrorKind::SessionExpired
+            )
+        {
+            for diagnostic in self.diagnose_jenkins_login(None).await {
+                diagnostic.colored_println(stdout);
+            }
+        }
+
+        if verbose && let Ok(ref client) = client {
             match client {
                 VfpJenkinsClient::ApiTokenClient(_) => {
                     colored_println(stdout, ThemeColor::Second, LOGIN_SUCCESS_BY_API_TOKEN)
@@ -53,12 +108,77 @@ impl DbDataProxy {
                 VfpJenkinsClient::PwdClient(_) => {
                     colored_println(stdout, ThemeColor::Second, LOGIN_SUCCESS_BY_PWD)
                 }
+                VfpJenkinsClient::SessionClient(_) => {
+                    colored_println(stdout, ThemeColor::Second, LOGIN_SUCCESS_BY_SESSION)
+                }
             }
         }
 
         client
     }
 
+    /// # get jenkins credential
+    ///
+    /// the credential of the profile in use. the session takes precedence.
+    /// fails if the session source or the credential store cannot be read.
+    pub fn get_jenkins_credential(&self) -> Result<Option<JenkinsCredential>, JenkinsError> {
+        if self.is_session_login() {
+            let source = self.get_jenkins_session_source().cloned();
+            let session = match &source {
+                Some(source) => source.read()?,
+                None => self
+                    .get_jenkins_session()
+                    .map_err(credential_failed)?
+                    .unwrap_or_default(),
+            };
+
+            return Ok(JenkinsSession::parse(&session)
+                .map(|session| JenkinsCredential::Session(session, source)));
+        }
+
+        if let Some(pwd) = self.get_jenkins_pwd().map_err(credential_failed)? {
+            return Ok(Some(JenkinsCredential::Pwd(pwd)));
+        }
+        Ok(self
+            .get_jenkins_api_token()
+            .map_err(credential_failed)?
+            .map(JenkinsCredential::ApiToken))
+    }
+
+    /// # diagnose jenkins login
+    ///
+    /// check the login of the profile in use step by step. the permissions of the job are checked
+    /// if `job_path` is given.
+    pub async fn diagnose_jenkins_login(&self, job_path: Option<&JobPath>) -> Vec<Diagnostic> {
+        let (Some(url), Some(username)) = (self.get_jenkins_url(), self.get_jenkins_username())
+        else {
+            return vec![Diagnostic::new(
+                DiagnosticStatus::Failed,
+                Ok(ERR_JENKINS_CLIENT_INVALID_SIMPLE.to_string()),
+            )
+            .with_hint(HINT_DIAG_LOGIN)];
+        };
+
+        let client = self.get_jenkins_credential().and_then(|credential| {
+            let credential = credential.ok_or(JenkinsError::RequestError(
+                ERR_JENKINS_CLIENT_INVALID_SIMPLE.to_string(),
+            ))?;
+            new_jenkins_async_client(url, username, &credential)
+        });
+
+        match client {
+            Ok(client) => diagnose_login(url, username, &client, job_path).await,
+            Err(e) => {
+                let kind = JenkinsErrorKind::from(&e);
+                let diagnostic = Diagnostic::new(DiagnosticStatus::Failed, Ok(kind.to_string()));
+                match kind.get_hint() {
+                    Some(hint) => vec![diagnostic.with_hint(hint)],
+                    None => vec![diagnostic],
+                }
+            }
+        }
+    }
+
     /// # get interest job name
     ///
     /// get the job name that is currently of interest.
@@ -69,11 +189,15 @@ impl DbDataProxy {
     }
 
     pub fn get_all_job_names(&self) -> Vec<String> {
-        self.data
-            .job_relative_data_arr
-            .iter()
-            .map(|data| data.job_name.clone())
-            .collect()
+        self.get_profile()
+            .map(|profile| {
+                profile
+                    .job_relative_data_arr
+                    .iter()
+                    .map(|data| data.job_name.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     pub fn insert_job_name(&mut self, val: &str) -> &mut Self {
@@ -81,27 +205,76 @@ impl DbDataProxy {
         self
     }
 
+    /// # get server job names
+    ///
+    /// job names queried from the current Jenkins last time. may be expired.
+    pub fn get_server_job_names(&self) -> Vec<String> {
+        self.get_profile()
+            .and_then(|profile| profile.server_job_cache.as_ref())
+            .filter(|cache| Some(&cache.jenkins_url) == self.get_jenkins_url().as_ref())
+            .map(|cache| cache.job_paths.iter().map(JobPath::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// # is server job cache expired
+    ///
+    /// whether the jobs should be queried again. true if they belong to another Jenkins.
+    pub fn is_server_job_cache_expired(&self) -> bool {
+        let cache = self
+            .get_profile()
+            .and_then(|profile| profile.server_job_cache.as_ref());
+        cache.is_none_or(|cache| {
+            Some(&cache.jenkins_url) != self.get_jenkins_url().as_ref()
+                || chrono::Utc::now().timestamp() - cache.updated_at
+                    >= default_config::SERVER_JOB_CACHE_TTL_SECS
+        })
+    }
+
+    pub fn set_server_job_paths(&mut self, job_paths: Vec<JobPath>) -> &mut Self {
+        let cache = self
+            .get_jenkins_url()
+            .clone()
+            .map(|jenkins_url| ServerJobCache {
+                jenkins_url,
+                updated_at: chrono::Utc::now().timestamp(),
+                job_paths,
+            });
+        self.get_profile_mut().server_job_cache = cache;
+        self
+    }
+
     pub fn get_from_path(path: &Path) -> Option<Self> {
         match VersionOnly::get_state_from_path(path) {
             DBStatus::Exist(version) => {
                 let content = std::fs::read_to_string(path).ok()?;
                 #[cfg(debug_assertions)]
-                {
-                    match parse_content_with_upgrade(version, &content).map(|d| d.into()) {
-                        Ok(result) => Some(result),
-                        Err(e) => {
-                            eprintln!("Failed to parse or upgrade the database content: {}", e);
-                            None
-                        }
+                let mut db: Self = match parse_content_with_upgrade(version, &content) {
+                    Ok(result) => result.into(),
+                    Err(e) => {
+                        eprintln!("Failed to parse or upgrade the database content: {}", e);
+                        return None;
                     }
-                }
+                };
 
                 #[cfg(not(debug_assertions))]
+                let mut db: Self = parse_content_with_upgrade(version, &content).ok()?.into();
+
+                if version < VERSION_FP_DB_V9 {
+                    db.move_secrets_to_store();
+                }
+
+                // the secrets may be moved out of the db, so write it back at once.
+                if version != LATEST_VERSION
+                    && let Err(e) = db.save(path)
                 {
-                    parse_content_with_upgrade(version, &content)
-                        .map(|d| d.into())
-                        .ok()
+                    colored_println(
+                        &mut std::io::stdout(),
+                        ThemeColor::Error,
+                        &formatx!(ERR_DB_SAVE_FAILURE, e).unwrap_or_default(),
+                    );
                 }
+
+                Some(db)
             }
             DBStatus::NotExist => None,
         }
@@ -115,30 +288,159 @@ impl DbDataProxy {
             .map_err(|e| e.to_string())
     }
 
+    /// # user never login
+    ///
+    /// a secret that cannot be read from the credential store still counts as logged in,
+    /// so the failure is reported when it is used.
     pub fn user_never_login(&self) -> bool {
-        self.data.jenkins_username.is_none()
-            || (self.data.jenkins_api_token.is_none() && self.data.jenkins_pwd.is_none())
+        self.get_jenkins_username().is_none()
+            || (matches!(self.get_jenkins_api_token(), Ok(None))
+                && matches!(self.get_jenkins_pwd(), Ok(None))
+                && !self.is_session_login())
+    }
+
+    /// # is session login
+    ///
+    /// whether the profile logs in by a session cookie or bearer token,
+    /// which takes precedence over the password and api token.
+    pub fn is_session_login(&self) -> bool {
+        self.get_jenkins_session_source().is_some()
+            || !matches!(self.get_jenkins_session(), Ok(None))
+    }
+
+    /// # get profile
+    ///
+    /// the selected profile. if none is selected, the default one, or the first one.
+    fn get_profile(&self) -> Option<&ServerProfile> {
+        match &self.profile_name {
+            Some(name) => self
+                .data
+                .profiles
+                .iter()
+                .find(|profile| &profile.name == name),
+            None => self
+                .data
+                .profiles
+                .iter()
+                .find(|profile| profile.name == DEFAULT_PROFILE_NAME)
+                .or(self.data.profiles.first()),
+        }
+    }
+
+    /// # get profile mut
+    ///
+    /// the same profile as [Self::get_profile]. created if it does not exist.
+    fn get_profile_mut(&mut self) -> &mut ServerProfile {
+        let index = match &self.profile_name {
+            Some(name) => self
+                .data
+                .profiles
+                .iter()
+                .position(|profile| &profile.name == name),
+            None => self
+                .data
+                .profiles
+                .iter()
+                .position(|profile| profile.name == DEFAULT_PROFILE_NAME)
+                .or((!self.data.profiles.is_empty()).then_some(0)),
+        };
+
+        let index = index.unwrap_or_else(|| {
+            let name = self.profile_name.as_deref().unwrap_or(DEFAULT_PROFILE_NAME);
+            self.data.profiles.push(ServerProfile::new(name));
+            self.data.profiles.len() - 1
+        });
+
+        &mut self.data.profiles[index]
+    }
+
+    /// # get profile name
+    ///
+    /// name of the profile in use.
+    pub fn get_profile_name(&self) -> &str {
+        self.get_profile()
+            .map(|profile| profile.name.as_str())
+            .or(self.profile_name.as_deref())
+            .unwrap_or(DEFAULT_PROFILE_NAME)
+    }
+
+    pub fn get_profile_names(&self) -> Vec<&str> {
+        self.data
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect()
+    }
+
+    pub fn has_profile(&self, name: &str) -> bool {
+        self.data
+            .profiles
+            .iter()
+            .any(|profile| profile.name == name)
+    }
+
+    /// # select profile
+    ///
+    /// use the profile for this run. it is created when written, like on login.
+    pub fn select_profile(&mut self, name: &str) -> &mut Self {
+        self.profile_name = Some(name.to_string());
+        self
+    }
+
+    pub fn is_profile_selected(&self) -> bool {
+        self.profile_name.is_some()
+    }
+
+    /// # find profile by url
+    ///
+    /// find the profile whose Jenkins has the same host and port as the url.
+    pub fn find_profile_by_url(&self, url: &str) -> Option<&str> {
+        let get_origin = |url: &str| {
+            url::Url::parse(url)
+                .ok()
+                .and_then(|url| Some((url.host_str()?.to_ascii_lowercase(), url.port())))
+        };
+        let origin = get_origin(url)?;
+
+        self.data
+            .profiles
+            .iter()
+            .find(|profile| {
+                profile
+                    .jenkins_url
+                    .as_deref()
+                    .and_then(get_origin)
+                    .is_some_and(|profile_origin| profile_origin == origin)
+            })
+            .map(|profile| profile.name.as_str())
     }
 
     pub fn get_repo_decoration(&self) -> &RepoDecoration {
         self.cached_repo_decoration.get_or_init(|| {
             let default = "";
-            let runtime_config = crate::default_config::runtime();
+            let job_name = self.get_interest_job_name().unwrap_or(default);
+            let runtime_config = crate::default_config::runtime_for_job(job_name);
 
             RepoDecoration::new(
-                self.get_extract_repo()
-                    .as_ref()
+                runtime_config
+                    .last_used_unless_set_for_job("repo_template", self.get_extract_repo())
                     .map(|s| s.as_ref())
                     .unwrap_or(runtime_config.repo_template.as_str()),
-                self.get_extract_locator_pattern()
-                    .as_ref()
+                runtime_config
+                    .last_used_unless_set_for_job(
+                        "locator_pattern",
+                        self.get_extract_locator_pattern(),
+                    )
                     .map(|s| s.as_ref())
                     .unwrap_or(runtime_config.locator_pattern.as_str()),
-                self.get_extract_s_locator_template()
-                    .as_ref()
+                runtime_config
+                    .last_used_unless_set_for_job(
+                        "locator_template",
+                        self.get_extract_s_locator_template(),
+                    )
                     .map(|s| s.as_ref())
                     .unwrap_or(runtime_config.locator_template.as_str()),
-                self.get_interest_job_name().unwrap_or(default),
+                job_name,
             )
         })
     }
@@ -152,12 +454,9 @@ impl DbDataProxy {
     }
 
     fn keep_job_relative_data_count(&mut self) -> &mut Self {
-        if self.data.job_relative_data_arr.len()
-            > crate::default_config::MAX_JOB_RELATIVE_DATA_COUNT
-        {
-            self.data
-                .job_relative_data_arr
-                .truncate(crate::default_config::MAX_JOB_RELATIVE_DATA_COUNT);
+        let job_relative_data_arr = &mut self.get_profile_mut().job_relative_data_arr;
+        if job_relative_data_arr.len() > crate::default_config::MAX_JOB_RELATIVE_DATA_COUNT {
+            job_relative_data_arr.truncate(crate::default_config::MAX_JOB_RELATIVE_DATA_COUNT);
         }
 
         self
@@ -170,30 +469,29 @@ impl DbDataProxy {
         &self,
         priority_job_name: Option<&str>,
     ) -> Option<&JobRelativeData> {
+        let job_relative_data_arr = &self.get_profile()?.job_relative_data_arr;
         if let Some(name) = priority_job_name {
-            self.data
-                .job_relative_data_arr
+            job_relative_data_arr
                 .iter()
                 .find(|data| data.job_name == name)
         } else {
-            self.data.job_relative_data_arr.first()
+            job_relative_data_arr.first()
         }
     }
 
     fn try_get_job_relative_data_mut(&mut self, job_name: &str) -> Option<&mut JobRelativeData> {
-        let index = self
-            .data
-            .job_relative_data_arr
+        let job_relative_data_arr = &mut self.get_profile_mut().job_relative_data_arr;
+        let index = job_relative_data_arr
             .iter()
             .position(|data| data.job_name == job_name);
 
         if let Some(idx) = index {
-            bring_element_to_first(&mut self.data.job_relative_data_arr, idx);
+            bring_element_to_first(job_relative_data_arr, idx);
         } else {
-            self.data.job_relative_data_arr.insert(
+            job_relative_data_arr.insert(
                 0,
                 JobRelativeData {
-                    job_name: job_name.to_owned(),
+                    job_name: job_name.into(),
                     last_inner_version: None,
                     last_player_count: None,
                     blast_path: None,
@@ -205,7 +503,7 @@ impl DbDataProxy {
             self.keep_job_relative_data_count();
         }
 
-        self.data.job_relative_data_arr.first_mut()
+        self.get_profile_mut().job_relative_data_arr.first_mut()
     }
 
     pub fn get_last_inner_version(&self, job_name: &str) -> Option<u32> {
@@ -272,46 +570,167 @@ impl DbDataProxy {
     }
 
     pub fn get_jenkins_url(&self) -> &Option<String> {
-        &self.data.jenkins_url
+        self.get_profile()
+            .map_or(&None, |profile| &profile.jenkins_url)
     }
 
     pub fn set_jenkins_url(&mut self, val: Option<String>) -> &mut Self {
-        self.data.jenkins_url = val;
+        self.get_profile_mut().jenkins_url = val;
         self
     }
 
     pub fn get_jenkins_username(&self) -> &Option<String> {
-        &self.data.jenkins_username
+        self.get_profile()
+            .map_or(&None, |profile| &profile.jenkins_username)
     }
 
     pub fn set_jenkins_username(&mut self, val: Option<String>) -> &mut Self {
-        self.data.jenkins_username = val;
+        self.get_profile_mut().jenkins_username = val;
         self
     }
 
-    pub fn get_jenkins_api_token(&self) -> &Option<String> {
-        &self.data.jenkins_api_token
+    pub fn get_jenkins_api_token(&self) -> Result<Option<String>, CredentialError> {
+        match self
+            .get_profile()
+            .and_then(|profile| profile.jenkins_api_token.clone())
+        {
+            Some(v) => Ok(Some(v)),
+            None => self.get_stored_secret(get_credential_store(), API_TOKEN_SECRET),
+        }
     }
 
-    pub fn set_jenkins_api_token(&mut self, val: Option<String>) -> &mut Self {
-        self.data.jenkins_api_token = val;
-        self
+    pub fn set_jenkins_api_token(
+        &mut self,
+        val: Option<String>,
+    ) -> Result<&mut Self, CredentialError> {
+        self.get_profile_mut().jenkins_api_token =
+            self.store_secret(get_credential_store(), API_TOKEN_SECRET, val)?;
+        Ok(self)
     }
 
-    pub fn get_jenkins_pwd(&self) -> Option<String> {
-        self.data.jenkins_pwd.clone().and_then(|v| {
-            base64::prelude::BASE64_STANDARD
+    pub fn get_jenkins_pwd(&self) -> Result<Option<String>, CredentialError> {
+        match self
+            .get_profile()
+            .and_then(|profile| profile.jenkins_pwd.clone())
+        {
+            Some(v) => Ok(base64::prelude::BASE64_STANDARD
                 .decode(v)
                 .ok()
-                .and_then(|v| String::from_utf8(v).ok())
-        })
+                .and_then(|v| String::from_utf8(v).ok())),
+            None => self.get_stored_secret(get_credential_store(), PWD_SECRET),
+        }
+    }
+
+    pub fn set_jenkins_pwd(&mut self, val: Option<String>) -> Result<&mut Self, CredentialError> {
+        self.get_profile_mut().jenkins_pwd = self
+            .store_secret(get_credential_store(), PWD_SECRET, val)?
+            .map(|v| base64::prelude::BASE64_STANDARD.encode(v));
+        Ok(self)
     }
 
-    pub fn set_jenkins_pwd(&mut self, val: Option<String>) -> &mut Self {
-        self.data.jenkins_pwd = val.map(|v| base64::prelude::BASE64_STANDARD.encode(v));
+    pub fn get_jenkins_session(&self) -> Result<Option<String>, CredentialError> {
+        match self
+            .get_profile()
+            .and_then(|profile| profile.jenkins_session.clone())
+        {
+            Some(v) => Ok(Some(v)),
+            None => self.get_stored_secret(get_credential_store(), SESSION_SECRET),
+        }
+    }
+
+    pub fn set_jenkins_session(
+        &mut self,
+        val: Option<String>,
+    ) -> Result<&mut Self, CredentialError> {
+        self.get_profile_mut().jenkins_session =
+            self.store_secret(get_credential_store(), SESSION_SECRET, val)?;
+        Ok(self)
+    }
+
+    pub fn get_jenkins_session_source(&self) -> Option<&SessionSource> {
+        self.get_profile()?.jenkins_session_source.as_ref()
+    }
+
+    pub fn set_jenkins_session_source(&mut self, val: Option<SessionSource>) -> &mut Self {
+        self.get_profile_mut().jenkins_session_source = val;
         self
     }
 
+    /// # get stored secret
+    ///
+    /// read the secret of the profile in use from the credential store. cached for this run.
+    /// the failures are not cached, so they are reported on every read.
+    fn get_stored_secret(
+        &self,
+        store: Option<&dyn CredentialStore>,
+        secret_name: &str,
+    ) -> Result<Option<String>, CredentialError> {
+        let (Some(store), Some(profile)) = (store, self.get_profile()) else {
+            return Ok(None);
+        };
+        let key = get_secret_key(&profile.name, secret_name);
+
+        if let Some(secret) = self
+            .cached_secrets
+            .lock()
+            .ok()
+            .and_then(|cached_secrets| cached_secrets.get(&key).cloned())
+        {
+            return Ok(secret);
+        }
+
+        let secret = store.get(&key)?;
+        if let Ok(mut cached_secrets) = self.cached_secrets.lock() {
+            cached_secrets.insert(key, secret.clone());
+        }
+        Ok(secret)
+    }
+
+    /// # store secret
+    ///
+    /// write the secret of the profile in use to the credential store.
+    ///
+    /// ### Returns
+    ///
+    /// the value to keep in the db, which is the secret itself in the plaintext mode.
+    /// fails rather than keeping the secret in the db if the store failed.
+    fn store_secret(
+        &mut self,
+        store: Option<&dyn CredentialStore>,
+        secret_name: &str,
+        val: Option<String>,
+    ) -> Result<Option<String>, CredentialError> {
+        let Some(store) = store else {
+            return Ok(val);
+        };
+        let key = get_secret_key(self.get_profile_mut().name.as_str(), secret_name);
+
+        match &val {
+            Some(secret) => store.set(&key, secret)?,
+            None => store.delete(&key)?,
+        };
+        if let Ok(cached_secrets) = self.cached_secrets.get_mut() {
+            cached_secrets.insert(key, val);
+        }
+        Ok(None)
+    }
+
+    /// # move secrets to store
+    ///
+    /// move the plaintext secrets of all profiles, which the dbs before v9 kept,
+    /// into the credential store.
+    fn move_secrets_to_store(&mut self) {
+        let has_secret =
+            self.data.profiles.iter().any(|profile| {
+                profile.jenkins_api_token.is_some() || profile.jenkins_pwd.is_some()
+            });
+        if has_secret && let Some(store) = get_credential_store() {
+            for profile in self.data.profiles.iter_mut() {
+                move_secrets_to_store(store, profile);
+            }
+        }
+    }
+
     pub fn get_jenkins_build_param(&self, job_name: &str) -> Option<&VfpJobBuildParam> {
         self.try_get_job_relative_data_with_priority_job(Some(job_name))
             .and_then(|data| data.jenkins_build_params.as_ref())
@@ -338,14 +757,86 @@ impl DbDataProxy {
         self.try_get_job_relative_data_with_priority_job(Some(job_name))
             .and_then(|data| data.distr_src_index)
     }
-    
-    pub fn set_distr_src_index(&mut self,job_name: &str, val: Option<u32>) -> &mut Self {
+
+    pub fn set_distr_src_index(&mut self, job_name: &str, val: Option<u32>) -> &mut Self {
         self.try_get_job_relative_data_mut(job_name)
             .map(|data| data.distr_src_index = val);
-        
+
+        self
+    }
+
+    pub fn get_build_presets(&self, job_name: &str) -> &[BuildPreset] {
+        self.get_profile()
+            .and_then(|profile| {
+                profile
+                    .job_build_presets
+                    .iter()
+                    .find(|data| data.job_name == job_name)
+            })
+            .map(|data| data.presets.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn get_build_preset(&self, job_name: &str, name: &str) -> Option<&BuildPreset> {
+        self.get_build_presets(job_name)
+            .iter()
+            .find(|preset| preset.name == name)
+    }
+
+    /// # set build preset
+    ///
+    /// insert a preset, or replace the one with the same name.
+    pub fn set_build_preset(
+        &mut self,
+        job_name: &str,
+        name: &str,
+        params: VfpJobBuildParam,
+    ) -> &mut Self {
+        let job_build_presets = &mut self.get_profile_mut().job_build_presets;
+        let index = job_build_presets
+            .iter()
+            .position(|data| data.job_name == job_name)
+            .unwrap_or_else(|| {
+                job_build_presets.push(JobBuildPresets {
+                    job_name: job_name.into(),
+                    presets: Vec::new(),
+                });
+                job_build_presets.len() - 1
+            });
+
+        let presets = &mut job_build_presets[index].presets;
+        match presets.iter_mut().find(|preset| preset.name == name) {
+            Some(preset) => preset.params = params,
+            None => presets.push(BuildPreset {
+                name: name.to_string(),
+                params,
+            }),
+        }
+
         self
     }
 
+    /// # remove build preset
+    ///
+    /// ### Returns
+    ///
+    /// whether the preset existed.
+    pub fn remove_build_preset(&mut self, job_name: &str, name: &str) -> bool {
+        if self.get_build_preset(job_name, name).is_none() {
+            return false;
+        }
+
+        let job_build_presets = &mut self.get_profile_mut().job_build_presets;
+        for data in job_build_presets.iter_mut() {
+            if data.job_name == job_name {
+                data.presets.retain(|preset| preset.name != name);
+            }
+        }
+        job_build_presets.retain(|data| !data.presets.is_empty());
+
+        true
+    }
+
     pub fn is_auto_update_enabled(&self) -> bool {
         self.data.auto_update_enabled
     }
@@ -379,9 +870,49 @@ impl DbDataProxy {
     //endregion
 }
 
+fn credential_failed(e: CredentialError) -> JenkinsError {
+    JenkinsError::RequestError(e.to_string())
+}
+
+/// # move secrets to store
+///
+/// move the plaintext api token and password of the profile into the store.
+/// a secret is kept in the profile if the store failed to save it.
+fn move_secrets_to_store(store: &dyn CredentialStore, profile: &mut ServerProfile) {
+    if let Some(api_token) = &profile.jenkins_api_token
+        && store
+            .set(&get_secret_key(&profile.name, API_TOKEN_SECRET), api_token)
+            .is_ok()
+    {
+        profile.jenkins_api_token = None;
+    }
+
+    // the password was saved as base64 in the db.
+    let pwd = profile
+        .jenkins_pwd
+        .as_ref()
+        .and_then(|v| base64::prelude::BASE64_STANDARD.decode(v).ok())
+        .and_then(|v| String::from_utf8(v).ok());
+    if let Some(pwd) = pwd
+        && store
+            .set(&get_secret_key(&profile.name, PWD_SECRET), &pwd)
+            .is_ok()
+    {
+        profile.jenkins_pwd = None;
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::db::db_data_proxy::DbDataProxy;
+    use crate::credential::{
+        CredentialError, CredentialStore, EncryptedFileStore, API_TOKEN_SECRET,
+    };
+    use crate::db::db_data_proxy::{move_secrets_to_store, DbDataProxy};
+    use crate::db::db_struct::fp_db_v8::ServerProfile;
+    use crate::db::db_struct::LatestVersionData;
+    use crate::jenkins::build::VfpJobBuildParam;
+    use base64::Engine;
+    use std::collections::HashMap;
     use std::io::Write;
     use std::path::{Path, PathBuf};
 
@@ -427,6 +958,139 @@ blast_path = 'C:\\Users\\LviatYi\\Desktop\\Temp'
         assert!(config.get_extract_locator_pattern().is_none());
     }
 
+    #[test]
+    fn test_build_presets() {
+        let mut db = DbDataProxy::default();
+        let job_name = "test_job";
+
+        let mut params = VfpJobBuildParam::new_with_override_recommend_param(HashMap::new());
+        db.set_build_preset(job_name, "perf-profiling", params.clone());
+        params.set_enable_content_preview(false);
+        db.set_build_preset(job_name, "perf-profiling", params);
+
+        assert_eq!(db.get_build_presets(job_name).len(), 1);
+        assert_eq!(
+            db.get_build_preset(job_name, "perf-profiling")
+                .and_then(|preset| preset.params.params.get("EnableContentPreview")),
+            Some(&serde_json::Value::Bool(false))
+        );
+
+        let content = toml::to_string(&db.data).unwrap();
+        let restored = toml::from_str::<LatestVersionData>(&content).unwrap();
+        assert_eq!(restored, db.data);
+
+        for i in 0..=crate::default_config::MAX_JOB_RELATIVE_DATA_COUNT {
+            db.insert_job_name(&format!("other_job_{}", i));
+        }
+        assert_eq!(db.get_build_presets(job_name).len(), 1);
+
+        assert!(!db.remove_build_preset("typo_job", "perf-profiling"));
+        assert!(!db.get_all_job_names().contains(&"typo_job".to_string()));
+
+        assert!(db.remove_build_preset(job_name, "perf-profiling"));
+        assert!(!db.remove_build_preset(job_name, "perf-profiling"));
+        assert!(db.get_build_presets(job_name).is_empty());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let mut db = DbDataProxy::default();
+        db.set_jenkins_url(Some("https://main.jenkins.com".to_string()));
+        db.insert_job_name("main_job");
+
+        db.select_profile("partner");
+        assert!(db.get_jenkins_url().is_none());
+        db.set_jenkins_url(Some("https://partner.jenkins.com:8443/".to_string()));
+        db.insert_job_name("partner_job");
+        assert_eq!(db.get_all_job_names(), vec!["partner_job"]);
+        assert_eq!(db.get_profile_names(), vec!["default", "partner"]);
+
+        assert_eq!(
+            db.find_profile_by_url("https://partner.jenkins.com:8443/job/partner_job/12/"),
+            Some("partner")
+        );
+        assert_eq!(
+            db.find_profile_by_url("https://MAIN.jenkins.com/job/main_job/"),
+            Some("default")
+        );
+        assert_eq!(db.find_profile_by_url("https://partner.jenkins.com/"), None);
+
+        db.select_profile("default");
+        assert_eq!(db.get_all_job_names(), vec!["main_job"]);
+    }
+
+    #[test]
+    fn test_secrets() {
+        let mut db = DbDataProxy::default();
+        db.set_jenkins_url(Some("https://main.jenkins.com".to_string()));
+
+        // the plaintext mode keeps the secret in the db.
+        let stored = db.store_secret(None, API_TOKEN_SECRET, Some("some-token".to_string()));
+        assert_eq!(stored, Ok(Some("some-token".to_string())));
+        assert_eq!(db.get_stored_secret(None, API_TOKEN_SECRET), Ok(None));
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = EncryptedFileStore::with_passphrase(dir.path().join("credentials"), "pass");
+        let stored = db.store_secret(
+            Some(&store),
+            API_TOKEN_SECRET,
+            Some("some-token".to_string()),
+        );
+        assert_eq!(stored, Ok(None));
+        assert_eq!(
+            store.get("default/api_token"),
+            Ok(Some("some-token".to_string()))
+        );
+
+        // a failed read is reported every time, and a failed write is not kept in the db.
+        let mut db = DbDataProxy::from(db.data);
+        let store = EncryptedFileStore::with_passphrase(dir.path().join("credentials"), "wrong");
+        for _ in 0..2 {
+            assert_eq!(
+                db.get_stored_secret(Some(&store), API_TOKEN_SECRET),
+                Err(CredentialError::DecryptFailed)
+            );
+        }
+        assert!(db
+            .store_secret(
+                Some(&store),
+                API_TOKEN_SECRET,
+                Some("other-token".to_string())
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_move_secrets_to_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EncryptedFileStore::with_passphrase(dir.path().join("credentials"), "pass");
+
+        let mut profile = ServerProfile {
+            name: "partner".to_string(),
+            ..Default::default()
+        };
+        profile.jenkins_api_token = Some("some-token".to_string());
+        profile.jenkins_pwd = Some(base64::prelude::BASE64_STANDARD.encode("some-pwd"));
+        move_secrets_to_store(&store, &mut profile);
+
+        assert!(profile.jenkins_api_token.is_none());
+        assert!(profile.jenkins_pwd.is_none());
+        assert_eq!(
+            store.get("partner/api_token"),
+            Ok(Some("some-token".to_string()))
+        );
+        assert_eq!(store.get("partner/pwd"), Ok(Some("some-pwd".to_string())));
+
+        let store = EncryptedFileStore::with_passphrase(dir.path().join("credentials"), "wrong");
+        let mut profile = ServerProfile {
+            name: "partner".to_string(),
+            ..Default::default()
+        };
+        profile.jenkins_api_token = Some("some-token".to_string());
+        move_secrets_to_store(&store, &mut profile);
+        assert_eq!(profile.jenkins_api_token.as_deref(), Some("some-token"));
+    }
+
     #[test]
     fn test_get_file_not_exist() {
         let config = DbDataProxy::get_from_path(Path::new("Z:\\NOT_EXIST"));
@@ -1,3 +1,4 @@
+use crate::db::db_struct::fp_db_v8::{self, FpDbV8, ServerProfile, DEFAULT_PROFILE_NAME};
 use crate::db::db_struct::versioned_data::{UpgradeValue, VersionedData};
 use crate::define_versioned_data_type;
 use crate::jenkins::build::VfpJobBuildParam;
@@ -41,7 +42,40 @@ pub(crate) struct JobRelativeData {
 
 impl VersionedData for FpDbV7 {
     fn parse_next_version(self: Box<Self>) -> UpgradeValue {
-        UpgradeValue::Latest(*self)
+        let mut upg = FpDbV8::default();
+
+        upg.extract_repo = self.extract_repo;
+        upg.extract_locator_pattern = self.extract_locator_pattern;
+        upg.extract_s_locator_template = self.extract_s_locator_template;
+
+        if self.jenkins_url.is_some() || !self.job_relative_data_arr.is_empty() {
+            upg.profiles.push(ServerProfile {
+                name: DEFAULT_PROFILE_NAME.to_string(),
+                jenkins_url: self.jenkins_url,
+                jenkins_username: self.jenkins_username,
+                jenkins_api_token: self.jenkins_api_token,
+                jenkins_pwd: self.jenkins_pwd,
+                job_relative_data_arr: self
+                    .job_relative_data_arr
+                    .into_iter()
+                    .map(|data| fp_db_v8::JobRelativeData {
+                        job_name: data.job_name.into(),
+                        last_inner_version: data.last_inner_version,
+                        last_player_count: data.last_player_count,
+                        blast_path: data.blast_path,
+                        jenkins_build_params: data.jenkins_build_params,
+                        distr_src_index: data.distr_src_index,
+                    })
+                    .collect(),
+                ..Default::default()
+            });
+        }
+
+        upg.never_check_version = self.never_check_version;
+        upg.auto_update_enabled = self.auto_update_enabled;
+        upg.latest_remote_version = self.latest_remote_version;
+
+        UpgradeValue::Upgraded(Box::new(upg))
     }
 }
 
@@ -0,0 +1,249 @@
+use crate::db::db_struct::fp_db_v9::FpDbV9;
+use crate::db::db_struct::versioned_data::{UpgradeValue, VersionedData};
+use crate::define_versioned_data_type;
+use crate::jenkins::build::VfpJobBuildParam;
+use crate::jenkins::job_path::JobPath;
+use crate::jenkins::session::SessionSource;
+use serde::{Deserialize, Serialize, Serializer};
+use std::path::PathBuf;
+
+pub const VERSION_FP_DB_V8: u32 = 8;
+
+/// name of the profile used when no profile is specified.
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+define_versioned_data_type!(FpDbV8, VERSION_FP_DB_V8, {
+    pub extract_repo: Option<String>,
+    pub extract_locator_pattern: Option<String>,
+    pub extract_s_locator_template: Option<String>,
+
+    #[serde(default)]
+    pub profiles: Vec<ServerProfile>,
+
+    #[serde(default)]
+    pub never_check_version: bool,
+    #[serde(default)]
+    pub auto_update_enabled: bool,
+    pub latest_remote_version: Option<String>,
+    }
+);
+
+/// # ServerProfile
+///
+/// A Jenkins server with its own credentials and jobs.
+#[derive(Deserialize, Serialize, Default, Debug)]
+pub struct ServerProfile {
+    pub name: String,
+
+    pub jenkins_url: Option<String>,
+    pub jenkins_username: Option<String>,
+    pub jenkins_api_token: Option<String>,
+    pub jenkins_pwd: Option<String>,
+
+    /// the session cookie or bearer token, kept like the api token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jenkins_session: Option<String>,
+    /// read the session from here on every run, instead of the saved one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jenkins_session_source: Option<SessionSource>,
+
+    /// the recently used jobs, the most recent first. only the latest few are kept.
+    pub job_relative_data_arr: Vec<JobRelativeData>,
+
+    /// presets of every job, which are kept however long ago the job was used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub job_build_presets: Vec<JobBuildPresets>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_job_cache: Option<ServerJobCache>,
+}
+
+impl ServerProfile {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+pub(crate) struct JobRelativeData {
+    /// path of the job. stored as a string like `Folder/Sub/main`, the same as a plain job name.
+    pub job_name: JobPath,
+
+    pub last_inner_version: Option<u32>,
+    pub last_player_count: Option<u32>,
+    pub blast_path: Option<PathBuf>,
+
+    pub jenkins_build_params: Option<VfpJobBuildParam>,
+
+    pub distr_src_index: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug)]
+pub struct JobBuildPresets {
+    pub job_name: JobPath,
+
+    pub presets: Vec<BuildPreset>,
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct BuildPreset {
+    pub name: String,
+
+    pub params: VfpJobBuildParam,
+}
+
+/// # ServerJobCache
+///
+/// Job paths queried from Jenkins, used by the job name prompt.
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct ServerJobCache {
+    /// the Jenkins the jobs belong to.
+    pub jenkins_url: String,
+
+    /// seconds since the epoch.
+    pub updated_at: i64,
+
+    pub job_paths: Vec<JobPath>,
+}
+
+impl VersionedData for FpDbV8 {
+    fn parse_next_version(self: Box<Self>) -> UpgradeValue {
+        let mut upg = FpDbV9::default();
+
+        upg.extract_repo = self.extract_repo;
+        upg.extract_locator_pattern = self.extract_locator_pattern;
+        upg.extract_s_locator_template = self.extract_s_locator_template;
+        // the secrets are moved to the credential store after loading, see `DbDataProxy`.
+        upg.profiles = self.profiles;
+
+        upg.never_check_version = self.never_check_version;
+        upg.auto_update_enabled = self.auto_update_enabled;
+        upg.latest_remote_version = self.latest_remote_version;
+
+        UpgradeValue::Upgraded(Box::new(upg))
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+mod tests {
+    use super::*;
+
+    impl PartialEq for ServerProfile {
+        fn eq(&self, other: &Self) -> bool {
+            self.name == other.name
+                && self.jenkins_url == other.jenkins_url
+                && self.jenkins_username == other.jenkins_username
+                && self.jenkins_api_token == other.jenkins_api_token
+                && self.jenkins_pwd == other.jenkins_pwd
+                && self.jenkins_session == other.jenkins_session
+                && self.jenkins_session_source == other.jenkins_session_source
+                && self.job_relative_data_arr == other.job_relative_data_arr
+                && self.job_build_presets == other.job_build_presets
+                && self.server_job_cache == other.server_job_cache
+        }
+    }
+
+    impl PartialEq for JobRelativeData {
+        fn eq(&self, other: &Self) -> bool {
+            self.job_name == other.job_name
+                && self.last_inner_version == other.last_inner_version
+                && self.last_player_count == other.last_player_count
+                && self.blast_path == other.blast_path
+                && self.jenkins_build_params == other.jenkins_build_params
+                && self.distr_src_index == other.distr_src_index
+        }
+    }
+
+    impl PartialEq for JobBuildPresets {
+        fn eq(&self, other: &Self) -> bool {
+            self.job_name == other.job_name && self.presets == other.presets
+        }
+    }
+
+    impl PartialEq for BuildPreset {
+        fn eq(&self, other: &Self) -> bool {
+            self.name == other.name && self.params == other.params
+        }
+    }
+
+    impl PartialEq for FpDbV8 {
+        fn eq(&self, other: &Self) -> bool {
+            self.extract_repo == other.extract_repo
+                && self.extract_locator_pattern == other.extract_locator_pattern
+                && self.extract_s_locator_template == other.extract_s_locator_template
+                && self.profiles == other.profiles
+                && self.never_check_version == other.never_check_version
+                && self.auto_update_enabled == other.auto_update_enabled
+                && self.latest_remote_version == other.latest_remote_version
+        }
+    }
+
+    impl PartialEq for ServerJobCache {
+        fn eq(&self, other: &Self) -> bool {
+            self.jenkins_url == other.jenkins_url
+                && self.updated_at == other.updated_at
+                && self.job_paths == other.job_paths
+        }
+    }
+
+    #[test]
+    fn test_upgrade_from_v7() {
+        let content = r#"version = 7
+jenkins_url = "https://example.jenkins.com"
+jenkins_username = "somebody"
+never_check_version = false
+auto_update_enabled = false
+
+[[job_relative_data_arr]]
+job_name = "test_job"
+last_inner_version = 1024
+"#;
+
+        let db = crate::db::db_struct::parse_content_with_upgrade(7, content).unwrap();
+        assert_eq!(db.profiles.len(), 1);
+
+        let profile = &db.profiles[0];
+        assert_eq!(profile.name, DEFAULT_PROFILE_NAME);
+        assert_eq!(
+            profile.jenkins_url.as_deref(),
+            Some("https://example.jenkins.com")
+        );
+        assert_eq!(profile.jenkins_username.as_deref(), Some("somebody"));
+        assert_eq!(profile.job_relative_data_arr[0].job_name, "test_job");
+        assert_eq!(
+            profile.job_relative_data_arr[0].last_inner_version,
+            Some(1024)
+        );
+        assert!(profile.jenkins_session.is_none());
+        assert!(profile.jenkins_session_source.is_none());
+    }
+
+    #[test]
+    fn test_upgrade_keeps_presets() {
+        let content = r#"version = 8
+
+[[profiles]]
+name = "default"
+job_relative_data_arr = []
+
+[[profiles.job_build_presets]]
+job_name = "Folder/test_job"
+
+[[profiles.job_build_presets.presets]]
+name = "perf-profiling"
+
+[profiles.job_build_presets.presets.params.params]
+EnableContentPreview = false
+"#;
+
+        let db = crate::db::db_struct::parse_content_with_upgrade(8, content).unwrap();
+        let presets = &db.profiles[0].job_build_presets;
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].job_name, "Folder/test_job");
+        assert_eq!(presets[0].presets[0].name, "perf-profiling");
+    }
+}
@@ -0,0 +1,49 @@
+use crate::db::db_struct::fp_db_v8::ServerProfile;
+use crate::db::db_struct::versioned_data::{UpgradeValue, VersionedData};
+use crate::define_versioned_data_type;
+use serde::{Deserialize, Serialize, Serializer};
+
+pub const VERSION_FP_DB_V9: u32 = 9;
+
+define_versioned_data_type!(FpDbV9, VERSION_FP_DB_V9, {
+    pub extract_repo: Option<String>,
+    pub extract_locator_pattern: Option<String>,
+    pub extract_s_locator_template: Option<String>,
+
+    /// the api token, password and session of a profile are kept in the credential store,
+    /// and are in the db only in the plaintext mode, or if they failed to move there
+    /// from an old db.
+    #[serde(default)]
+    pub profiles: Vec<ServerProfile>,
+
+    #[serde(default)]
+    pub never_check_version: bool,
+    #[serde(default)]
+    pub auto_update_enabled: bool,
+    pub latest_remote_version: Option<String>,
+    }
+);
+
+impl VersionedData for FpDbV9 {
+    fn parse_next_version(self: Box<Self>) -> UpgradeValue {
+        UpgradeValue::Latest(*self)
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+mod tests {
+    use super::*;
+
+    impl PartialEq for FpDbV9 {
+        fn eq(&self, other: &Self) -> bool {
+            self.extract_repo == other.extract_repo
+                && self.extract_locator_pattern == other.extract_locator_pattern
+                && self.extract_s_locator_template == other.extract_s_locator_template
+                && self.profiles == other.profiles
+                && self.never_check_version == other.never_check_version
+                && self.auto_update_enabled == other.auto_update_enabled
+                && self.latest_remote_version == other.latest_remote_version
+        }
+    }
+}
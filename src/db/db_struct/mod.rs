@@ -5,6 +5,8 @@ use crate::db::db_struct::fp_db_v4::{FpDbV4, VERSION_FP_DB_V4};
 use crate::db::db_struct::fp_db_v5::{FpDbV5, VERSION_FP_DB_V5};
 use crate::db::db_struct::fp_db_v6::{FpDbV6, VERSION_FP_DB_V6};
 use crate::db::db_struct::fp_db_v7::{FpDbV7, VERSION_FP_DB_V7};
+use crate::db::db_struct::fp_db_v8::{FpDbV8, VERSION_FP_DB_V8};
+use crate::db::db_struct::fp_db_v9::{FpDbV9, VERSION_FP_DB_V9};
 use crate::db::db_struct::versioned_data::{UpgradeValue, VersionedData};
 
 pub mod db_status;
@@ -16,10 +18,14 @@ pub mod fp_db_v4;
 pub mod fp_db_v5;
 pub mod fp_db_v6;
 pub mod fp_db_v7;
+pub mod fp_db_v8;
+pub mod fp_db_v9;
 pub mod version_only;
 pub mod versioned_data;
 
-pub type LatestVersionData = FpDbV7;
+pub type LatestVersionData = FpDbV9;
+
+pub const LATEST_VERSION: u32 = VERSION_FP_DB_V9;
 
 /// # parse content with upgrade
 ///
@@ -51,6 +57,12 @@ fn parse_content_by_version(
     content: &str,
 ) -> Result<Box<dyn VersionedData>, toml::de::Error> {
     match version {
+        VERSION_FP_DB_V9 => {
+            FpDbV9::parse_from_string(content).map(|v| Box::new(v) as Box<dyn VersionedData>)
+        }
+        VERSION_FP_DB_V8 => {
+            FpDbV8::parse_from_string(content).map(|v| Box::new(v) as Box<dyn VersionedData>)
+        }
         VERSION_FP_DB_V7 => {
             FpDbV7::parse_from_string(content).map(|v| Box::new(v) as Box<dyn VersionedData>)
         }
@@ -0,0 +1,468 @@
+use super::layer::{is_valid_layer, join_key, parse_toml_value, ConfigOrigin};
+use super::{
+    find_project_runtime_config, resolve_runtime_config_path, HTTP_CONFIG_KEYS, JOBS_KEY,
+    JOB_CONFIG_KEYS, PROJECT_RUNTIME_CONFIG_FILE_NAME, RUNTIME_CONFIG_KEYS,
+    USER_RUNTIME_CONFIG_PATH,
+};
+use crate::constant::log::*;
+use crate::constant::util::get_similar_strings;
+use crate::credential::{CredentialStoreKind, CREDENTIAL_STORE_VALUES};
+use clap::ValueEnum;
+use formatx::formatx;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+use toml_edit::{DocumentMut, Key, TableLike};
+
+const PLACEHOLDER_ID: &str = "{ID}";
+const PLACEHOLDER_AUTO_DETECT: &str = "{*}";
+
+/// # ConfigLayerKind
+///
+/// The file layers of the runtime config which `fp config` writes to.
+#[derive(Debug, PartialEq, Clone, Copy, ValueEnum)]
+pub enum ConfigLayerKind {
+    /// `fp-config.toml` next to the executable, shared by the users of it.
+    Exe,
+    /// `~/.config/fp/config.toml`.
+    User,
+    /// `.fp.toml` of the project, found from the current directory upwards.
+    /// created in the current directory if it is not found.
+    Project,
+}
+
+impl ConfigLayerKind {
+    pub fn get_path(&self) -> Option<PathBuf> {
+        match self {
+            ConfigLayerKind::Exe => Some(resolve_runtime_config_path()),
+            ConfigLayerKind::User => {
+                dirs::home_dir().map(|home| home.join(USER_RUNTIME_CONFIG_PATH))
+            }
+            ConfigLayerKind::Project => std::env::current_dir().ok().map(|dir| {
+                find_project_runtime_config(&dir)
+                    .unwrap_or_else(|| dir.join(PROJECT_RUNTIME_CONFIG_FILE_NAME))
+            }),
+        }
+    }
+
+    /// whether a value from `origin` takes precedence over the one of this layer.
+    pub fn is_overridden_by(&self, origin: &ConfigOrigin) -> bool {
+        let rank = match self {
+            ConfigLayerKind::Exe => 1,
+            ConfigLayerKind::User => 2,
+            ConfigLayerKind::Project => 3,
+        };
+        let origin_rank = match origin {
+            ConfigOrigin::Build => 0,
+            ConfigOrigin::Executable(_) => 1,
+            ConfigOrigin::User(_) => 2,
+            ConfigOrigin::Project(_) => 3,
+            ConfigOrigin::Env(_) => 4,
+            ConfigOrigin::Cli => 5,
+        };
+        origin_rank > rank
+    }
+}
+
+/// # check config key
+///
+/// Whether `key` is a key of a value, like `repo_template`, `http.timeout_secs`,
+/// `job_groups.all-platform` or `jobs."Client.*".exe_file_name`.
+/// Fails with the similar keys if it is unknown.
+pub fn check_config_key(key: &str) -> Result<(), String> {
+    let segments = parse_key(key)?;
+    let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+    let known = match segments.as_slice() {
+        ["job_groups" | "http" | JOBS_KEY] => false,
+        [key] => RUNTIME_CONFIG_KEYS.contains(key),
+        ["job_groups", _] => true,
+        ["http", key] => HTTP_CONFIG_KEYS.contains(key),
+        [JOBS_KEY, pattern, rest @ ..] if !rest.is_empty() => {
+            return check_job_config_key(pattern, &rest.join("."));
+        }
+        _ => false,
+    };
+
+    if known {
+        Ok(())
+    } else {
+        Err(unknown_key_message(key))
+    }
+}
+
+fn unknown_key_message(key: &str) -> String {
+    let candidates = get_value_keys();
+    let suggestions = get_similar_strings(key, candidates.iter().map(String::as_str));
+    if suggestions.is_empty() {
+        formatx!(ERR_UNKNOWN_CONFIG_KEY, key).unwrap_or_default()
+    } else {
+        formatx!(
+            ERR_UNKNOWN_CONFIG_KEY_SUGGESTIONS,
+            key,
+            suggestions.join(", ")
+        )
+        .unwrap_or_default()
+    }
+}
+
+/// `key` in the `[jobs]` table of `pattern`, which should be one of [JOB_CONFIG_KEYS].
+fn check_job_config_key(pattern: &str, key: &str) -> Result<(), String> {
+    if JOB_CONFIG_KEYS.contains(&key) {
+        return Ok(());
+    }
+
+    let prefix = join_key(JOBS_KEY, pattern);
+    let full_key = format!("{}.{}", prefix, key);
+    if get_value_keys().iter().any(|value_key| value_key == key) {
+        return Err(
+            formatx!(ERR_CONFIG_NOT_PER_JOB, full_key, JOB_CONFIG_KEYS.join(", "))
+                .unwrap_or_default(),
+        );
+    }
+
+    let suggestions = get_similar_strings(key, JOB_CONFIG_KEYS)
+        .into_iter()
+        .map(|key| join_key(&prefix, key))
+        .collect::<Vec<_>>();
+    if suggestions.is_empty() {
+        Err(formatx!(ERR_UNKNOWN_CONFIG_KEY, full_key).unwrap_or_default())
+    } else {
+        Err(formatx!(
+            ERR_UNKNOWN_CONFIG_KEY_SUGGESTIONS,
+            full_key,
+            suggestions.join(", ")
+        )
+        .unwrap_or_default())
+    }
+}
+
+/// the keys of the values except the groups of `job_groups` and the overrides of `jobs`,
+/// which are named by the user.
+fn get_value_keys() -> Vec<String> {
+    RUNTIME_CONFIG_KEYS
+        .iter()
+        .filter(|key| !matches!(**key, "job_groups" | "http" | JOBS_KEY))
+        .map(|key| key.to_string())
+        .chain(HTTP_CONFIG_KEYS.iter().map(|key| join_key("http", key)))
+        .collect()
+}
+
+fn parse_key(key: &str) -> Result<Vec<String>, String> {
+    Key::parse(key)
+        .map(|keys| keys.iter().map(|key| key.get().to_string()).collect())
+        .map_err(|_| formatx!(ERR_INVALID_CONFIG_KEY, key).unwrap_or_default())
+}
+
+/// # find unknown keys
+///
+/// the keys in a runtime config file which are ignored, with the similar keys if any.
+pub fn find_unknown_keys(table: &Table) -> Vec<String> {
+    let mut unknown = vec![];
+    for (key, value) in table {
+        match (key.as_str(), value) {
+            ("job_groups", Value::Table(_)) => {}
+            ("http", Value::Table(http)) => unknown.extend(
+                http.keys()
+                    .map(|key| join_key("http", key))
+                    .filter_map(|key| check_config_key(&key).err()),
+            ),
+            (JOBS_KEY, Value::Table(jobs)) => {
+                for (pattern, overrides) in jobs {
+                    let prefix = join_key(JOBS_KEY, pattern);
+                    match overrides {
+                        Value::Table(overrides) => unknown.extend(
+                            overrides
+                                .keys()
+                                .map(|key| join_key(&prefix, key))
+                                .filter_map(|key| check_config_key(&key).err()),
+                        ),
+                        _ => unknown.extend(check_config_key(&prefix).err()),
+                    }
+                }
+            }
+            (key, _) => unknown.extend(check_config_key(&join_key("", key)).err()),
+        }
+    }
+
+    unknown
+}
+
+/// the unknown keys in the file, or none if it cannot be read.
+pub fn find_unknown_keys_in_file(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| toml::from_str::<Table>(&raw).ok())
+        .map(|table| find_unknown_keys(&table))
+        .unwrap_or_default()
+}
+
+/// # validate config value
+///
+/// Check `value` of `key`, and get it in toml.
+/// The repo is required to exist unless `force`, for it may be unreachable for now.
+pub fn validate_config_value(key: &str, value: &str, force: bool) -> Result<String, String> {
+    check_config_key(key)?;
+    let toml_value = parse_toml_value(key, value)
+        .ok_or(formatx!(ERR_INVALID_CONFIG_VALUE, value, key).unwrap_or_default())?;
+
+    // the same checks for the overrides of a job.
+    let segments = parse_key(key)?;
+    let leaf = match segments
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [leaf] | [JOBS_KEY, _, leaf] => leaf.to_string(),
+        _ => String::new(),
+    };
+    match leaf.as_str() {
+        "locator_pattern" => check_locator_pattern(value)?,
+        "jenkins_url" => check_url(value)?,
+        "credential_store" if CredentialStoreKind::parse(value).is_none() => {
+            return Err(formatx!(
+                ERR_CONFIG_INVALID_CREDENTIAL_STORE,
+                value,
+                CREDENTIAL_STORE_VALUES.join(", ")
+            )
+            .unwrap_or_default());
+        }
+        "repo_template" if !force && !Path::new(value).is_dir() => {
+            return Err(formatx!(ERR_CONFIG_REPO_NOT_FOUND, value).unwrap_or_default());
+        }
+        _ => {}
+    }
+
+    Ok(toml_value)
+}
+
+/// `{ID}` is the ci number, which is required once. `{*}` matches anything.
+fn check_locator_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.matches(PLACEHOLDER_ID).count() != 1 {
+        return Err(formatx!(ERR_CONFIG_LOCATOR_PATTERN_ID, pattern).unwrap_or_default());
+    }
+
+    let unknown = Regex::new(r"\{[^}]*}").ok().and_then(|re| {
+        re.find_iter(pattern)
+            .map(|placeholder| placeholder.as_str())
+            .find(|placeholder| ![PLACEHOLDER_ID, PLACEHOLDER_AUTO_DETECT].contains(placeholder))
+    });
+    match unknown {
+        Some(placeholder) => {
+            Err(
+                formatx!(ERR_CONFIG_LOCATOR_PATTERN_PLACEHOLDER, placeholder, pattern)
+                    .unwrap_or_default(),
+            )
+        }
+        None => Ok(()),
+    }
+}
+
+fn check_url(value: &str) -> Result<(), String> {
+    match url::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host().is_some() => Ok(()),
+        _ => Err(formatx!(ERR_CONFIG_INVALID_URL, value).unwrap_or_default()),
+    }
+}
+
+/// # set config value
+///
+/// Write `key = value` to the file, keeping the comments and the other keys.
+/// `value` is in toml. The file and its directory are created if they do not exist.
+pub fn set_config_value(path: &Path, key: &str, value: &str) -> Result<(), String> {
+    let mut document = read_document(path)?;
+    let keys = parse_key(key)?;
+    let value = value
+        .parse::<toml_edit::Value>()
+        .map_err(|e| e.to_string())?;
+
+    let Some((last, parents)) = keys.split_last() else {
+        return Err(formatx!(ERR_INVALID_CONFIG_KEY, key).unwrap_or_default());
+    };
+    let mut table: &mut dyn TableLike = document.as_table_mut();
+    for parent in parents {
+        table = table
+            .entry(parent)
+            .or_insert_with(implicit_table)
+            .as_table_like_mut()
+            .ok_or(formatx!(ERR_CONFIG_NOT_TABLE, parent).unwrap_or_default())?;
+    }
+    table.insert(last, toml_edit::Item::Value(value));
+
+    write_document(path, &document)
+}
+
+/// # unset config value
+///
+/// Remove `key` from the file. Returns whether it was in the file.
+pub fn unset_config_value(path: &Path, key: &str) -> Result<bool, String> {
+    if !path.is_file() {
+        return Ok(false);
+    }
+
+    let mut document = read_document(path)?;
+    let keys = parse_key(key)?;
+    let Some((last, parents)) = keys.split_last() else {
+        return Ok(false);
+    };
+
+    let mut table: &mut dyn TableLike = document.as_table_mut();
+    for parent in parents {
+        match table
+            .get_mut(parent)
+            .and_then(|item| item.as_table_like_mut())
+        {
+            Some(parent) => table = parent,
+            None => return Ok(false),
+        }
+    }
+    if table.remove(last).is_none() {
+        return Ok(false);
+    }
+
+    write_document(path, &document).map(|_| true)
+}
+
+/// a table created for a nested key, without a header of its own if it has only tables,
+/// like `[jobs]` of `[jobs."Client.*"]`.
+fn implicit_table() -> toml_edit::Item {
+    let mut table = toml_edit::Table::new();
+    table.set_implicit(true);
+    toml_edit::Item::Table(table)
+}
+
+fn read_document(path: &Path) -> Result<DocumentMut, String> {
+    let raw = if path.is_file() {
+        fs::read_to_string(path).map_err(|e| e.to_string())?
+    } else {
+        String::new()
+    };
+
+    raw.parse::<DocumentMut>().map_err(|e| {
+        formatx!(
+            ERR_CONFIG_FILE_INVALID,
+            path.display().to_string(),
+            e.to_string()
+        )
+        .unwrap_or_default()
+    })
+}
+
+/// the file is not written if the result would be ignored as a whole.
+fn write_document(path: &Path, document: &DocumentMut) -> Result<(), String> {
+    let raw = document.to_string();
+    if !toml::from_str::<Table>(&raw).is_ok_and(|table| is_valid_layer(&table)) {
+        return Err(
+            formatx!(ERR_CONFIG_FILE_INVALID, path.display().to_string(), "").unwrap_or_default(),
+        );
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, raw).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_config_key() {
+        assert!(check_config_key("repo_template").is_ok());
+        assert!(check_config_key("http.timeout_secs").is_ok());
+        assert!(check_config_key("job_groups.\"Client.Opt\"").is_ok());
+        assert!(check_config_key("http").is_err());
+
+        let e = check_config_key("repo_templete").unwrap_err();
+        assert!(e.contains("repo_template"));
+        let e = check_config_key("http.timeout").unwrap_err();
+        assert!(e.contains("http.timeout_secs"));
+
+        assert!(check_config_key("jobs.\"Client.*\".exe_file_name").is_ok());
+        assert!(check_config_key("jobs.\"Client.*\"").is_err());
+        let e = check_config_key("jobs.\"Client.*\".jenkins_url").unwrap_err();
+        assert!(e.contains("exe_file_name"));
+        let e = check_config_key("jobs.Client.exe_file").unwrap_err();
+        assert!(e.contains("jobs.Client.exe_file_name"));
+    }
+
+    #[test]
+    fn test_validate_config_value() {
+        assert!(validate_config_value("locator_pattern", "{ID}-Hash.{*}", false).is_ok());
+        assert!(validate_config_value("locator_pattern", "CL-{*}", false).is_err());
+        assert!(validate_config_value("locator_pattern", "{ID}-{HASH}", false).is_err());
+        assert!(validate_config_value("jobs.Server.locator_pattern", "CL-{*}", false).is_err());
+
+        assert!(validate_config_value("jenkins_url", "https://jenkins.example.com", false).is_ok());
+        assert!(validate_config_value("jenkins_url", "jenkins.example.com", false).is_err());
+
+        assert!(validate_config_value("credential_store", "file", false).is_ok());
+        assert!(validate_config_value("credential_store", "vault", false).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing").display().to_string();
+        assert!(validate_config_value("repo_template", &missing, false).is_err());
+        assert!(validate_config_value("repo_template", &missing, true).is_ok());
+
+        assert_eq!(
+            validate_config_value("http.timeout_secs", "60", false),
+            Ok("60".to_string())
+        );
+        assert!(validate_config_value("http.timeout_secs", "ten", false).is_err());
+    }
+
+    #[test]
+    fn test_set_and_unset_config_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fp").join("config.toml");
+
+        set_config_value(&path, "repo_template", "'D:\\builds'").unwrap();
+        set_config_value(&path, "http.timeout_secs", "60").unwrap();
+        fs::write(
+            &path,
+            format!("# the repo\n{}", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+        set_config_value(&path, "job_groups.\"Client.All\"", "[\"a\", \"b\"]").unwrap();
+        set_config_value(&path, "jobs.\"Client.*\".exe_file_name", "'Client.exe'").unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(raw.contains("# the repo"));
+        let table = toml::from_str::<Table>(&raw).unwrap();
+        assert_eq!(table["repo_template"].as_str(), Some("D:\\builds"));
+        assert_eq!(table["http"]["timeout_secs"].as_integer(), Some(60));
+        assert_eq!(
+            table["job_groups"]["Client.All"].as_array().map(Vec::len),
+            Some(2)
+        );
+        assert_eq!(
+            table["jobs"]["Client.*"]["exe_file_name"].as_str(),
+            Some("Client.exe")
+        );
+        assert!(raw.contains("[jobs.\"Client.*\"]"));
+        assert!(!raw.contains("[jobs]"));
+
+        assert_eq!(unset_config_value(&path, "http.timeout_secs"), Ok(true));
+        assert_eq!(unset_config_value(&path, "http.timeout_secs"), Ok(false));
+        assert!(!fs::read_to_string(&path).unwrap().contains("timeout_secs"));
+    }
+
+    #[test]
+    fn test_find_unknown_keys() {
+        let table = toml::from_str::<Table>(
+            "repo_templete = 'a'\njenkins_url = 'b'\n[http]\ntimeout = 1\n[job_groups]\nall = []\n\
+            [jobs.Client]\nexe_file_name = 'a'\njenkins_url = 'b'",
+        )
+        .unwrap();
+
+        let unknown = find_unknown_keys(&table);
+        assert_eq!(unknown.len(), 3);
+        assert!(unknown
+            .iter()
+            .any(|e| e.contains("jobs.Client.jenkins_url")));
+        assert!(unknown.iter().any(|e| e.contains("repo_template")));
+        assert!(unknown.iter().any(|e| e.contains("http.timeout_secs")));
+    }
+}
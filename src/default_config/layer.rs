@@ -0,0 +1,703 @@
+use super::edit::{check_config_key, find_unknown_keys};
+use super::{
+    build_default_table, runtime_config_files, RuntimeConfigFile, JOBS_KEY, JOB_CONFIG_KEYS,
+    PROJECT_IGNORED_KEYS, RUNTIME_CONFIG_KEYS,
+};
+use crate::constant::log::*;
+use crate::pretty_log::{colored_println, ThemeColor};
+use formatx::formatx;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use toml::{Table, Value};
+
+/// prefix of the env vars like `FP_REPO_TEMPLATE`. `__` is the `.` of a nested key,
+/// like `FP_HTTP__TIMEOUT_SECS` for `http.timeout_secs`.
+/// `[jobs]` cannot be set by them, but by `--config` like `jobs."Client.*".exe_file_name=a.exe`.
+const ENV_PREFIX: &str = "FP_";
+const ENV_KEY_SEPARATOR: &str = "__";
+
+/// values of these keys are not printed.
+const SECRET_KEYS: [&str; 2] = ["query_token_github", "http.client_cert_password"];
+const SECRET_MASK: &str = "******";
+
+/// the `--config KEY=VALUE` of the command line, set before the runtime config is loaded.
+static CLI_OVERRIDES: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+/// # ConfigOrigin
+///
+/// Where a value of the runtime config comes from, from low to high priority.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConfigOrigin {
+    /// compiled in by the env vars of `build.rs`.
+    Build,
+    /// `fp-config.toml` next to the executable.
+    Executable(PathBuf),
+    /// `~/.config/fp/config.toml`.
+    User(PathBuf),
+    /// `.fp.toml` of the project.
+    Project(PathBuf),
+    /// an env var like `FP_REPO_TEMPLATE`, with its name.
+    Env(String),
+    /// `--config KEY=VALUE` of the command line.
+    Cli,
+}
+
+impl ConfigOrigin {
+    pub fn get_path(&self) -> Option<&Path> {
+        match self {
+            ConfigOrigin::Executable(path)
+            | ConfigOrigin::User(path)
+            | ConfigOrigin::Project(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ConfigOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ConfigOrigin::Build => CONFIG_ORIGIN_BUILD.to_string(),
+            ConfigOrigin::Executable(path)
+            | ConfigOrigin::User(path)
+            | ConfigOrigin::Project(path) => path.display().to_string(),
+            ConfigOrigin::Env(name) => formatx!(CONFIG_ORIGIN_ENV, name).unwrap_or_default(),
+            ConfigOrigin::Cli => CONFIG_ORIGIN_CLI.to_string(),
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// # RuntimeConfigEntry
+///
+/// An effective value of the runtime config, like `http.timeout_secs = 10`.
+#[derive(Debug, PartialEq)]
+pub struct RuntimeConfigEntry {
+    /// dotted key like `http.timeout_secs`.
+    pub key: String,
+    /// the value in toml, `None` if it is not set by any layer.
+    pub value: Option<String>,
+    pub origin: Option<ConfigOrigin>,
+}
+
+/// # set cli overrides
+///
+/// Use the `--config KEY=VALUE` of the command line as the highest layer.
+/// It takes effect only if it is set before the runtime config is loaded.
+pub fn set_cli_overrides(overrides: Vec<(String, String)>) {
+    let _ = CLI_OVERRIDES.set(overrides);
+}
+
+/// # parse config override
+///
+/// Parse `KEY=VALUE` of `--config`. The value is in toml like `10` or `["a", "b"]`,
+/// or a plain string without the quotes.
+pub fn parse_config_override(value: &str) -> Result<(String, String), String> {
+    let (key, value) = value
+        .split_once('=')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty())
+        .ok_or(ERR_INVALID_CONFIG_OVERRIDE.to_string())?;
+
+    check_config_key(key)?;
+    if parse_layer(key, value).is_none() {
+        return Err(formatx!(ERR_INVALID_CONFIG_VALUE, value, key).unwrap_or_default());
+    }
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// # LayeredConfig
+///
+/// The runtime config merged from all the layers, with the origin of every value.
+#[derive(Debug, Default)]
+pub(super) struct LayeredConfig {
+    table: Table,
+    /// origins of the values by their dotted keys.
+    origins: HashMap<String, ConfigOrigin>,
+    /// the merged layers from low to high priority, to apply the `[jobs]` tables layer by layer.
+    layers: Vec<(ConfigOrigin, Table)>,
+}
+
+impl LayeredConfig {
+    /// # load
+    ///
+    /// Merge the layers from low to high priority: the compiled defaults, the files,
+    /// the `FP_*` env vars and the `--config` of the command line.
+    /// A layer which cannot be parsed is ignored as a whole, with a warning.
+    pub(super) fn load() -> Self {
+        let mut config = Self::default();
+        let mut warnings = vec![];
+        config.merge(&ConfigOrigin::Build, build_default_table());
+
+        for origin in runtime_config_files() {
+            warnings.extend(config.merge_file(&origin));
+        }
+
+        // the env vars which are not in utf-8 cannot be the ones of fp.
+        let mut env_vars = std::env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect::<Vec<_>>();
+        env_vars.sort();
+        for (name, value) in env_vars {
+            let Some(key) = env_key(&name) else {
+                continue;
+            };
+            let origin = ConfigOrigin::Env(name);
+            match parse_layer(&key, &value) {
+                Some(table) => config.merge(&origin, table),
+                None => warnings.push(
+                    formatx!(
+                        WARN_CONFIG_LAYER_SKIPPED,
+                        origin.to_string(),
+                        formatx!(ERR_INVALID_CONFIG_VALUE, &value, &key).unwrap_or_default()
+                    )
+                    .unwrap_or_default(),
+                ),
+            }
+        }
+
+        for (key, value) in CLI_OVERRIDES.get().into_iter().flatten() {
+            if let Some(table) = parse_layer(key, value) {
+                config.merge(&ConfigOrigin::Cli, table);
+            }
+        }
+
+        let mut stdout = std::io::stdout();
+        for warning in warnings {
+            colored_println(&mut stdout, ThemeColor::Warn, &warning);
+        }
+
+        config
+    }
+
+    /// merge the file of `origin` if it exists, and get the warnings of it,
+    /// like the unknown keys. the keys of [PROJECT_IGNORED_KEYS] are dropped from `.fp.toml`.
+    fn merge_file(&mut self, origin: &ConfigOrigin) -> Vec<String> {
+        let Some(path) = origin.get_path() else {
+            return vec![];
+        };
+
+        let mut table = match read_layer(path) {
+            Ok(Some(table)) => table,
+            Ok(None) => return vec![],
+            Err(e) => {
+                return vec![
+                    formatx!(WARN_CONFIG_LAYER_SKIPPED, origin.to_string(), e).unwrap_or_default()
+                ];
+            }
+        };
+
+        let mut warnings = find_unknown_keys(&table)
+            .into_iter()
+            .map(|e| {
+                formatx!(WARN_CONFIG_UNKNOWN_KEY_IN_FILE, e, origin.to_string()).unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        if matches!(origin, ConfigOrigin::Project(_)) {
+            for key in remove_project_ignored_keys(&mut table) {
+                warnings.push(
+                    formatx!(WARN_CONFIG_IGNORED_IN_PROJECT, key, origin.to_string())
+                        .unwrap_or_default(),
+                );
+            }
+        }
+        self.merge(origin, table);
+
+        warnings
+    }
+
+    fn merge(&mut self, origin: &ConfigOrigin, layer: Table) {
+        merge_table(
+            &mut self.table,
+            &mut self.origins,
+            layer.clone(),
+            origin,
+            "",
+        );
+        self.layers.push((origin.clone(), layer));
+    }
+
+    /// # for job
+    ///
+    /// The config of `job_name`, where the `[jobs]` tables matching it are applied
+    /// layer by layer. they override the values of their own layer and the lower ones,
+    /// but not the values of the higher layers like the env vars.
+    pub(super) fn for_job(&self, job_name: &str) -> Self {
+        let mut config = Self::default();
+        for (origin, layer) in &self.layers {
+            let mut layer = layer.clone();
+            let jobs = layer.remove(JOBS_KEY);
+            merge_table(&mut config.table, &mut config.origins, layer, origin, "");
+
+            for overrides in job_tables(jobs.as_ref(), job_name) {
+                merge_table(
+                    &mut config.table,
+                    &mut config.origins,
+                    overrides,
+                    origin,
+                    "",
+                );
+            }
+        }
+
+        config
+    }
+
+    /// the keys set by the `[jobs]` tables matching `job_name` in any layer.
+    pub(super) fn job_keys(&self, job_name: &str) -> HashSet<String> {
+        self.layers
+            .iter()
+            .flat_map(|(_, layer)| job_tables(layer.get(JOBS_KEY), job_name))
+            .flat_map(|overrides| overrides.into_iter().map(|(key, _)| key))
+            .collect()
+    }
+
+    pub(super) fn to_config_file(&self) -> RuntimeConfigFile {
+        Value::Table(self.table.clone())
+            .try_into::<RuntimeConfigFile>()
+            .unwrap_or_default()
+    }
+
+    /// # entries
+    ///
+    /// every known key in order. The values of a table like `[http]` are listed one by one.
+    pub(super) fn entries(&self) -> Vec<RuntimeConfigEntry> {
+        let mut entries = vec![];
+        for key in RUNTIME_CONFIG_KEYS {
+            match self.table.get(key) {
+                Some(Value::Table(table)) if !table.is_empty() => {
+                    self.collect_entries(&mut entries, key, table)
+                }
+                Some(value) if !matches!(value, Value::Table(_)) => {
+                    entries.push(self.entry(key.to_string(), value))
+                }
+                _ => entries.push(RuntimeConfigEntry {
+                    key: key.to_string(),
+                    value: None,
+                    origin: None,
+                }),
+            }
+        }
+
+        entries
+    }
+
+    fn collect_entries(&self, entries: &mut Vec<RuntimeConfigEntry>, prefix: &str, table: &Table) {
+        for (key, value) in table {
+            let key = join_key(prefix, key);
+            match value {
+                Value::Table(table) => self.collect_entries(entries, &key, table),
+                value => entries.push(self.entry(key, value)),
+            }
+        }
+    }
+
+    fn entry(&self, key: String, value: &Value) -> RuntimeConfigEntry {
+        let value = if SECRET_KEYS.contains(&key.as_str()) {
+            SECRET_MASK.to_string()
+        } else {
+            value.to_string()
+        };
+
+        RuntimeConfigEntry {
+            origin: self.origins.get(&key).cloned(),
+            value: Some(value),
+            key,
+        }
+    }
+}
+
+/// the `[jobs]` tables of a layer matching `job_name` from low to high priority:
+/// the globs by their literal length, and then the exact name.
+/// only the keys of [JOB_CONFIG_KEYS] are kept.
+fn job_tables(jobs: Option<&Value>, job_name: &str) -> Vec<Table> {
+    let Some(Value::Table(jobs)) = jobs else {
+        return vec![];
+    };
+
+    let mut tables = jobs
+        .iter()
+        .filter_map(|(pattern, value)| value.as_table().map(|table| (pattern, table)))
+        .filter(|(pattern, _)| is_job_matched(pattern, job_name))
+        .collect::<Vec<_>>();
+    tables.sort_by_key(|(pattern, _)| {
+        (
+            pattern.as_str() == job_name,
+            pattern.chars().filter(|c| !matches!(c, '*' | '?')).count(),
+        )
+    });
+
+    tables
+        .into_iter()
+        .map(|(_, table)| {
+            table
+                .iter()
+                .filter(|(key, _)| JOB_CONFIG_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect()
+        })
+        .collect()
+}
+
+/// the values of `src` replace the ones in `dest`, and the tables are merged key by key.
+/// blank strings and empty arrays are taken as unset, so that they do not hide the lower layers.
+fn merge_table(
+    dest: &mut Table,
+    origins: &mut HashMap<String, ConfigOrigin>,
+    src: Table,
+    origin: &ConfigOrigin,
+    prefix: &str,
+) {
+    for (key, value) in src {
+        let path = join_key(prefix, &key);
+        match value {
+            Value::Table(table) => {
+                let dest_table = dest
+                    .entry(key)
+                    .or_insert_with(|| Value::Table(Table::new()));
+                if !dest_table.is_table() {
+                    *dest_table = Value::Table(Table::new());
+                }
+                if let Value::Table(dest_table) = dest_table {
+                    merge_table(dest_table, origins, table, origin, &path);
+                }
+            }
+            value if is_blank(&value) => {}
+            value => {
+                origins.insert(path, origin.clone());
+                dest.insert(key, value);
+            }
+        }
+    }
+}
+
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::String(value) => value.trim().is_empty(),
+        Value::Array(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+/// join the keys with `.`, quoting the ones which are not bare keys like a job name with dots.
+pub(super) fn join_key(prefix: &str, key: &str) -> String {
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let key = if is_bare {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    };
+
+    if prefix.is_empty() {
+        key
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// # is job matched
+///
+/// whether `pattern` of `[jobs]` is `job_name`, or a glob matching it,
+/// where `*` is any characters and `?` is one.
+pub(super) fn is_job_matched(pattern: &str, job_name: &str) -> bool {
+    if pattern == job_name {
+        return true;
+    }
+    if !pattern.contains(['*', '?']) {
+        return false;
+    }
+
+    let regex = pattern
+        .split('*')
+        .map(|part| {
+            part.split('?')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(".")
+        })
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{}$", regex)).is_ok_and(|re| re.is_match(job_name))
+}
+
+/// the job names in `[jobs]` would be upper-cased in the names of the env vars.
+fn is_known_key(key: &str) -> bool {
+    let root = key.split('.').next().unwrap_or_default().trim();
+    root != JOBS_KEY && RUNTIME_CONFIG_KEYS.contains(&root)
+}
+
+pub(super) fn is_valid_layer(table: &Table) -> bool {
+    Value::Table(table.clone())
+        .try_into::<RuntimeConfigFile>()
+        .is_ok()
+}
+
+/// the layer of the file, or none if it does not exist.
+/// fails with the reason if it cannot be read or parsed.
+fn read_layer(path: &Path) -> Result<Option<Table>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let table = toml::from_str::<Table>(&raw).map_err(|e| e.message().to_string())?;
+    Value::Table(table.clone())
+        .try_into::<RuntimeConfigFile>()
+        .map_err(|e| e.message().to_string())?;
+    Ok(Some(table))
+}
+
+/// # is ignored in project
+///
+/// whether `key` is ignored in `.fp.toml`, like `jenkins_url`.
+pub fn is_ignored_in_project(key: &str) -> bool {
+    PROJECT_IGNORED_KEYS.contains(&key)
+}
+
+/// remove the keys of [PROJECT_IGNORED_KEYS] from `table`, and get the removed ones.
+fn remove_project_ignored_keys(table: &mut Table) -> Vec<String> {
+    let mut removed = vec![];
+    for key in PROJECT_IGNORED_KEYS {
+        let value = match key.split_once('.') {
+            Some((parent, key)) => table
+                .get_mut(parent)
+                .and_then(Value::as_table_mut)
+                .and_then(|parent| parent.remove(key)),
+            None => table.remove(key),
+        };
+        if value.is_some() {
+            removed.push(key.to_string());
+        }
+    }
+
+    removed
+}
+
+/// # parse toml value
+///
+/// `value` in toml for `key`. it is taken as a string
+/// if it is not valid toml, or not of the type of the key.
+pub(super) fn parse_toml_value(key: &str, value: &str) -> Option<String> {
+    let quoted = Value::String(value.to_string()).to_string();
+    [value.to_string(), quoted]
+        .into_iter()
+        .find(|value| parse_layer_strictly(key, value).is_some())
+}
+
+/// a layer of a single `key = value`.
+fn parse_layer(key: &str, value: &str) -> Option<Table> {
+    parse_toml_value(key, value).and_then(|value| parse_layer_strictly(key, &value))
+}
+
+fn parse_layer_strictly(key: &str, value: &str) -> Option<Table> {
+    toml::from_str::<Table>(&format!("{} = {}", key, value))
+        .ok()
+        .filter(is_valid_layer)
+}
+
+/// the dotted key of the env var, or none if it is not one of the runtime config.
+fn env_key(name: &str) -> Option<String> {
+    let key = name
+        .strip_prefix(ENV_PREFIX)?
+        .to_ascii_lowercase()
+        .replace(ENV_KEY_SEPARATOR, ".");
+    is_known_key(&key).then_some(key)
+}
+
+#[cfg(test)]
+fn env_layer(name: &str, value: &str) -> Option<Table> {
+    env_key(name).and_then(|key| parse_layer(&key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(raw: &str) -> Table {
+        toml::from_str::<Table>(raw).unwrap()
+    }
+
+    #[test]
+    fn test_merge_layers() {
+        let user = ConfigOrigin::User(PathBuf::from("config.toml"));
+        let mut config = LayeredConfig::default();
+        config.merge(
+            &ConfigOrigin::Build,
+            layer("repo_template = 'build'\nlocator_pattern = '{ID}'"),
+        );
+        config.merge(
+            &user,
+            layer("repo_template = 'user'\nlocator_pattern = ' '\n[http]\ntimeout_secs = 60"),
+        );
+        config.merge(
+            &ConfigOrigin::Env("FP_HTTP__MAX_RETRIES".to_string()),
+            env_layer("FP_HTTP__MAX_RETRIES", "5").unwrap(),
+        );
+
+        let file_config = config.to_config_file();
+        assert_eq!(file_config.repo_template.as_deref(), Some("user"));
+        assert_eq!(file_config.locator_pattern.as_deref(), Some("{ID}"));
+        let http = file_config.http.unwrap();
+        assert_eq!(http.timeout_secs, Some(60));
+        assert_eq!(http.max_retries, Some(5));
+
+        let entries = config.entries();
+        let get = |key: &str| entries.iter().find(|entry| entry.key == key).unwrap();
+        assert_eq!(get("repo_template").origin, Some(user.clone()));
+        assert_eq!(get("locator_pattern").origin, Some(ConfigOrigin::Build));
+        assert_eq!(get("http.timeout_secs").value.as_deref(), Some("60"));
+        assert_eq!(
+            get("http.max_retries").origin,
+            Some(ConfigOrigin::Env("FP_HTTP__MAX_RETRIES".to_string()))
+        );
+        assert_eq!(get("jenkins_url").value, None);
+    }
+
+    #[test]
+    fn test_parse_config_override() {
+        assert_eq!(
+            parse_config_override(r"repo_template=D:\builds"),
+            Ok(("repo_template".to_string(), r"D:\builds".to_string()))
+        );
+        assert!(parse_layer("p4_depot_path", "123").is_some());
+        assert!(parse_config_override("http.timeout_secs=ten").is_err());
+        assert!(parse_config_override("repo_templete=D:\\builds").is_err());
+        assert!(parse_config_override("repo_template").is_err());
+
+        assert_eq!(env_layer("FP_HOME", "/home/someone"), None);
+        assert_eq!(env_layer("FP_JOBS__CLIENT__REPO_TEMPLATE", "build"), None);
+        assert_eq!(env_layer("REPO_TEMPLATE", "build"), None);
+    }
+
+    #[test]
+    fn test_for_job() {
+        let user = ConfigOrigin::User(PathBuf::from("config.toml"));
+        let project = ConfigOrigin::Project(PathBuf::from(".fp.toml"));
+        let mut config = LayeredConfig::default();
+        config.merge(
+            &user,
+            layer(
+                r#"
+                repo_template = 'repo'
+                exe_file_name = 'Client.exe'
+                [jobs."FCM.*"]
+                repo_template = 'fcm'
+                jenkins_url = 'https://fcm.example.com'
+                [jobs."FCM.*.Server.*"]
+                exe_file_name = 'Server.exe'
+                [jobs."FCM.Dev.Server.Opt"]
+                repo_template = 'server'
+                mending_file_path = 'b.ini'
+                "#,
+            ),
+        );
+        config.merge(
+            &project,
+            layer("[jobs.\"FCM.*\"]\nmending_file_path = 'a.ini'"),
+        );
+
+        let job_config = config.for_job("FCM.Dev.Server.Opt");
+        let file_config = job_config.to_config_file();
+        assert_eq!(file_config.repo_template.as_deref(), Some("server"));
+        assert_eq!(file_config.exe_file_name.as_deref(), Some("Server.exe"));
+        assert_eq!(file_config.mending_file_path.as_deref(), Some("a.ini"));
+        assert!(file_config.jobs.is_none());
+
+        let entries = job_config.entries();
+        let get = |key: &str| entries.iter().find(|entry| entry.key == key).unwrap();
+        assert_eq!(get("mending_file_path").origin, Some(project));
+        assert_eq!(get("repo_template").origin, Some(user));
+
+        let file_config = config.for_job("FCM.Dev.Client.Opt").to_config_file();
+        assert_eq!(file_config.repo_template.as_deref(), Some("fcm"));
+        assert_eq!(file_config.exe_file_name.as_deref(), Some("Client.exe"));
+        assert_eq!(
+            config.job_keys("FCM.Dev.Client.Opt"),
+            HashSet::from(["repo_template".to_string(), "mending_file_path".to_string()])
+        );
+        assert!(config.job_keys("Tools").is_empty());
+
+        let env = ConfigOrigin::Env("FP_EXE_FILE_NAME".to_string());
+        config.merge(&env, env_layer("FP_EXE_FILE_NAME", "Env.exe").unwrap());
+        let file_config = config.for_job("FCM.Dev.Server.Opt").to_config_file();
+        assert_eq!(file_config.exe_file_name.as_deref(), Some("Env.exe"));
+        assert_eq!(file_config.jenkins_url, None);
+    }
+
+    #[test]
+    fn test_merge_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".fp.toml");
+        let project = ConfigOrigin::Project(path.clone());
+        let mut config = LayeredConfig::default();
+        assert!(config.merge_file(&project).is_empty());
+
+        fs::write(&path, "repo_template = 1\n").unwrap();
+        let warnings = config.merge_file(&project);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(&path.display().to_string()));
+
+        fs::write(&path, "repo_templete = 'repo'\n").unwrap();
+        let warnings = config.merge_file(&project);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("repo_template"));
+
+        fs::write(
+            &path,
+            "repo_template = 'repo'\njenkins_url = 'https://evil.example.com'\n\
+            [http]\ntimeout_secs = 60\nhttps_proxy = 'http://evil.example.com'\n\
+            no_proxy = '*'\nca_bundle_path = 'evil.pem'\nclient_cert_path = 'evil.p12'\n\
+            client_key_path = 'evil.key'\nclient_cert_password = 'evil'\n",
+        )
+        .unwrap();
+        let warnings = config.merge_file(&project);
+        assert_eq!(warnings.len(), 7);
+        let file_config = config.to_config_file();
+        assert_eq!(file_config.repo_template.as_deref(), Some("repo"));
+        assert_eq!(file_config.jenkins_url, None);
+        let http = file_config.http.unwrap();
+        assert_eq!(http.timeout_secs, Some(60));
+        assert_eq!(http.https_proxy, None);
+        assert_eq!(http.no_proxy, None);
+        assert_eq!(http.ca_bundle_path, None);
+        assert_eq!(http.client_cert_path, None);
+        assert_eq!(http.client_key_path, None);
+        assert_eq!(http.client_cert_password, None);
+
+        let user = ConfigOrigin::User(path);
+        assert!(config.merge_file(&user).is_empty());
+        assert!(config.to_config_file().jenkins_url.is_some());
+    }
+
+    #[test]
+    fn test_is_job_matched() {
+        assert!(is_job_matched("FCM.Dev.Client", "FCM.Dev.Client"));
+        assert!(is_job_matched("FCM.*.Client", "FCM.Dev.Client"));
+        assert!(is_job_matched(
+            "FCM.Dev.Client.Blast.?pt",
+            "FCM.Dev.Client.Blast.Opt"
+        ));
+        assert!(is_job_matched("Folder/*", "Folder/Sub/main"));
+        assert!(!is_job_matched("FCM.*.Client", "FCM.Dev.Server"));
+        assert!(!is_job_matched("FCM.Dev", "FCM.Dev.Client"));
+        assert!(!is_job_matched("FCM.(Dev)", "FCM.Dev"));
+    }
+
+    #[test]
+    fn test_join_key() {
+        assert_eq!(join_key("", "http"), "http");
+        assert_eq!(join_key("http", "timeout_secs"), "http.timeout_secs");
+        assert_eq!(
+            join_key("job_groups", "Client.Opt"),
+            "job_groups.\"Client.Opt\""
+        );
+    }
+}
@@ -1,41 +1,181 @@
 pub const COUNT: u32 = 4;
 pub const RUN_COUNT: u32 = 1;
+pub const QUEUE_POLL_TRIAL_COUNT: u32 = 15;
+pub const QUEUE_POLL_INTERVAL_SECS: u64 = 2;
 pub const WATCH_INTERVAL: u64 = 10;
+pub const WATCH_MAX_QUERY_FAILURE_COUNT: u32 = 6;
 pub const WATCH_QUERY_BUILDS_COUNT: u32 = 10;
+pub const WATCH_PROGRESS_BAR_WIDTH: usize = 20;
 pub const OLDEST_SUPPORT_UPDATE_VERSION: &str = "1.5.0";
 pub const MAX_JOB_RELATIVE_DATA_COUNT: usize = 8;
 pub const USER_QUERY_JENKINS_BUILD_COUNT: usize = 50;
 pub const JENKINS_QUERY_CONCURRENCY_COUNT: usize = 20;
+pub const JOB_TREE_DEPTH: u32 = 4;
+pub const SERVER_JOB_CACHE_TTL_SECS: i64 = 60 * 60;
+pub const HTTP_TIMEOUT_SECS: u64 = 30;
+pub const HTTP_CONNECT_TIMEOUT_SECS: u64 = 10;
+pub const HTTP_MAX_RETRIES: u32 = 3;
+pub const HTTP_RETRY_BASE_DELAY_MILLIS: u64 = 500;
+pub const HTTP_RETRY_MAX_DELAY_MILLIS: u64 = 8000;
 pub const RELEASE_URL: &str = "https://github.com/LviatYi/vertical-fire-platform/releases/tag/v";
 
+mod edit;
+mod layer;
+
+use layer::LayeredConfig;
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
+use toml::{Table, Value};
+
+pub use edit::{
+    check_config_key, find_unknown_keys_in_file, set_config_value, unset_config_value,
+    validate_config_value, ConfigLayerKind,
+};
+pub use layer::{
+    is_ignored_in_project, parse_config_override, set_cli_overrides, ConfigOrigin,
+    RuntimeConfigEntry,
+};
 
 const DEFAULT_RUNTIME_CONFIG_PATH: &str = "fp-config.toml";
-const BUILD_DEFAULT_RECOMMEND_JOB_NAMES: &str =
-    if let Some(v) = option_env!("RECOMMEND_JOB_NAMES") { v } else { "" };
-const BUILD_DEFAULT_REPO_TEMPLATE: &str =
-    if let Some(v) = option_env!("REPO_TEMPLATE") { v } else { "" };
-const BUILD_DEFAULT_LOCATOR_PATTERN: &str =
-    if let Some(v) = option_env!("LOCATOR_PATTERN") { v } else { "" };
-const BUILD_DEFAULT_LOCATOR_TEMPLATE: &str =
-    if let Some(v) = option_env!("LOCATOR_TEMPLATE") { v } else { "" };
-const BUILD_DEFAULT_MENDING_FILE_PATH: &str =
-    if let Some(v) = option_env!("MENDING_FILE_PATH") { v } else { "" };
-const BUILD_DEFAULT_PT_RELATIVE_PATH: &str =
-    if let Some(v) = option_env!("PT_RELATIVE_PATH") { v } else { "" };
-const BUILD_DEFAULT_PACKAGE_FILE_STEM: &str =
-    if let Some(v) = option_env!("PACKAGE_FILE_STEM") { v } else { "" };
-const BUILD_DEFAULT_EXE_FILE_NAME: &str =
-    if let Some(v) = option_env!("EXE_FILE_NAME") { v } else { "" };
-const BUILD_DEFAULT_CHECK_EXE_FILE_NAME: &str =
-    if let Some(v) = option_env!("CHECK_EXE_FILE_NAME") { v } else { "" };
-const BUILD_DEFAULT_JENKINS_URL: &str =
-    if let Some(v) = option_env!("JENKINS_URL") { v } else { "" };
-const BUILD_DEFAULT_QUERY_TOKEN_GITHUB: &str =
-    if let Some(v) = option_env!("QUERY_TOKEN_GITHUB") { v } else { "" };
+const USER_RUNTIME_CONFIG_PATH: &str = ".config/fp/config.toml";
+const PROJECT_RUNTIME_CONFIG_FILE_NAME: &str = ".fp.toml";
+
+/// the keys of the runtime config, in the order of `fp config show`.
+pub const RUNTIME_CONFIG_KEYS: [&str; 16] = [
+    "recommend_job_names",
+    "repo_template",
+    "locator_pattern",
+    "locator_template",
+    "mending_file_path",
+    "pt_relative_path",
+    "package_file_stem",
+    "exe_file_name",
+    "check_exe_file_name",
+    "jenkins_url",
+    "query_token_github",
+    "p4_depot_path",
+    "credential_store",
+    "job_groups",
+    "http",
+    JOBS_KEY,
+];
+
+/// the table of the overrides by job names or globs, like `[jobs."FCM.*.Client.*"]`.
+const JOBS_KEY: &str = "jobs";
+
+/// the keys which can be overridden in `[jobs]`, which are resolved by the job
+/// of extract, run and distr.
+const JOB_CONFIG_KEYS: [&str; 8] = [
+    "repo_template",
+    "locator_pattern",
+    "locator_template",
+    "mending_file_path",
+    "pt_relative_path",
+    "package_file_stem",
+    "exe_file_name",
+    "check_exe_file_name",
+];
+
+/// the keys which are ignored in `.fp.toml`, for a cloned project should not redirect
+/// the credentials, or the requests to Jenkins.
+const PROJECT_IGNORED_KEYS: [&str; 10] = [
+    "jenkins_url",
+    "credential_store",
+    "http.http_proxy",
+    "http.https_proxy",
+    "http.no_proxy",
+    "http.insecure_servers",
+    "http.ca_bundle_path",
+    "http.client_cert_path",
+    "http.client_key_path",
+    "http.client_cert_password",
+];
+
+/// the keys of `[http]`, the fields of [HttpConfig].
+const HTTP_CONFIG_KEYS: [&str; 11] = [
+    "ca_bundle_path",
+    "insecure_servers",
+    "http_proxy",
+    "https_proxy",
+    "no_proxy",
+    "client_cert_path",
+    "client_key_path",
+    "client_cert_password",
+    "timeout_secs",
+    "connect_timeout_secs",
+    "max_retries",
+];
+const BUILD_DEFAULT_RECOMMEND_JOB_NAMES: &str = if let Some(v) = option_env!("RECOMMEND_JOB_NAMES")
+{
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_REPO_TEMPLATE: &str = if let Some(v) = option_env!("REPO_TEMPLATE") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_LOCATOR_PATTERN: &str = if let Some(v) = option_env!("LOCATOR_PATTERN") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_LOCATOR_TEMPLATE: &str = if let Some(v) = option_env!("LOCATOR_TEMPLATE") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_MENDING_FILE_PATH: &str = if let Some(v) = option_env!("MENDING_FILE_PATH") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_PT_RELATIVE_PATH: &str = if let Some(v) = option_env!("PT_RELATIVE_PATH") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_PACKAGE_FILE_STEM: &str = if let Some(v) = option_env!("PACKAGE_FILE_STEM") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_EXE_FILE_NAME: &str = if let Some(v) = option_env!("EXE_FILE_NAME") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_CHECK_EXE_FILE_NAME: &str = if let Some(v) = option_env!("CHECK_EXE_FILE_NAME")
+{
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_JENKINS_URL: &str = if let Some(v) = option_env!("JENKINS_URL") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_QUERY_TOKEN_GITHUB: &str = if let Some(v) = option_env!("QUERY_TOKEN_GITHUB") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_P4_DEPOT_PATH: &str = if let Some(v) = option_env!("P4_DEPOT_PATH") {
+    v
+} else {
+    ""
+};
+const BUILD_DEFAULT_CREDENTIAL_STORE: &str = if let Some(v) = option_env!("CREDENTIAL_STORE") {
+    v
+} else {
+    ""
+};
 
 #[derive(Debug, Default, Deserialize)]
 struct RuntimeConfigFile {
@@ -50,6 +190,92 @@ struct RuntimeConfigFile {
     check_exe_file_name: Option<String>,
     jenkins_url: Option<String>,
     query_token_github: Option<String>,
+    p4_depot_path: Option<String>,
+    credential_store: Option<String>,
+    job_groups: Option<HashMap<String, Vec<String>>>,
+    http: Option<HttpConfig>,
+    /// only to check the types of the overrides, which are merged by [LayeredConfig::for_job].
+    #[allow(dead_code)]
+    jobs: Option<HashMap<String, JobConfigFile>>,
+}
+
+/// # JobConfigFile
+///
+/// A table of `[jobs]`, with the keys of [JOB_CONFIG_KEYS] only.
+/// the other keys are ignored with a warning, like the unknown keys.
+#[derive(Debug, Default, Deserialize)]
+#[allow(dead_code)]
+struct JobConfigFile {
+    repo_template: Option<String>,
+    locator_pattern: Option<String>,
+    locator_template: Option<String>,
+    mending_file_path: Option<String>,
+    pt_relative_path: Option<String>,
+    package_file_stem: Option<String>,
+    exe_file_name: Option<String>,
+    check_exe_file_name: Option<String>,
+}
+
+/// # HttpConfig
+///
+/// TLS and proxy settings of the requests to Jenkins, the `[http]` of the runtime config.
+#[derive(Default, Clone, Deserialize)]
+pub struct HttpConfig {
+    /// PEM file of extra root certificates, like the CA of the company.
+    pub ca_bundle_path: Option<String>,
+
+    /// hosts like `jenkins.internal` or `jenkins.internal:8443` whose certificates are not verified.
+    #[serde(default)]
+    pub insecure_servers: Vec<String>,
+
+    /// proxy of the http requests. `HTTP_PROXY` is used if it is not set.
+    pub http_proxy: Option<String>,
+
+    /// proxy of the https requests. `HTTPS_PROXY` is used if it is not set.
+    pub https_proxy: Option<String>,
+
+    /// hosts that bypass the proxy, like `localhost,.corp.com`. `NO_PROXY` is used if it is not set.
+    pub no_proxy: Option<String>,
+
+    /// client certificate. a PEM file with `client_key_path`, or a PKCS#12 file.
+    pub client_cert_path: Option<String>,
+
+    /// PKCS#8 PEM private key of the client certificate.
+    pub client_key_path: Option<String>,
+
+    /// password of the PKCS#12 client certificate.
+    pub client_cert_password: Option<String>,
+
+    /// timeout of a whole request. [HTTP_TIMEOUT_SECS] if it is not set.
+    pub timeout_secs: Option<u64>,
+
+    /// timeout of connecting. [HTTP_CONNECT_TIMEOUT_SECS] if it is not set.
+    pub connect_timeout_secs: Option<u64>,
+
+    /// retries of a GET request on timeouts and 5xx. [HTTP_MAX_RETRIES] if it is not set.
+    pub max_retries: Option<u32>,
+}
+
+/// the password is not printed.
+impl Debug for HttpConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpConfig")
+            .field("ca_bundle_path", &self.ca_bundle_path)
+            .field("insecure_servers", &self.insecure_servers)
+            .field("http_proxy", &self.http_proxy)
+            .field("https_proxy", &self.https_proxy)
+            .field("no_proxy", &self.no_proxy)
+            .field("client_cert_path", &self.client_cert_path)
+            .field("client_key_path", &self.client_key_path)
+            .field(
+                "client_cert_password",
+                &self.client_cert_password.as_ref().map(|_| "***"),
+            )
+            .field("timeout_secs", &self.timeout_secs)
+            .field("connect_timeout_secs", &self.connect_timeout_secs)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
 }
 
 #[derive(Debug, Default)]
@@ -65,57 +291,176 @@ pub struct RuntimeConfig {
     pub check_exe_file_name: String,
     pub jenkins_url: String,
     pub query_token_github: String,
+
+    /// depot path of the workspace like `//stream/...`.
+    /// Perforce helpers of `fp build` are enabled only if it is set.
+    pub p4_depot_path: String,
+
+    /// where the Jenkins secrets are kept: `keyring`, `file` or `plaintext`.
+    /// the keyring is used if it is empty.
+    pub credential_store: String,
+
+    /// named groups of job names, built together by `fp build -g`.
+    pub job_groups: HashMap<String, Vec<String>>,
+
+    pub http: HttpConfig,
+
+    /// the keys overridden by the `[jobs]` tables, empty if it is not of a job.
+    pub job_keys: HashSet<String>,
 }
 
+impl RuntimeConfig {
+    /// whether `key` is overridden for the job, where it wins over the last used value.
+    pub fn is_set_for_job(&self, key: &str) -> bool {
+        self.job_keys.contains(key)
+    }
+
+    /// the last used value of `key`, which is shared by the jobs,
+    /// unless `key` is overridden for the job.
+    pub fn last_used_unless_set_for_job<'a>(
+        &self,
+        key: &str,
+        last_used: &'a Option<String>,
+    ) -> Option<&'a String> {
+        last_used.as_ref().filter(|_| !self.is_set_for_job(key))
+    }
+}
+
+static LAYERED_CONFIG: LazyLock<LayeredConfig> = LazyLock::new(LayeredConfig::load);
 static RUNTIME_CONFIG: LazyLock<RuntimeConfig> = LazyLock::new(load_runtime_config);
 
 pub fn runtime() -> &'static RuntimeConfig {
     &RUNTIME_CONFIG
 }
 
+/// # runtime for job
+///
+/// The runtime config of `job_name`, where the `[jobs]` tables matching it override the values.
+pub fn runtime_for_job(job_name: &str) -> RuntimeConfig {
+    RuntimeConfig {
+        job_keys: LAYERED_CONFIG.job_keys(job_name),
+        ..to_runtime_config(LAYERED_CONFIG.for_job(job_name).to_config_file())
+    }
+}
+
+/// # runtime entries
+///
+/// every effective value of the runtime config, and the layer it comes from.
+pub fn runtime_entries() -> Vec<RuntimeConfigEntry> {
+    LAYERED_CONFIG.entries()
+}
+
+/// # runtime entries for job
+///
+/// every effective value of the runtime config of `job_name`, and the layer it comes from.
+pub fn runtime_entries_for_job(job_name: &str) -> Vec<RuntimeConfigEntry> {
+    LAYERED_CONFIG
+        .for_job(job_name)
+        .entries()
+        .into_iter()
+        .filter(|entry| entry.key != JOBS_KEY)
+        .collect()
+}
+
 fn load_runtime_config() -> RuntimeConfig {
-    let file_config = load_runtime_config_file();
+    to_runtime_config(LAYERED_CONFIG.to_config_file())
+}
 
+fn to_runtime_config(file_config: RuntimeConfigFile) -> RuntimeConfig {
     RuntimeConfig {
-        recommend_job_names: resolve_recommend_job_names(
-            file_config.recommend_job_names,
-            BUILD_DEFAULT_RECOMMEND_JOB_NAMES,
-        ),
-        repo_template: resolve_string(file_config.repo_template, BUILD_DEFAULT_REPO_TEMPLATE),
-        locator_pattern: resolve_string(file_config.locator_pattern, BUILD_DEFAULT_LOCATOR_PATTERN),
-        locator_template: resolve_string(file_config.locator_template, BUILD_DEFAULT_LOCATOR_TEMPLATE),
-        mending_file_path: resolve_string(
-            file_config.mending_file_path,
-            BUILD_DEFAULT_MENDING_FILE_PATH,
-        ),
-        pt_relative_path: resolve_string(file_config.pt_relative_path, BUILD_DEFAULT_PT_RELATIVE_PATH),
-        package_file_stem: resolve_string(
-            file_config.package_file_stem,
-            BUILD_DEFAULT_PACKAGE_FILE_STEM,
-        ),
-        exe_file_name: resolve_string(file_config.exe_file_name, BUILD_DEFAULT_EXE_FILE_NAME),
-        check_exe_file_name: resolve_string(
-            file_config.check_exe_file_name,
-            BUILD_DEFAULT_CHECK_EXE_FILE_NAME,
-        ),
-        jenkins_url: resolve_string(file_config.jenkins_url, BUILD_DEFAULT_JENKINS_URL),
-        query_token_github: resolve_string(
-            file_config.query_token_github,
-            BUILD_DEFAULT_QUERY_TOKEN_GITHUB,
+        recommend_job_names: resolve_recommend_job_names(file_config.recommend_job_names),
+        repo_template: resolve_string(file_config.repo_template),
+        locator_pattern: resolve_string(file_config.locator_pattern),
+        locator_template: resolve_string(file_config.locator_template),
+        mending_file_path: resolve_string(file_config.mending_file_path),
+        pt_relative_path: resolve_string(file_config.pt_relative_path),
+        package_file_stem: resolve_string(file_config.package_file_stem),
+        exe_file_name: resolve_string(file_config.exe_file_name),
+        check_exe_file_name: resolve_string(file_config.check_exe_file_name),
+        jenkins_url: resolve_string(file_config.jenkins_url),
+        query_token_github: resolve_string(file_config.query_token_github),
+        p4_depot_path: resolve_string(file_config.p4_depot_path),
+        credential_store: resolve_string(file_config.credential_store),
+        job_groups: file_config.job_groups.unwrap_or_default(),
+        http: file_config.http.unwrap_or_default(),
+        job_keys: HashSet::new(),
+    }
+}
+
+/// the lowest layer, compiled in by the env vars of `build.rs`.
+fn build_default_table() -> Table {
+    let mut table = Table::new();
+    table.insert(
+        "recommend_job_names".to_string(),
+        Value::Array(
+            split_trimmed(BUILD_DEFAULT_RECOMMEND_JOB_NAMES)
+                .into_iter()
+                .map(Value::String)
+                .collect(),
         ),
+    );
+
+    for (key, value) in [
+        ("repo_template", BUILD_DEFAULT_REPO_TEMPLATE),
+        ("locator_pattern", BUILD_DEFAULT_LOCATOR_PATTERN),
+        ("locator_template", BUILD_DEFAULT_LOCATOR_TEMPLATE),
+        ("mending_file_path", BUILD_DEFAULT_MENDING_FILE_PATH),
+        ("pt_relative_path", BUILD_DEFAULT_PT_RELATIVE_PATH),
+        ("package_file_stem", BUILD_DEFAULT_PACKAGE_FILE_STEM),
+        ("exe_file_name", BUILD_DEFAULT_EXE_FILE_NAME),
+        ("check_exe_file_name", BUILD_DEFAULT_CHECK_EXE_FILE_NAME),
+        ("jenkins_url", BUILD_DEFAULT_JENKINS_URL),
+        ("query_token_github", BUILD_DEFAULT_QUERY_TOKEN_GITHUB),
+        ("p4_depot_path", BUILD_DEFAULT_P4_DEPOT_PATH),
+        ("credential_store", BUILD_DEFAULT_CREDENTIAL_STORE),
+    ] {
+        table.insert(key.to_string(), Value::String(value.trim().to_string()));
     }
+
+    table
 }
 
-fn load_runtime_config_file() -> RuntimeConfigFile {
-    let raw = match fs::read_to_string(resolve_runtime_config_path()) {
-        Ok(raw) => raw,
-        Err(_) => return RuntimeConfigFile::default(),
-    };
+/// # runtime config files
+///
+/// the files of the runtime config from low to high priority: next to the executable,
+/// of the user, and `.fp.toml` of the project found from the current directory upwards.
+pub fn runtime_config_files() -> Vec<ConfigOrigin> {
+    let mut files = vec![ConfigOrigin::Executable(resolve_runtime_config_path())];
+    if let Some(home) = dirs::home_dir() {
+        files.push(ConfigOrigin::User(home.join(USER_RUNTIME_CONFIG_PATH)));
+    }
+    if let Some(project) = std::env::current_dir()
+        .ok()
+        .and_then(|dir| find_project_runtime_config(&dir))
+    {
+        files.push(ConfigOrigin::Project(project));
+    }
 
-    toml::from_str::<RuntimeConfigFile>(&raw).unwrap_or_default()
+    files
 }
 
-fn resolve_runtime_config_path() -> PathBuf {
+fn find_project_runtime_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(PROJECT_RUNTIME_CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// # check runtime config file
+///
+/// whether the runtime config file at `path` exists.
+/// fails if it cannot be read or parsed, where the file is ignored with a warning.
+pub fn check_runtime_config_file(path: &Path) -> Result<bool, String> {
+    if !path.is_file() {
+        return Ok(false);
+    }
+
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    toml::from_str::<RuntimeConfigFile>(&raw)
+        .map(|_| true)
+        .map_err(|e| e.message().to_string())
+}
+
+pub fn resolve_runtime_config_path() -> PathBuf {
     std::env::current_exe()
         .ok()
         .as_deref()
@@ -128,28 +473,17 @@ fn runtime_config_path_from_root(root: &Path) -> PathBuf {
     root.join(DEFAULT_RUNTIME_CONFIG_PATH)
 }
 
-fn resolve_recommend_job_names(from_file: Option<Vec<String>>, from_build: &str) -> Vec<String> {
-    let from_file = from_file
+fn resolve_recommend_job_names(value: Option<Vec<String>>) -> Vec<String> {
+    value
         .unwrap_or_default()
         .into_iter()
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
-        .collect::<Vec<_>>();
-
-    if !from_file.is_empty() {
-        return from_file;
-    }
-
-    split_trimmed(from_build)
+        .collect()
 }
 
-fn resolve_string(from_file: Option<String>, from_build: &str) -> String {
-    let from_file = from_file.unwrap_or_default().trim().to_string();
-    if !from_file.is_empty() {
-        return from_file;
-    }
-
-    from_build.trim().to_string()
+fn resolve_string(value: Option<String>) -> String {
+    value.unwrap_or_default().trim().to_string()
 }
 
 fn split_trimmed(value: &str) -> Vec<String> {
@@ -173,4 +507,26 @@ mod tests {
             PathBuf::from(r"C:\tools\fp\fp-config.toml")
         );
     }
+
+    #[test]
+    fn test_check_runtime_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DEFAULT_RUNTIME_CONFIG_PATH);
+        assert_eq!(check_runtime_config_file(&path), Ok(false));
+
+        fs::write(&path, "repo_template = '\\\\share\\repo'\n").unwrap();
+        assert_eq!(check_runtime_config_file(&path), Ok(true));
+
+        fs::write(&path, "repo_template = \n").unwrap();
+        assert!(check_runtime_config_file(&path).is_err());
+
+        fs::write(&path, "job_groups = ['a']\n").unwrap();
+        assert!(check_runtime_config_file(&path).is_err());
+
+        fs::write(&path, "[jobs.\"Client.*\"]\nexe_file_name = 'Client.exe'\n").unwrap();
+        assert_eq!(check_runtime_config_file(&path), Ok(true));
+
+        fs::write(&path, "[jobs.\"Client.*\"]\nexe_file_name = 1\n").unwrap();
+        assert!(check_runtime_config_file(&path).is_err());
+    }
 }
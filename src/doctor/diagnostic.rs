@@ -0,0 +1,54 @@
+use crate::constant::log::*;
+use crate::pretty_log::{colored_println, ThemeColor};
+use std::io::Write;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DiagnosticStatus {
+    Ok,
+    Warn,
+    Failed,
+    Skipped,
+}
+
+/// # Diagnostic
+///
+/// The result of one check, printed as one line with the fix below it.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub status: DiagnosticStatus,
+    pub message: String,
+    /// how to fix it, if it is not ok.
+    pub hint: Option<&'static str>,
+}
+
+impl Diagnostic {
+    pub fn new(status: DiagnosticStatus, message: Result<String, formatx::Error>) -> Self {
+        Self {
+            status,
+            message: message.unwrap_or_default(),
+            hint: None,
+        }
+    }
+
+    pub fn with_hint(mut self, hint: &'static str) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    pub fn colored_println<W: Write>(&self, stdout: &mut W) {
+        let (tag, color) = match self.status {
+            DiagnosticStatus::Ok => (DIAG_TAG_OK, ThemeColor::Success),
+            DiagnosticStatus::Warn => (DIAG_TAG_WARN, ThemeColor::Warn),
+            DiagnosticStatus::Failed => (DIAG_TAG_FAILED, ThemeColor::Error),
+            DiagnosticStatus::Skipped => (DIAG_TAG_SKIPPED, ThemeColor::Second),
+        };
+        colored_println(stdout, color, &format!("{} {}", tag, self.message));
+        if let Some(hint) = self.hint {
+            colored_println(
+                stdout,
+                ThemeColor::Second,
+                &format!("{} {}", DIAG_TAG_INDENT, hint),
+            );
+        }
+    }
+}
@@ -0,0 +1,436 @@
+pub mod diagnostic;
+
+use crate::constant::log::*;
+use crate::default_config::{
+    check_runtime_config_file, find_unknown_keys_in_file, ConfigOrigin, RuntimeConfig,
+};
+use crate::doctor::diagnostic::{Diagnostic, DiagnosticStatus};
+use crate::extract::extractor_util::is_file_in_zip;
+use crate::extract::repo_decoration::RepoDecoration;
+use crate::run::find_program;
+use formatx::formatx;
+use std::fs;
+use std::path::Path;
+
+/// written and removed to check the blast path is writable.
+const PROBE_FILE_NAME: &str = ".fp-doctor";
+
+/// the commands to find and kill the running instances, by `fp run`.
+const PROCESS_TOOLS: [(&str, &str); 2] = [
+    ("wmic", HINT_DOCTOR_WMIC),
+    ("taskkill", HINT_DOCTOR_TASKKILL),
+];
+
+/// # check runtime config
+///
+/// Check the runtime config files can be parsed,
+/// and the keys used by extract and run are set.
+pub fn check_runtime_config(
+    files: &[ConfigOrigin],
+    runtime_config: &RuntimeConfig,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = files
+        .iter()
+        .filter_map(ConfigOrigin::get_path)
+        .flat_map(|path| {
+            let path_str = path.display().to_string();
+            match check_runtime_config_file(path) {
+                Ok(true) => std::iter::once(Diagnostic::new(
+                    DiagnosticStatus::Ok,
+                    formatx!(DOCTOR_CONFIG_LOADED, &path_str),
+                ))
+                .chain(find_unknown_keys_in_file(path).into_iter().map(|e| {
+                    Diagnostic::new(DiagnosticStatus::Warn, Ok(e))
+                        .with_hint(HINT_DOCTOR_CONFIG_UNKNOWN_KEY)
+                }))
+                .collect(),
+                Ok(false) => vec![],
+                Err(e) => vec![Diagnostic::new(
+                    DiagnosticStatus::Failed,
+                    formatx!(DOCTOR_CONFIG_INVALID, &path_str, e),
+                )
+                .with_hint(HINT_DOCTOR_CONFIG_FILE)],
+            }
+        })
+        .collect::<Vec<_>>();
+    if diagnostics.is_empty() {
+        let paths = files
+            .iter()
+            .filter_map(ConfigOrigin::get_path)
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        diagnostics.push(
+            Diagnostic::new(
+                DiagnosticStatus::Warn,
+                formatx!(DOCTOR_CONFIG_NOT_FOUND, paths.join(", ")),
+            )
+            .with_hint(HINT_DOCTOR_CONFIG_FILE),
+        );
+    }
+
+    let keys = [
+        ("repo_template", &runtime_config.repo_template),
+        ("locator_pattern", &runtime_config.locator_pattern),
+        ("locator_template", &runtime_config.locator_template),
+        ("mending_file_path", &runtime_config.mending_file_path),
+        ("package_file_stem", &runtime_config.package_file_stem),
+        ("exe_file_name", &runtime_config.exe_file_name),
+        ("check_exe_file_name", &runtime_config.check_exe_file_name),
+        ("jenkins_url", &runtime_config.jenkins_url),
+    ];
+    let missing_keys = keys
+        .iter()
+        .filter(|(_, value)| value.is_empty())
+        .map(|(key, _)| *key)
+        .collect::<Vec<_>>();
+    let keys = if missing_keys.is_empty() {
+        Diagnostic::new(DiagnosticStatus::Ok, Ok(DOCTOR_CONFIG_KEYS_SET.into()))
+    } else {
+        Diagnostic::new(
+            DiagnosticStatus::Warn,
+            formatx!(DOCTOR_CONFIG_KEYS_MISSING, missing_keys.join(", ")),
+        )
+        .with_hint(HINT_DOCTOR_CONFIG_KEYS)
+    };
+
+    diagnostics.push(keys);
+    if let Some(locator_pattern) = locator_pattern_diagnostic(&runtime_config.locator_pattern) {
+        diagnostics.push(locator_pattern);
+    }
+
+    diagnostics
+}
+
+/// without `{ID}`, no ci can be extracted from the directory names.
+fn locator_pattern_diagnostic(locator_pattern: &str) -> Option<Diagnostic> {
+    (!locator_pattern.is_empty() && !locator_pattern.contains("{ID}")).then(|| {
+        Diagnostic::new(
+            DiagnosticStatus::Failed,
+            formatx!(DOCTOR_LOCATOR_PATTERN_NO_ID, locator_pattern),
+        )
+        .with_hint(HINT_DOCTOR_LOCATOR_PATTERN)
+    })
+}
+
+/// # check repo
+///
+/// Check the repo is accessible, the directories of the job match `locator_pattern`,
+/// and the latest package is found with the file to mend in it.
+/// The checks are stopped at the first failure.
+pub fn check_repo(
+    repo_template: &str,
+    locator_pattern: &str,
+    locator_template: &str,
+    mending_file_path: &str,
+    job_name: Option<&str>,
+) -> Vec<Diagnostic> {
+    if repo_template.is_empty() {
+        return vec![
+            Diagnostic::new(DiagnosticStatus::Failed, Ok(DOCTOR_REPO_NOT_SET.into()))
+                .with_hint(HINT_DOCTOR_CONFIG_KEYS),
+        ];
+    }
+
+    if let Err(e) = fs::read_dir(repo_template) {
+        return vec![Diagnostic::new(
+            DiagnosticStatus::Failed,
+            formatx!(DOCTOR_REPO_INACCESSIBLE, repo_template, e.to_string()),
+        )
+        .with_hint(HINT_DOCTOR_REPO_INACCESSIBLE)];
+    }
+    let mut diagnostics = vec![Diagnostic::new(
+        DiagnosticStatus::Ok,
+        formatx!(DOCTOR_REPO_ACCESSIBLE, repo_template),
+    )];
+
+    let Some(job_name) = job_name else {
+        diagnostics.push(Diagnostic::new(
+            DiagnosticStatus::Skipped,
+            Ok(DOCTOR_JOB_SKIPPED.into()),
+        ));
+        return diagnostics;
+    };
+
+    let repo = RepoDecoration::new(repo_template, locator_pattern, locator_template, job_name);
+    let job_repo = repo.assemble_build_target_repo();
+    let job_repo_str = job_repo.display().to_string();
+    let dir_names = match fs::read_dir(&job_repo) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .collect::<Vec<_>>(),
+        Err(_) => {
+            diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticStatus::Failed,
+                    formatx!(DOCTOR_JOB_REPO_NOT_FOUND, &job_repo_str),
+                )
+                .with_hint(HINT_DOCTOR_JOB_REPO_NOT_FOUND),
+            );
+            return diagnostics;
+        }
+    };
+
+    let ci_list = repo.get_sorted_ci_list();
+    let Some(&latest_ci) = ci_list.first() else {
+        diagnostics.push(locator_mismatch_diagnostic(
+            &job_repo_str,
+            locator_pattern,
+            &dir_names,
+        ));
+        return diagnostics;
+    };
+    diagnostics.push(Diagnostic::new(
+        DiagnosticStatus::Ok,
+        formatx!(
+            DOCTOR_LOCATOR_MATCHED,
+            ci_list.len(),
+            &job_repo_str,
+            locator_pattern,
+            latest_ci
+        ),
+    ));
+
+    let package = repo.get_full_path_by_ci(latest_ci).unwrap_or_default();
+    let package_str = package.display().to_string();
+    if !package.is_file() {
+        diagnostics.push(
+            Diagnostic::new(
+                DiagnosticStatus::Failed,
+                formatx!(DOCTOR_PACKAGE_NOT_FOUND, &package_str),
+            )
+            .with_hint(HINT_DOCTOR_PACKAGE_NOT_FOUND),
+        );
+        return diagnostics;
+    }
+    diagnostics.push(Diagnostic::new(
+        DiagnosticStatus::Ok,
+        formatx!(DOCTOR_PACKAGE_FOUND, &package_str),
+    ));
+
+    if !mending_file_path.is_empty() {
+        diagnostics.push(mending_file_diagnostic(
+            &package_str,
+            mending_file_path,
+            is_file_in_zip(&package, mending_file_path),
+        ));
+    }
+
+    diagnostics
+}
+
+fn locator_mismatch_diagnostic(
+    job_repo: &str,
+    locator_pattern: &str,
+    dir_names: &[String],
+) -> Diagnostic {
+    let message = match dir_names.first() {
+        Some(example) => formatx!(
+            DOCTOR_LOCATOR_MISMATCH_EXAMPLE,
+            job_repo,
+            locator_pattern,
+            example
+        ),
+        None => formatx!(DOCTOR_LOCATOR_MISMATCH_EMPTY, job_repo),
+    };
+
+    Diagnostic::new(DiagnosticStatus::Failed, message).with_hint(HINT_DOCTOR_LOCATOR_PATTERN)
+}
+
+fn mending_file_diagnostic(
+    package: &str,
+    mending_file_path: &str,
+    found: Result<bool, String>,
+) -> Diagnostic {
+    match found {
+        Ok(true) => Diagnostic::new(
+            DiagnosticStatus::Ok,
+            formatx!(DOCTOR_MENDING_FILE_FOUND, mending_file_path),
+        ),
+        Ok(false) => Diagnostic::new(
+            DiagnosticStatus::Failed,
+            formatx!(DOCTOR_MENDING_FILE_NOT_FOUND, mending_file_path, package),
+        )
+        .with_hint(HINT_DOCTOR_MENDING_FILE),
+        Err(e) => Diagnostic::new(
+            DiagnosticStatus::Failed,
+            formatx!(DOCTOR_PACKAGE_INVALID, package, e),
+        ),
+    }
+}
+
+/// # check blast path
+///
+/// Check the blast path of the job exists and is writable, by writing and removing a file in it.
+pub fn check_blast_path(blast_path: Option<&Path>) -> Diagnostic {
+    let Some(blast_path) = blast_path else {
+        return Diagnostic::new(
+            DiagnosticStatus::Skipped,
+            Ok(DOCTOR_BLAST_PATH_NOT_SET.into()),
+        );
+    };
+
+    let blast_path_str = blast_path.display().to_string();
+    if !blast_path.is_dir() {
+        return Diagnostic::new(
+            DiagnosticStatus::Warn,
+            formatx!(DOCTOR_BLAST_PATH_NOT_FOUND, &blast_path_str),
+        )
+        .with_hint(HINT_DOCTOR_BLAST_PATH);
+    }
+
+    let probe = blast_path.join(PROBE_FILE_NAME);
+    match fs::write(&probe, "").and_then(|_| fs::remove_file(&probe)) {
+        Ok(_) => Diagnostic::new(
+            DiagnosticStatus::Ok,
+            formatx!(DOCTOR_BLAST_PATH_WRITABLE, &blast_path_str),
+        ),
+        Err(e) => Diagnostic::new(
+            DiagnosticStatus::Failed,
+            formatx!(DOCTOR_BLAST_PATH_READONLY, &blast_path_str, e.to_string()),
+        )
+        .with_hint(HINT_DOCTOR_BLAST_PATH),
+    }
+}
+
+/// # check process tools
+///
+/// Check the commands used by `fp run` are in `PATH`. They are only on Windows.
+pub fn check_process_tools() -> Vec<Diagnostic> {
+    if !cfg!(windows) {
+        return vec![Diagnostic::new(
+            DiagnosticStatus::Skipped,
+            Ok(DOCTOR_TOOLS_SKIPPED.into()),
+        )];
+    }
+
+    PROCESS_TOOLS
+        .iter()
+        .map(|(name, hint)| match find_program(name) {
+            Some(path) => Diagnostic::new(
+                DiagnosticStatus::Ok,
+                formatx!(DOCTOR_TOOL_FOUND, name, path.display().to_string()),
+            ),
+            None => Diagnostic::new(
+                DiagnosticStatus::Failed,
+                formatx!(DOCTOR_TOOL_NOT_FOUND, name),
+            )
+            .with_hint(hint),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_check_runtime_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime_config = RuntimeConfig {
+            repo_template: "\\\\share\\repo".to_string(),
+            locator_pattern: "CL-{*}".to_string(),
+            ..Default::default()
+        };
+
+        let path = dir.path().join("fp-config.toml");
+        let files = [ConfigOrigin::Executable(path.clone())];
+        let diagnostics = check_runtime_config(&files, &runtime_config);
+        let status = diagnostics.iter().map(|d| d.status).collect::<Vec<_>>();
+        assert_eq!(
+            status,
+            vec![
+                DiagnosticStatus::Warn,
+                DiagnosticStatus::Warn,
+                DiagnosticStatus::Failed
+            ]
+        );
+        assert!(!diagnostics[1].message.contains("repo_template"));
+        assert!(diagnostics[1].message.contains("mending_file_path"));
+        assert!(diagnostics[2].message.contains("{ID}"));
+
+        fs::write(&path, "repo_template = \n").unwrap();
+        let diagnostics = check_runtime_config(&files, &runtime_config);
+        assert_eq!(diagnostics[0].status, DiagnosticStatus::Failed);
+    }
+
+    #[test]
+    fn test_check_repo() {
+        let repo = tempfile::tempdir().unwrap();
+        let repo_template = repo.path().to_str().unwrap();
+        let job_repo = repo.path().join("Some-JOB");
+        fs::create_dir_all(job_repo.join("12-Hash.abc")).unwrap();
+        fs::create_dir_all(job_repo.join("34-Hash.def")).unwrap();
+
+        let mut zip = zip::ZipWriter::new(
+            fs::File::create(job_repo.join("34-Hash.def").join("app.zip")).unwrap(),
+        );
+        zip.start_file("config/user.ini", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"[default]").unwrap();
+        zip.finish().unwrap();
+
+        let status =
+            |diagnostics: Vec<Diagnostic>| diagnostics.iter().map(|d| d.status).collect::<Vec<_>>();
+        assert_eq!(
+            status(check_repo(
+                repo_template,
+                "{ID}-Hash.{*}",
+                "\\app.zip",
+                "config\\user.ini",
+                Some("Some-JOB")
+            )),
+            vec![DiagnosticStatus::Ok; 4]
+        );
+        assert_eq!(
+            status(check_repo(
+                repo_template,
+                "{ID}-CL.{*}",
+                "\\app.zip",
+                "config\\user.ini",
+                Some("Some-JOB")
+            )),
+            vec![DiagnosticStatus::Ok, DiagnosticStatus::Failed]
+        );
+        assert_eq!(
+            *status(check_repo(
+                repo_template,
+                "{ID}-Hash.{*}",
+                "\\app.zip",
+                "user.ini",
+                Some("Some-JOB")
+            ))
+            .last()
+            .unwrap(),
+            DiagnosticStatus::Failed
+        );
+        assert_eq!(
+            status(check_repo(
+                &repo.path().join("missing").display().to_string(),
+                "{ID}-Hash.{*}",
+                "\\app.zip",
+                "user.ini",
+                Some("Some-JOB")
+            )),
+            vec![DiagnosticStatus::Failed]
+        );
+    }
+
+    #[test]
+    fn test_check_blast_path() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            check_blast_path(Some(dir.path())).status,
+            DiagnosticStatus::Ok
+        );
+        assert!(!dir.path().join(PROBE_FILE_NAME).exists());
+        assert_eq!(
+            check_blast_path(Some(&dir.path().join("missing"))).status,
+            DiagnosticStatus::Warn
+        );
+        assert_eq!(check_blast_path(None).status, DiagnosticStatus::Skipped);
+    }
+}
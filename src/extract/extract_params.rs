@@ -1,7 +1,7 @@
 use clap::Args;
 use std::path::PathBuf;
 
-#[derive(Args)]
+#[derive(Args, Clone)]
 pub struct ExtractParams {
     /// expected quantity.
     #[arg(short, long)]
@@ -22,4 +22,4 @@ pub struct ExtractParams {
     #[arg(long = "s-locator-template")]
     /// secondary locator template.
     pub secondary_locator_template: Option<String>,
-}
\ No newline at end of file
+}
@@ -146,6 +146,23 @@ pub fn extract_zip_file(from: &Path, dest: &Path) -> Result<u128, String> {
     }
 }
 
+/// # Is file in zip
+///
+/// check the file at `relative_path` is in the zip without extracting it.
+/// both `\\` and `/` are accepted as the separator of `relative_path`.
+pub fn is_file_in_zip(from: &Path, relative_path: &str) -> Result<bool, String> {
+    let relative_path =
+        remove_beginning_separator_in_relative_path(relative_path).replace('\\', "/");
+    let archive = fs::File::open(from)
+        .map_err(|e| e.to_string())
+        .and_then(|zip_file| zip::ZipArchive::new(zip_file).map_err(|e| e.to_string()))
+        .map_err(|e| format!("{} {}", constant::log::ERR_ZIP_CANNOT_OPEN, e))?;
+
+    Ok(archive
+        .file_names()
+        .any(|name| name.replace('\\', "/").eq_ignore_ascii_case(&relative_path)))
+}
+
 pub fn mending_user_ini(dest: &Path, index: u32, mend_file_path: &str) -> Result<u128, Error> {
     let start_time = std::time::Instant::now();
     let user_ini_path = dest.join(mend_file_path);
@@ -249,4 +266,26 @@ mod tests {
 
         assert_eq!(fs::read_to_string(file_path).unwrap(), "hello world");
     }
+
+    #[test]
+    fn test_is_file_in_zip() {
+        let temp_root_dir = tempdir().unwrap();
+        let zip_file_path = temp_root_dir.path().join("test.zip");
+
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_file_path).unwrap());
+        zip.start_file("config/user.ini", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"[default]").unwrap();
+        zip.finish().unwrap();
+
+        assert_eq!(
+            is_file_in_zip(&zip_file_path, "\\config\\user.ini"),
+            Ok(true)
+        );
+        assert_eq!(
+            is_file_in_zip(&zip_file_path, "config/other.ini"),
+            Ok(false)
+        );
+        assert!(is_file_in_zip(&temp_root_dir.path().join("missing.zip"), "user.ini").is_err());
+    }
 }
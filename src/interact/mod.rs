@@ -3,15 +3,19 @@ use crate::constant::log::*;
 use crate::db::db_data_proxy::DbDataProxy;
 use crate::default_config;
 use crate::extract::repo_decoration::OrderedCiList;
+use crate::jenkins::build::{VfpBuildParamKind, VfpBuildParamSpec, VfpJobBuildParam};
 use crate::jenkins::jenkins_model::run_status::RunStatus;
 use crate::jenkins::jenkins_model::shelves::Shelves;
+use crate::p4::P4Change;
 use crate::pretty_log::{clean_one_line, colored_println, ThemeColor};
 use crate::service::jenkins_rpc_service::JenkinsRpcService;
 use dirs::home_dir;
 use formatx::formatx;
 use inquire::error::InquireResult;
+use inquire::ui::{Color, RenderConfig, Styled};
 use inquire::validator::{ErrorMessage, Validation};
-use inquire::{InquireError, Password, PasswordDisplayMode, Select, Text};
+use inquire::{Confirm, InquireError, MultiSelect, Password, PasswordDisplayMode, Select, Text};
+use serde_json::Value;
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -779,11 +783,19 @@ pub async fn input_ci_for_watch(
 pub fn input_job_name(param_val: Option<String>, db: &DbDataProxy) -> InquireResult<String> {
     let runtime_config = default_config::runtime();
     let mut optional_job_names: Vec<String> = db.get_all_job_names();
+    // only a job from the history is the last used one.
+    let has_last_used = !optional_job_names.is_empty();
     for recommend in &runtime_config.recommend_job_names {
         if !optional_job_names.contains(&recommend) {
             optional_job_names.push(recommend.to_string());
         }
     }
+    // jobs on the server follow, so that typing searches them all.
+    for server_job_name in db.get_server_job_names() {
+        if !optional_job_names.contains(&server_job_name) {
+            optional_job_names.push(server_job_name);
+        }
+    }
 
     let default_job_name = runtime_config.recommend_job_names.first().cloned();
 
@@ -791,7 +803,7 @@ pub fn input_job_name(param_val: Option<String>, db: &DbDataProxy) -> InquireRes
         .into_iter()
         .enumerate()
         .map(|(idx, item)| {
-            if idx == 0 {
+            if idx == 0 && has_last_used {
                 SelectionCustomizableOptionVal::from_with_hint(
                     item.clone(),
                     &format!("({})", HINT_LAST_USED_SUFFIX),
@@ -818,22 +830,33 @@ pub fn input_job_name(param_val: Option<String>, db: &DbDataProxy) -> InquireRes
     }
 }
 
-pub fn input_cl(param_val: Option<u32>, db_val: &Option<u32>) -> InquireResult<Option<u32>> {
-    let options: Vec<SelectionCustomizableOptionVal<u32>> = if let Some(last_used) = *db_val {
-        vec![
-            SelectionCustomizableOptionVal::None,
-            SelectionCustomizableOptionVal::from_with_hint(
-                last_used,
-                &format!("({})", HINT_LAST_USED_SUFFIX),
-            ),
-            SelectionCustomizableOptionVal::Custom,
-        ]
-    } else {
-        vec![
-            SelectionCustomizableOptionVal::None,
-            SelectionCustomizableOptionVal::Custom,
-        ]
-    };
+/// # input cl
+///
+/// ### Arguments
+///
+/// * `p4_have_val`: the latest change list synced to the workspace, offered as the default.
+pub fn input_cl(
+    param_val: Option<u32>,
+    db_val: &Option<u32>,
+    p4_have_val: Option<u32>,
+) -> InquireResult<Option<u32>> {
+    let mut options: Vec<SelectionCustomizableOptionVal<u32>> = Vec::new();
+    if let Some(have) = p4_have_val {
+        options.push(SelectionCustomizableOptionVal::from_with_hint(
+            have,
+            &format!("({})", HINT_P4_HAVE_SUFFIX),
+        ));
+    }
+    options.push(SelectionCustomizableOptionVal::None);
+    if let Some(last_used) = *db_val
+        && Some(last_used) != p4_have_val
+    {
+        options.push(SelectionCustomizableOptionVal::from_with_hint(
+            last_used,
+            &format!("({})", HINT_LAST_USED_SUFFIX),
+        ));
+    }
+    options.push(SelectionCustomizableOptionVal::Custom);
 
     match input_by_selection_various::<u32>(
         param_val,
@@ -864,9 +887,16 @@ pub fn input_cl(param_val: Option<u32>, db_val: &Option<u32>) -> InquireResult<O
     }
 }
 
+/// # input sl
+///
+/// ### Arguments
+///
+/// * `p4_shelves`: pending shelved change lists of the user.
+///   if any, the custom option selects from them, and falls back to text input if none is selected.
 pub fn input_sl(
     param_val: Option<Shelves>,
     db_val: &Option<Shelves>,
+    p4_shelves: &[P4Change],
 ) -> InquireResult<Option<Shelves>> {
     let options: Vec<SelectionCustomizableOptionVal<Shelves>> =
         if let Some(last_used) = db_val.clone() {
@@ -895,6 +925,15 @@ pub fn input_sl(
     ) {
         Ok(SelectionCustomizableOptionVal::DataContain(d)) => Ok(Some(d.get_data())),
         Ok(SelectionCustomizableOptionVal::Custom) => {
+            if !p4_shelves.is_empty() {
+                let selected =
+                    MultiSelect::new(HINT_SELECT_P4_SHELVES, p4_shelves.iter().collect())
+                        .prompt()?;
+                if !selected.is_empty() {
+                    return Ok(Some(selected.iter().map(|change| change.number).collect()));
+                }
+            }
+
             let input = Text::from(HINT_INPUT_CUSTOM)
                 .with_validator(|input: &str| {
                     if input.parse::<Shelves>().is_ok() {
@@ -913,3 +952,122 @@ pub fn input_sl(
         Err(e) => Err(e),
     }
 }
+
+/// # input build params
+///
+/// Edit build params by a form driven by the job definition.
+/// Current values are pre-filled and params marked necessary are highlighted.
+/// Change list and shelved change list are skipped since they are asked separately.
+pub fn input_build_params(
+    specs: &[VfpBuildParamSpec],
+    build_params: &mut VfpJobBuildParam,
+) -> InquireResult<()> {
+    let necessary_render_config = RenderConfig::default_colored()
+        .with_prompt_prefix(Styled::new("!").with_fg(Color::LightRed));
+
+    for spec in specs.iter().filter(|spec| {
+        spec.name != VfpJobBuildParam::PARAM_NAME_CHANGE_LIST
+            && spec.name != VfpJobBuildParam::PARAM_NAME_SHELVED_CHANGE
+    }) {
+        let current = build_params.params.get(&spec.name);
+        let message = if spec.necessary {
+            formatx!(HINT_INPUT_NECESSARY_PARAM, &spec.name).unwrap_or(spec.name.clone())
+        } else {
+            spec.name.clone()
+        };
+        let render_config = if spec.necessary {
+            necessary_render_config
+        } else {
+            RenderConfig::default_colored()
+        };
+        let help_message = spec.description.as_deref().unwrap_or_default();
+
+        let value = match &spec.kind {
+            VfpBuildParamKind::String
+            | VfpBuildParamKind::Text
+            | VfpBuildParamKind::Credentials
+            | VfpBuildParamKind::Dynamic { .. } => Text::new(&message)
+                .with_initial_value(current.and_then(Value::as_str).unwrap_or_default())
+                .with_help_message(help_message)
+                .with_render_config(render_config)
+                .prompt()
+                .map(Value::String)?,
+            VfpBuildParamKind::Run { project_name } => Text::new(&message)
+                .with_initial_value(current.and_then(Value::as_str).unwrap_or_default())
+                .with_placeholder(&format!("{}#", project_name))
+                .with_help_message(help_message)
+                .with_render_config(render_config)
+                .prompt()
+                .map(Value::String)?,
+            VfpBuildParamKind::File => Text::new(&message)
+                .with_initial_value(current.and_then(Value::as_str).unwrap_or_default())
+                .with_help_message(help_message)
+                .with_render_config(render_config)
+                .with_validator(|input: &str| {
+                    if input.is_empty() || PathBuf::from(input).is_file() {
+                        Ok(Validation::Valid)
+                    } else {
+                        Ok(Validation::Invalid(ErrorMessage::Custom(
+                            HINT_FILE_NOT_FOUND.to_string(),
+                        )))
+                    }
+                })
+                .prompt()
+                .map(Value::String)?,
+            VfpBuildParamKind::Password => Password::new(&message)
+                .without_confirmation()
+                .with_display_mode(PasswordDisplayMode::Masked)
+                .with_help_message(help_message)
+                .with_render_config(render_config)
+                .prompt()
+                .map(Value::String)?,
+            VfpBuildParamKind::Bool => Confirm::new(&message)
+                .with_default(current.and_then(Value::as_bool).unwrap_or_default())
+                .with_help_message(help_message)
+                .with_render_config(render_config)
+                .prompt()
+                .map(Value::Bool)?,
+            VfpBuildParamKind::Choice(choices) => {
+                let starting_cursor = choices
+                    .iter()
+                    .position(|choice| Some(choice.as_str()) == current.and_then(Value::as_str))
+                    .unwrap_or_default();
+
+                Select::new(&message, choices.clone())
+                    .with_starting_cursor(starting_cursor)
+                    .with_help_message(help_message)
+                    .with_render_config(render_config)
+                    .prompt()
+                    .map(Value::String)?
+            }
+            VfpBuildParamKind::MultiChoice { choices, delimiter } => {
+                let selected: Vec<&str> = current
+                    .and_then(Value::as_str)
+                    .map(|value| value.split(delimiter.as_str()).map(str::trim).collect())
+                    .unwrap_or_default();
+                let default_indices: Vec<usize> = choices
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, choice)| selected.contains(&choice.as_str()))
+                    .map(|(index, _)| index)
+                    .collect();
+
+                MultiSelect::new(&message, choices.clone())
+                    .with_default(&default_indices)
+                    .with_help_message(help_message)
+                    .with_render_config(render_config)
+                    .prompt()
+                    .map(|selected| Value::String(selected.join(delimiter)))?
+            }
+        };
+
+        // empty file and password are left to the defaults of Jenkins.
+        if (spec.is_file() || spec.is_secret()) && value.as_str().is_some_and(str::is_empty) {
+            build_params.params.remove(&spec.name);
+        } else {
+            build_params.params.insert(spec.name.clone(), value);
+        }
+    }
+
+    Ok(())
+}
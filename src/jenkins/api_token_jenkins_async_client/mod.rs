@@ -0,0 +1,92 @@
+use crate::jenkins::http_client::{build_http_client, with_total_timeout};
+use jenkins_sdk::client::AsyncClient;
+use jenkins_sdk::JenkinsError;
+use reqwest::multipart::Form;
+use reqwest::{Client, RequestBuilder, Response};
+
+/// Asynchronous Jenkins API client.
+pub struct ApiTokenJenkinsAsyncClient {
+    url: String,
+    username: String,
+    api_token: String,
+    client: Client,
+}
+
+impl ApiTokenJenkinsAsyncClient {
+    /// Creates a new asynchronous Jenkins API client authentic by API Token.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Base URL of the Jenkins server.
+    /// * `username` - Username for authentication.
+    /// * `api_token` - API token for authentication.
+    ///
+    /// Fails if the `[http]` of the runtime config is invalid.
+    pub fn new(url: &str, username: &str, api_token: &str) -> Result<Self, JenkinsError> {
+        Ok(Self {
+            url: url.into(),
+            username: username.into(),
+            api_token: api_token.into(),
+            client: build_http_client(url, false)?,
+        })
+    }
+
+    fn get_request_builder(
+        &self,
+        method: &str,
+        endpoint: &str,
+    ) -> Result<RequestBuilder, JenkinsError> {
+        let url = format!("{}/{}", self.url, endpoint);
+        Ok(self
+            .client
+            .request(method.parse()?, url)
+            .basic_auth(&self.username, Some(&self.api_token))
+            .header("User-Agent", "jenkins-sdk-rust"))
+    }
+
+    /// Sends a request once, and returns the response whatever its status is.
+    pub async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<Response, JenkinsError> {
+        let req = with_total_timeout(self.get_request_builder(method, endpoint)?, endpoint);
+
+        let resp = if let Some(p) = params {
+            req.form(&p).send().await?
+        } else {
+            req.send().await?
+        };
+
+        Ok(resp)
+    }
+
+    /// Sends a multipart form, which is required to upload files.
+    /// The upload has no total timeout, only the read timeout of the client.
+    pub async fn send_multipart(
+        &self,
+        method: &str,
+        endpoint: &str,
+        form: Form,
+    ) -> Result<Response, JenkinsError> {
+        Ok(self
+            .get_request_builder(method, endpoint)?
+            .multipart(form)
+            .send()
+            .await?)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncClient for ApiTokenJenkinsAsyncClient {
+    /// Sends an asynchronous HTTP request to the Jenkins server.
+    async fn request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<String, JenkinsError> {
+        Ok(self.send(method, endpoint, params).await?.text().await?)
+    }
+}
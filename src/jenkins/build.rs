@@ -1,15 +1,26 @@
+use crate::constant::log::*;
+use crate::constant::util::get_similar_strings;
+use crate::default_config::{QUEUE_POLL_INTERVAL_SECS, QUEUE_POLL_TRIAL_COUNT};
+use crate::jenkins::error_kind::JenkinsErrorKind;
+use crate::jenkins::jenkins_endpoint::build_with_parameters::BuildWithParameters;
 use crate::jenkins::jenkins_endpoint::job_config_json::JobConfigJson;
 use crate::jenkins::jenkins_endpoint::job_config_xml::JobConfigXML;
 use crate::jenkins::jenkins_model::job_definition_json::JobDefinitionJson;
 use crate::jenkins::jenkins_model::job_definition_xml::JobDefinitionXml;
 use crate::jenkins::jenkins_model::shelves::Shelves;
-use crate::jenkins::query::VfpJenkinsClient;
+use crate::jenkins::job_path::JobPath;
+use crate::jenkins::query::{query_queue_item, VfpJenkinsClient};
 use crate::vfp_error::VfpFrontError;
-use jenkins_sdk::{JenkinsError, TriggerBuild};
+use formatx::formatx;
+use jenkins_sdk::{Endpoint, JenkinsError, StopBuild};
+use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VfpJobBuildParam {
@@ -93,6 +104,62 @@ impl VfpJobBuildParam {
         self
     }
 
+    /// # remove change params
+    ///
+    /// remove the change list and shelved change list, which vary from build to build.
+    pub fn remove_change_params(&mut self) -> &mut Self {
+        self.params.remove(Self::PARAM_NAME_CHANGE_LIST);
+        self.params.remove(Self::PARAM_NAME_SHELVED_CHANGE);
+        self
+    }
+
+    /// # remove secret params
+    ///
+    /// remove the params that should never be saved or shown, like passwords.
+    pub fn remove_secret_params(&mut self, specs: &[VfpBuildParamSpec]) -> &mut Self {
+        specs
+            .iter()
+            .filter(|spec| spec.is_secret())
+            .for_each(|spec| {
+                self.params.remove(&spec.name);
+            });
+        self
+    }
+
+    /// # get changes from
+    ///
+    /// the params that are added, removed or changed compared to the base, sorted by name.
+    pub fn get_changes_from(&self, base: &Self) -> Vec<VfpBuildParamChange> {
+        let mut changes: Vec<VfpBuildParamChange> = self
+            .params
+            .iter()
+            .filter_map(|(name, value)| match base.params.get(name) {
+                None => Some(VfpBuildParamChange::Added {
+                    name: name.clone(),
+                    value: value.clone(),
+                }),
+                Some(from) if from != value => Some(VfpBuildParamChange::Changed {
+                    name: name.clone(),
+                    from: from.clone(),
+                    to: value.clone(),
+                }),
+                Some(_) => None,
+            })
+            .chain(
+                base.params
+                    .iter()
+                    .filter(|(name, _)| !self.params.contains_key(*name))
+                    .map(|(name, value)| VfpBuildParamChange::Removed {
+                        name: name.clone(),
+                        value: value.clone(),
+                    }),
+            )
+            .collect();
+
+        changes.sort_by(|l, r| l.get_name().cmp(r.get_name()));
+        changes
+    }
+
     pub fn to_json_value(&self) -> Value {
         serde_json::to_value(
             self.params
@@ -170,24 +237,345 @@ impl VfpJobBuildParam {
     //endregion ⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠐⠒⠒⠒⠒⠚⠛⣿⡟⠄⠄⢠⠄⠄⠄⡄⠄⠄⣠⡶⠶⣶⠶⠶⠂⣠⣶⣶⠂⠄⣸⡿⠄⠄⢀⣿⠇⠄⣰⡿⣠⡾⠋⠄⣼⡟⠄⣠⡾⠋⣾⠏⠄⢰⣿⠁⠄⠄⣾⡏⠄⠠⠿⠿⠋⠠⠶⠶⠿⠶⠾⠋⠄⠽⠟⠄⠄⠄⠃⠄⠄⣼⣿⣤⡤⠤⠤⠤⠤⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄⠄
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum VfpBuildParamChange {
+    Added {
+        name: String,
+        value: Value,
+    },
+    Removed {
+        name: String,
+        value: Value,
+    },
+    Changed {
+        name: String,
+        from: Value,
+        to: Value,
+    },
+}
+
+impl VfpBuildParamChange {
+    pub fn get_name(&self) -> &str {
+        match self {
+            VfpBuildParamChange::Added { name, .. }
+            | VfpBuildParamChange::Removed { name, .. }
+            | VfpBuildParamChange::Changed { name, .. } => name,
+        }
+    }
+}
+
+/// # VfpBuildParamSpec
+///
+/// How a build param is defined in the job, used to drive the interactive editor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VfpBuildParamSpec {
+    pub name: String,
+
+    /// plain text description.
+    pub description: Option<String>,
+
+    pub kind: VfpBuildParamKind,
+
+    /// marked necessary(red) in the description by the job.
+    pub necessary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VfpBuildParamKind {
+    String,
+    /// multi-line string.
+    Text,
+    /// never saved or shown.
+    Password,
+    Bool,
+    Choice(Vec<String>),
+    /// several choices joined by the delimiter, from Extended Choice.
+    MultiChoice {
+        choices: Vec<String>,
+        delimiter: String,
+    },
+    /// a run of the project, like `project#42`.
+    Run {
+        project_name: String,
+    },
+    /// path of a local file, uploaded by multipart.
+    File,
+    /// id of a credential.
+    Credentials,
+    /// choices rendered by the script of Active Choices, which can only be taken as a string.
+    Dynamic {
+        referenced_params: Vec<String>,
+    },
+}
+
+impl VfpBuildParamSpec {
+    pub fn is_secret(&self) -> bool {
+        matches!(self.kind, VfpBuildParamKind::Password)
+    }
+
+    pub fn is_file(&self) -> bool {
+        matches!(self.kind, VfpBuildParamKind::File)
+    }
+
+    /// # parse value
+    ///
+    /// type the user-supplied value by the definition.
+    pub fn parse_value(&self, value: &Value) -> Result<Value, BuildParamError> {
+        let raw = match value {
+            Value::String(s) => s.clone(),
+            _ => value.to_string(),
+        };
+
+        match &self.kind {
+            VfpBuildParamKind::String
+            | VfpBuildParamKind::Text
+            | VfpBuildParamKind::Password
+            | VfpBuildParamKind::Credentials
+            | VfpBuildParamKind::Dynamic { .. } => Ok(Value::String(raw)),
+            VfpBuildParamKind::Run { project_name } => {
+                if raw.parse::<u32>().is_ok() {
+                    Ok(Value::String(format!("{}#{}", project_name, raw)))
+                } else if raw.rsplit_once('#').is_some_and(|(project, number)| {
+                    !project.is_empty() && number.parse::<u32>().is_ok()
+                }) {
+                    Ok(Value::String(raw))
+                } else {
+                    Err(BuildParamError::NotRun {
+                        name: self.name.clone(),
+                        value: raw,
+                    })
+                }
+            }
+            VfpBuildParamKind::File => {
+                if Path::new(&raw).is_file() {
+                    Ok(Value::String(raw))
+                } else {
+                    Err(BuildParamError::FileNotFound {
+                        name: self.name.clone(),
+                        path: raw,
+                    })
+                }
+            }
+            VfpBuildParamKind::MultiChoice { choices, delimiter } => {
+                let selected: Vec<&str> = raw
+                    .split(delimiter.as_str())
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .collect();
+
+                match selected
+                    .iter()
+                    .find(|item| !choices.is_empty() && !choices.iter().any(|c| c == *item))
+                {
+                    Some(invalid) => Err(BuildParamError::NotInChoices {
+                        name: self.name.clone(),
+                        suggestions: get_similar_strings(
+                            invalid,
+                            choices.iter().map(String::as_str),
+                        )
+                        .into_iter()
+                        .map(str::to_string)
+                        .collect(),
+                        value: invalid.to_string(),
+                        choices: choices.clone(),
+                    }),
+                    None => Ok(Value::String(selected.join(delimiter))),
+                }
+            }
+            VfpBuildParamKind::Bool => {
+                raw.parse::<bool>()
+                    .map(Value::Bool)
+                    .map_err(|_| BuildParamError::NotBool {
+                        name: self.name.clone(),
+                        value: raw,
+                    })
+            }
+            VfpBuildParamKind::Choice(choices) => {
+                if choices.contains(&raw) {
+                    Ok(Value::String(raw))
+                } else {
+                    Err(BuildParamError::NotInChoices {
+                        name: self.name.clone(),
+                        suggestions: get_similar_strings(&raw, choices.iter().map(String::as_str))
+                            .into_iter()
+                            .map(str::to_string)
+                            .collect(),
+                        value: raw,
+                        choices: choices.clone(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// # validate param pairs
+///
+/// check user-supplied params against the job definition, and type them by it.
+///
+/// ### Returns
+///
+/// the typed params, or all the invalid ones.
+pub fn validate_param_pairs(
+    specs: &[VfpBuildParamSpec],
+    param_pairs: Vec<(String, Value)>,
+) -> Result<Vec<(String, Value)>, Vec<BuildParamError>> {
+    let mut typed = Vec::new();
+    let mut errors = Vec::new();
+
+    for (name, value) in param_pairs {
+        match specs.iter().find(|spec| spec.name == name) {
+            Some(spec) => match spec.parse_value(&value) {
+                Ok(value) => typed.push((name, value)),
+                Err(e) => errors.push(e),
+            },
+            None => errors.push(BuildParamError::UnknownName {
+                suggestions: get_similar_strings(
+                    &name,
+                    specs.iter().map(|spec| spec.name.as_str()),
+                )
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+                name,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(typed)
+    } else {
+        Err(errors)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BuildParamError {
+    UnknownName {
+        name: String,
+        suggestions: Vec<String>,
+    },
+    NotBool {
+        name: String,
+        value: String,
+    },
+    NotInChoices {
+        name: String,
+        value: String,
+        choices: Vec<String>,
+        suggestions: Vec<String>,
+    },
+    NotRun {
+        name: String,
+        value: String,
+    },
+    FileNotFound {
+        name: String,
+        path: String,
+    },
+}
+
+impl Display for BuildParamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (msg, suggestions) = match self {
+            BuildParamError::UnknownName { name, suggestions } => {
+                (formatx!(ERR_BUILD_PARAM_UNKNOWN, name), suggestions)
+            }
+            BuildParamError::NotBool { name, value } => {
+                return write!(
+                    f,
+                    "{}",
+                    formatx!(ERR_BUILD_PARAM_NOT_BOOL, name, value).unwrap_or_default()
+                );
+            }
+            BuildParamError::NotRun { name, value } => {
+                return write!(
+                    f,
+                    "{}",
+                    formatx!(ERR_BUILD_PARAM_NOT_RUN, name, value).unwrap_or_default()
+                );
+            }
+            BuildParamError::FileNotFound { name, path } => {
+                return write!(
+                    f,
+                    "{}",
+                    formatx!(ERR_BUILD_PARAM_FILE_NOT_FOUND, name, path).unwrap_or_default()
+                );
+            }
+            BuildParamError::NotInChoices {
+                name,
+                value,
+                choices,
+                suggestions,
+            } => (
+                formatx!(
+                    ERR_BUILD_PARAM_NOT_IN_CHOICES,
+                    name,
+                    value,
+                    choices
+                        .iter()
+                        .map(|choice| format!("\"{}\"", choice))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                suggestions,
+            ),
+        };
+
+        write!(f, "{}", msg.unwrap_or_default())?;
+        if !suggestions.is_empty() {
+            write!(
+                f,
+                "{}",
+                formatx!(ERR_BUILD_PARAM_SUGGESTION, suggestions.join(", ")).unwrap_or_default()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// # VfpJobDefinition
+///
+/// The default build params of a job along with their definitions.
+#[derive(Debug, Clone, Default)]
+pub struct VfpJobDefinition {
+    pub template: VfpJobBuildParam,
+
+    pub param_specs: Vec<VfpBuildParamSpec>,
+}
+
 pub trait ToVfpJobBuildParam {
     fn to_vfp_job_build_param(&self) -> VfpJobBuildParam;
+
+    fn to_vfp_build_param_specs(&self) -> Vec<VfpBuildParamSpec>;
+
+    fn to_vfp_job_definition(&self) -> VfpJobDefinition {
+        VfpJobDefinition {
+            template: self.to_vfp_job_build_param(),
+            param_specs: self.to_vfp_build_param_specs(),
+        }
+    }
 }
 
 pub async fn query_job_config_xml(
     client: &VfpJenkinsClient,
     job_name: &str,
-) -> Result<VfpJobBuildParam, VfpFrontError> {
+) -> Result<VfpJobDefinition, VfpFrontError> {
     let content = jenkins_sdk::AsyncRawQuery::raw_query(
         &JobConfigXML {
-            job_name: job_name.to_string(),
+            job_path: job_name.into(),
         },
         client,
     )
-    .await?;
+    .await
+    .map_err(|e| match JenkinsErrorKind::from(&e) {
+        JenkinsErrorKind::Permission(_) => VfpFrontError::JobConfigMissingPermission,
+        _ => VfpFrontError::from(e),
+    })?;
 
     quick_xml::de::from_str::<JobDefinitionXml>(&content)
-        .map(|result| result.to_vfp_job_build_param())
+        .map(|result| result.to_vfp_job_definition())
         .map_err(|e| {
             if content.contains("missing the Job/Configure permission") {
                 VfpFrontError::JobConfigMissingPermission
@@ -202,34 +590,137 @@ pub async fn query_job_config_xml(
 
 pub async fn query_job_config_json(
     client: &VfpJenkinsClient,
-    job_name: impl Into<String>,
-) -> Result<VfpJobBuildParam, VfpFrontError> {
+    job_name: impl Into<JobPath>,
+) -> Result<VfpJobDefinition, VfpFrontError> {
     let content = jenkins_sdk::AsyncRawQuery::raw_query(
         &JobConfigJson {
-            job_name: job_name.into(),
+            job_path: job_name.into(),
         },
         client,
     )
     .await?;
 
     serde_json::from_str::<JobDefinitionJson>(&content)
-        .map(|result| result.to_vfp_job_build_param())
+        .map(|result| result.to_vfp_job_definition())
         .map_err(|e| VfpFrontError::JobConfigParseError {
             e: e.to_string(),
             content,
         })
 }
 
+/// # request build
+///
+/// trigger a build of the job.
+/// file params defined in `param_specs` are uploaded by multipart from their local paths.
+/// returns the id of the queue item if jenkins tells it in `Location`.
 pub async fn request_build(
     client: &VfpJenkinsClient,
     job_name: &str,
     build_param: &VfpJobBuildParam,
+    param_specs: &[VfpBuildParamSpec],
+) -> Result<Option<u64>, JenkinsError> {
+    let mut build_param = build_param.clone();
+    let file_params: Vec<(String, String)> = param_specs
+        .iter()
+        .filter(|spec| spec.is_file())
+        .filter_map(|spec| build_param.params.remove_entry(&spec.name))
+        .filter_map(|(name, value)| {
+            value
+                .as_str()
+                .filter(|path| !path.is_empty())
+                .map(|path| (name, path.to_string()))
+        })
+        .collect();
+
+    let sdk_job_name = JobPath::from(job_name).to_sdk_job_name();
+    let trigger = BuildWithParameters::new(&sdk_job_name, &build_param.to_json_value());
+
+    if file_params.is_empty() {
+        let resp = client
+            .request_response(
+                trigger.method(),
+                &trigger.endpoint(),
+                trigger.params().as_deref(),
+            )
+            .await?;
+        return Ok(get_queue_item_id(&resp));
+    }
+
+    let mut form = Form::new();
+    for (name, value) in trigger.params().unwrap_or_default() {
+        form = form.text(name.to_string(), value.to_string());
+    }
+    for (name, path) in file_params {
+        let content = std::fs::read(&path).map_err(|e| {
+            JenkinsError::RequestError(
+                formatx!(ERR_BUILD_PARAM_FILE_READ_FAILED, &path, e.to_string())
+                    .unwrap_or_default(),
+            )
+        })?;
+        let file_name = Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(path);
+        form = form.part(name, Part::bytes(content).file_name(file_name));
+    }
+
+    let resp = client
+        .request_multipart(trigger.method(), &trigger.endpoint(), form)
+        .await?;
+
+    Ok(get_queue_item_id(&resp))
+}
+
+/// # get queue item id
+///
+/// jenkins answers a triggered build with `Location: <jenkins>/queue/item/<id>/`.
+fn get_queue_item_id(resp: &reqwest::Response) -> Option<u64> {
+    let location = resp
+        .headers()
+        .get(reqwest::header::LOCATION)?
+        .to_str()
+        .ok()?;
+    parse_queue_item_id(location)
+}
+
+fn parse_queue_item_id(location: &str) -> Option<u64> {
+    let (_, rest) = location.split_once("/queue/item/")?;
+    rest.trim_end_matches('/').parse().ok()
+}
+
+/// # wait for queued run
+///
+/// poll the queue item until it leaves the queue, and get the number of its run.
+/// returns `None` if the item is cancelled or still waits after the trials.
+pub async fn wait_for_queued_run(client: &VfpJenkinsClient, queue_id: u64) -> Option<u32> {
+    for _ in 0..QUEUE_POLL_TRIAL_COUNT {
+        if let Ok(item) = query_queue_item(client, queue_id).await {
+            if item.cancelled {
+                return None;
+            }
+            if let Some(executable) = item.executable {
+                return Some(executable.number);
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(QUEUE_POLL_INTERVAL_SECS)).await;
+    }
+
+    None
+}
+
+/// # request abort
+///
+/// stop a running run task. it needs a crumb if the client is authentic by password.
+pub async fn request_abort(
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    build_number: u32,
 ) -> Result<(), JenkinsError> {
     let _ = jenkins_sdk::AsyncRawQuery::raw_query(
-        &TriggerBuild {
-            job_name,
-            params: &build_param.to_json_value(),
-        },
+        &StopBuild(
+            &JobPath::from(job_name).to_sdk_job_name(),
+            build_number.to_string().as_str(),
+        ),
         client,
     )
     .await?;
@@ -246,6 +737,166 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_param_pairs() {
+        use super::*;
+
+        let specs = vec![
+            VfpBuildParamSpec {
+                name: "CustomServer".to_string(),
+                description: None,
+                kind: VfpBuildParamKind::String,
+                necessary: false,
+            },
+            VfpBuildParamSpec {
+                name: "Compile".to_string(),
+                description: None,
+                kind: VfpBuildParamKind::Bool,
+                necessary: false,
+            },
+            VfpBuildParamSpec {
+                name: "TestType".to_string(),
+                description: None,
+                kind: VfpBuildParamKind::Choice(vec!["".to_string(), "Some".to_string()]),
+                necessary: true,
+            },
+        ];
+
+        assert_eq!(
+            validate_param_pairs(
+                &specs,
+                vec![
+                    ("CustomServer".to_string(), Value::Bool(true)),
+                    ("Compile".to_string(), Value::Bool(false)),
+                    ("TestType".to_string(), Value::String("Some".to_string())),
+                ]
+            ),
+            Ok(vec![
+                (
+                    "CustomServer".to_string(),
+                    Value::String("true".to_string())
+                ),
+                ("Compile".to_string(), Value::Bool(false)),
+                ("TestType".to_string(), Value::String("Some".to_string())),
+            ])
+        );
+
+        assert_eq!(
+            validate_param_pairs(
+                &specs,
+                vec![
+                    ("CustomSever".to_string(), Value::String("".to_string())),
+                    ("Compile".to_string(), Value::String("yes".to_string())),
+                    ("TestType".to_string(), Value::String("some".to_string())),
+                ]
+            ),
+            Err(vec![
+                BuildParamError::UnknownName {
+                    name: "CustomSever".to_string(),
+                    suggestions: vec!["CustomServer".to_string()],
+                },
+                BuildParamError::NotBool {
+                    name: "Compile".to_string(),
+                    value: "yes".to_string(),
+                },
+                BuildParamError::NotInChoices {
+                    name: "TestType".to_string(),
+                    value: "some".to_string(),
+                    choices: vec!["".to_string(), "Some".to_string()],
+                    suggestions: vec!["Some".to_string()],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_value_of_more_kinds() {
+        use super::*;
+
+        let spec = |kind| VfpBuildParamSpec {
+            name: "Param".to_string(),
+            description: None,
+            kind,
+            necessary: false,
+        };
+
+        let run = spec(VfpBuildParamKind::Run {
+            project_name: "Some.Job".to_string(),
+        });
+        assert_eq!(
+            run.parse_value(&Value::String("851".to_string())),
+            Ok(Value::String("Some.Job#851".to_string()))
+        );
+        assert_eq!(
+            run.parse_value(&Value::String("Other.Job#12".to_string())),
+            Ok(Value::String("Other.Job#12".to_string()))
+        );
+        assert!(run
+            .parse_value(&Value::String("latest".to_string()))
+            .is_err());
+
+        let multi_choice = spec(VfpBuildParamKind::MultiChoice {
+            choices: vec!["Android".to_string(), "iOS".to_string()],
+            delimiter: ",".to_string(),
+        });
+        assert_eq!(
+            multi_choice.parse_value(&Value::String("Android, iOS".to_string())),
+            Ok(Value::String("Android,iOS".to_string()))
+        );
+        assert!(matches!(
+            multi_choice.parse_value(&Value::String("Android,ios".to_string())),
+            Err(BuildParamError::NotInChoices { value, .. }) if value == "ios"
+        ));
+
+        let file = spec(VfpBuildParamKind::File);
+        assert!(matches!(
+            file.parse_value(&Value::String("not/exist.patch".to_string())),
+            Err(BuildParamError::FileNotFound { .. })
+        ));
+
+        let mut build_params = VfpJobBuildParam::new_with_override_recommend_param(HashMap::from(
+            [("Param".to_string(), Value::String("secret".to_string()))],
+        ));
+        build_params.remove_secret_params(&[spec(VfpBuildParamKind::Password)]);
+        assert!(!build_params.params.contains_key("Param"));
+    }
+
+    #[test]
+    fn test_get_changes_from() {
+        use super::*;
+
+        let base = VfpJobBuildParam::new_with_override_recommend_param(HashMap::from([
+            ("CustomServer".to_string(), Value::String("".to_string())),
+            ("Compile".to_string(), Value::Bool(true)),
+        ]));
+        let mut current = base.clone();
+        current
+            .set_change_list(Some(12345))
+            .set_enable_content_preview(false)
+            .params
+            .remove("Compile");
+
+        assert_eq!(
+            current.get_changes_from(&base),
+            vec![
+                VfpBuildParamChange::Added {
+                    name: "Changelist".to_string(),
+                    value: Value::String("12345".to_string()),
+                },
+                VfpBuildParamChange::Removed {
+                    name: "Compile".to_string(),
+                    value: Value::Bool(true),
+                },
+                VfpBuildParamChange::Changed {
+                    name: "EnableContentPreview".to_string(),
+                    from: Value::Bool(true),
+                    to: Value::Bool(false),
+                },
+            ]
+        );
+        assert!(base.get_changes_from(&base).is_empty());
+    }
+
     #[test]
     fn test_default_vfp_job_build_param() {
         let param = super::VfpJobBuildParam::default();
@@ -253,4 +904,20 @@ mod tests {
         assert!(param.params.is_empty());
         assert!(param.from_default);
     }
+
+    #[test]
+    fn test_parse_queue_item_id() {
+        assert_eq!(
+            super::parse_queue_item_id("https://ci.example.com/queue/item/42/"),
+            Some(42)
+        );
+        assert_eq!(
+            super::parse_queue_item_id("https://ci.example.com/jenkins/queue/item/7"),
+            Some(7)
+        );
+        assert_eq!(
+            super::parse_queue_item_id("https://ci.example.com/job/a/"),
+            None
+        );
+    }
 }
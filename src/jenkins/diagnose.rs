@@ -0,0 +1,311 @@
+use crate::constant::log::*;
+use crate::doctor::diagnostic::{Diagnostic, DiagnosticStatus};
+use crate::jenkins::error_kind::JenkinsErrorKind;
+use crate::jenkins::http_client::build_http_client;
+use crate::jenkins::jenkins_endpoint::who_am_i::WhoAmIResult;
+use crate::jenkins::job_path::{percent_encode, JobPath};
+use crate::jenkins::query::VfpJenkinsClient;
+use formatx::formatx;
+use jenkins_sdk::client::AsyncClient;
+use jenkins_sdk::JenkinsError;
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// set by Jenkins on every response, with its version.
+const HEADER_JENKINS: &str = "X-Jenkins";
+
+/// the job api/json, where a folder or a disabled job is not buildable.
+#[derive(Deserialize)]
+struct JobBuildable {
+    #[serde(default)]
+    buildable: bool,
+}
+
+/// # diagnose login
+///
+/// Check the server, the credentials, the user, the permissions of the job and the crumb issuer,
+/// one by one. The checks needing the credentials are skipped if the server is unreachable,
+/// and the rest are skipped if the credentials are not accepted.
+pub async fn diagnose_login(
+    url: &str,
+    username: &str,
+    client: &VfpJenkinsClient,
+    job_path: Option<&JobPath>,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![diagnose_server(url).await];
+    if diagnostics[0].status == DiagnosticStatus::Failed {
+        diagnostics.push(Diagnostic::new(
+            DiagnosticStatus::Skipped,
+            Ok(DIAG_REST_SKIPPED.into()),
+        ));
+        return diagnostics;
+    }
+
+    let who_am_i = get(client, "whoAmI/api/json")
+        .await
+        .and_then(|content| Ok(serde_json::from_str::<WhoAmIResult>(&content)?));
+    let credential = credential_diagnostic(&who_am_i);
+    let credential_failed = credential.status == DiagnosticStatus::Failed;
+    diagnostics.push(credential);
+    if credential_failed {
+        diagnostics.push(Diagnostic::new(
+            DiagnosticStatus::Skipped,
+            Ok(DIAG_REST_SKIPPED.into()),
+        ));
+        return diagnostics;
+    }
+
+    let user = get(
+        client,
+        &format!("user/{}/api/json?tree=id", percent_encode(username)),
+    )
+    .await;
+    diagnostics.push(user_diagnostic(username, who_am_i.ok().as_ref(), &user));
+
+    match job_path {
+        Some(job_path) => {
+            let job_url_path = job_path.to_url_path();
+            let read = get(
+                client,
+                &format!("{}/api/json?tree=name,buildable", job_url_path),
+            )
+            .await;
+            diagnostics.push(job_read_diagnostic(job_path, &read));
+
+            if let Ok(content) = read {
+                let buildable = serde_json::from_str::<JobBuildable>(&content)
+                    .map(|job| job.buildable)
+                    .map_err(JenkinsError::from);
+                diagnostics.push(job_build_diagnostic(job_path, &buildable));
+                let config = get(client, &format!("{}/config.xml", job_url_path)).await;
+                diagnostics.push(job_configure_diagnostic(job_path, &config));
+            }
+        }
+        None => diagnostics.push(Diagnostic::new(
+            DiagnosticStatus::Skipped,
+            Ok(DIAG_JOB_SKIPPED.into()),
+        )),
+    }
+
+    let crumb = get(client, "crumbIssuer/api/json").await;
+    diagnostics.push(crumb_diagnostic(&crumb));
+
+    diagnostics
+}
+
+async fn get(client: &VfpJenkinsClient, endpoint: &str) -> Result<String, JenkinsError> {
+    client.request("GET", endpoint, None).await
+}
+
+/// the login page is requested without the credentials, and answers whoever is asking.
+async fn diagnose_server(url: &str) -> Diagnostic {
+    let resp = match build_http_client(url, false) {
+        Ok(client) => client
+            .get(format!("{}/login", url.trim_end_matches('/')))
+            .send()
+            .await
+            .map_err(JenkinsError::from),
+        Err(e) => Err(e),
+    };
+
+    match resp {
+        Ok(resp) => server_diagnostic(
+            url,
+            resp.status(),
+            resp.headers()
+                .get(HEADER_JENKINS)
+                .and_then(|v| v.to_str().ok()),
+        ),
+        Err(e) => {
+            let kind = JenkinsErrorKind::from(&e);
+            Diagnostic::new(
+                DiagnosticStatus::Failed,
+                formatx!(DIAG_SERVER_UNREACHABLE, url, kind.to_string()),
+            )
+            .with_hint(kind.get_hint().unwrap_or(HINT_JENKINS_NETWORK))
+        }
+    }
+}
+
+fn server_diagnostic(url: &str, status: StatusCode, version: Option<&str>) -> Diagnostic {
+    match version {
+        Some(version) => Diagnostic::new(
+            DiagnosticStatus::Ok,
+            formatx!(DIAG_SERVER_REACHABLE, url, version),
+        ),
+        None => Diagnostic::new(
+            DiagnosticStatus::Warn,
+            formatx!(DIAG_SERVER_NOT_JENKINS, url, status.to_string()),
+        ),
+    }
+}
+
+fn credential_diagnostic(who_am_i: &Result<WhoAmIResult, JenkinsError>) -> Diagnostic {
+    match who_am_i {
+        Ok(who_am_i) if who_am_i.anonymous || who_am_i.name.eq_ignore_ascii_case("anonymous") => {
+            Diagnostic::new(
+                DiagnosticStatus::Failed,
+                Ok(DIAG_CREDENTIAL_ANONYMOUS.into()),
+            )
+            .with_hint(HINT_JENKINS_AUTH)
+        }
+        Ok(who_am_i) => Diagnostic::new(
+            DiagnosticStatus::Ok,
+            formatx!(DIAG_CREDENTIAL_VALID, &who_am_i.name),
+        ),
+        Err(e) => {
+            let kind = JenkinsErrorKind::from(e);
+            Diagnostic::new(
+                DiagnosticStatus::Failed,
+                formatx!(DIAG_CREDENTIAL_INVALID, kind.to_string()),
+            )
+            .with_hint(kind.get_hint().unwrap_or(HINT_JENKINS_AUTH))
+        }
+    }
+}
+
+fn user_diagnostic(
+    username: &str,
+    who_am_i: Option<&WhoAmIResult>,
+    user: &Result<String, JenkinsError>,
+) -> Diagnostic {
+    match user {
+        Ok(_) => match who_am_i {
+            Some(who_am_i) if !who_am_i.name.eq_ignore_ascii_case(username) => Diagnostic::new(
+                DiagnosticStatus::Warn,
+                formatx!(DIAG_USER_MISMATCH, &who_am_i.name, username),
+            ),
+            _ => Diagnostic::new(DiagnosticStatus::Ok, formatx!(DIAG_USER_EXISTS, username)),
+        },
+        Err(e) => match JenkinsErrorKind::from(e) {
+            JenkinsErrorKind::NotFound(_) => Diagnostic::new(
+                DiagnosticStatus::Failed,
+                formatx!(DIAG_USER_NOT_FOUND, username),
+            ),
+            kind => Diagnostic::new(
+                DiagnosticStatus::Warn,
+                formatx!(DIAG_USER_UNKNOWN, username, kind.to_string()),
+            ),
+        },
+    }
+}
+
+/// Jenkins answers 404 for a job without Job/Read, as if it does not exist.
+fn job_read_diagnostic(job_path: &JobPath, read: &Result<String, JenkinsError>) -> Diagnostic {
+    match read {
+        Ok(_) => Diagnostic::new(DiagnosticStatus::Ok, formatx!(DIAG_JOB_READ_OK, job_path)),
+        Err(e) => Diagnostic::new(
+            DiagnosticStatus::Failed,
+            formatx!(
+                DIAG_JOB_READ_MISSING,
+                job_path,
+                JenkinsErrorKind::from(e).to_string()
+            ),
+        )
+        .with_hint(HINT_JENKINS_JOB_NOT_FOUND),
+    }
+}
+
+/// there is no api for the permissions, so only whether the job can be built is checked.
+fn job_build_diagnostic(job_path: &JobPath, buildable: &Result<bool, JenkinsError>) -> Diagnostic {
+    match buildable {
+        Ok(true) => Diagnostic::new(DiagnosticStatus::Ok, formatx!(DIAG_JOB_BUILD_OK, job_path)),
+        Ok(false) => Diagnostic::new(
+            DiagnosticStatus::Warn,
+            formatx!(DIAG_JOB_BUILD_MISSING, job_path),
+        ),
+        Err(e) => Diagnostic::new(
+            DiagnosticStatus::Warn,
+            formatx!(
+                DIAG_JOB_BUILD_UNKNOWN,
+                job_path,
+                JenkinsErrorKind::from(e).to_string()
+            ),
+        ),
+    }
+}
+
+fn job_configure_diagnostic(
+    job_path: &JobPath,
+    config: &Result<String, JenkinsError>,
+) -> Diagnostic {
+    match config {
+        Ok(_) => Diagnostic::new(
+            DiagnosticStatus::Ok,
+            formatx!(DIAG_JOB_CONFIGURE_OK, job_path),
+        ),
+        // config.xml is only a fallback of the job definition, so it is not a failure.
+        Err(_) => Diagnostic::new(
+            DiagnosticStatus::Warn,
+            formatx!(DIAG_JOB_CONFIGURE_MISSING, job_path),
+        ),
+    }
+}
+
+fn crumb_diagnostic(crumb: &Result<String, JenkinsError>) -> Diagnostic {
+    match crumb {
+        Ok(_) => Diagnostic::new(DiagnosticStatus::Ok, Ok(DIAG_CRUMB_OK.into())),
+        Err(e) => match JenkinsErrorKind::from(e) {
+            JenkinsErrorKind::NotFound(_) => {
+                Diagnostic::new(DiagnosticStatus::Ok, Ok(DIAG_CRUMB_DISABLED.into()))
+            }
+            kind => Diagnostic::new(
+                DiagnosticStatus::Failed,
+                formatx!(DIAG_CRUMB_FAILED, kind.to_string()),
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics() {
+        let url = "https://example.jenkins.com";
+        assert_eq!(
+            server_diagnostic(url, StatusCode::OK, Some("2.440.3")).status,
+            DiagnosticStatus::Ok
+        );
+        assert_eq!(
+            server_diagnostic(url, StatusCode::FOUND, None).status,
+            DiagnosticStatus::Warn
+        );
+
+        let anonymous = WhoAmIResult {
+            anonymous: true,
+            name: "anonymous".to_string(),
+        };
+        assert_eq!(
+            credential_diagnostic(&Ok(anonymous)).status,
+            DiagnosticStatus::Failed
+        );
+
+        let who_am_i = WhoAmIResult {
+            anonymous: false,
+            name: "someone".to_string(),
+        };
+        assert_eq!(
+            user_diagnostic("somebody", Some(&who_am_i), &Ok(String::new())).status,
+            DiagnosticStatus::Warn
+        );
+        assert_eq!(
+            user_diagnostic("Someone", Some(&who_am_i), &Ok(String::new())).status,
+            DiagnosticStatus::Ok
+        );
+
+        let job_path = JobPath::from("Folder/Some-JOB");
+        assert_eq!(
+            job_build_diagnostic(&job_path, &Ok(true)).status,
+            DiagnosticStatus::Ok
+        );
+        assert_eq!(
+            job_build_diagnostic(&job_path, &Ok(false)).status,
+            DiagnosticStatus::Warn
+        );
+
+        let e = JenkinsError::from(serde_json::from_str::<u32>("<html>").unwrap_err());
+        assert_eq!(crumb_diagnostic(&Err(e)).status, DiagnosticStatus::Failed);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::constant::log::*;
+use crate::jenkins::jenkins_url_factor::JenkinsUrlFactor;
+use crate::jenkins::job_path::JobPath;
+use formatx::formatx;
+use jenkins_sdk::JenkinsError;
+use reqwest::StatusCode;
+use std::fmt::Display;
+
+/// # JenkinsErrorKind
+///
+/// What went wrong with a request to Jenkins, classified from [JenkinsError].
+#[derive(Debug, PartialEq, Clone)]
+pub enum JenkinsErrorKind {
+    /// 401. the password or api token is wrong or expired.
+    Auth,
+    /// a login page is returned instead of the data, by an expired session or an SSO.
+    LoginPage,
+    /// the session cookie or bearer token is expired.
+    SessionExpired,
+    /// 403, with the url requested.
+    Permission(String),
+    /// 404 of a job.
+    JobNotFound(JobPath),
+    /// 404 of a run task.
+    BuildNotFound(JobPath, u32),
+    /// 404 of anything else, with the url requested.
+    NotFound(String),
+    /// 5xx.
+    Server(u16),
+    /// timeouts and failed connections.
+    Network(String),
+    /// the response is not the expected json or xml.
+    Parse(String),
+    Other(String),
+}
+
+impl JenkinsErrorKind {
+    /// # from status
+    ///
+    /// classify an error status of the response from `url`.
+    pub fn from_status(status: StatusCode, url: Option<&str>) -> Self {
+        let url = url.unwrap_or_default().to_string();
+        match status {
+            StatusCode::UNAUTHORIZED => JenkinsErrorKind::Auth,
+            StatusCode::FORBIDDEN => JenkinsErrorKind::Permission(url),
+            StatusCode::NOT_FOUND => {
+                let factor = JenkinsUrlFactor::from_url(&url).ok();
+                let job_path = factor.as_ref().and_then(|f| f.get_job_path().cloned());
+                match (job_path, factor.and_then(|f| f.get_build_number())) {
+                    (Some(job_path), Some(build_number)) => {
+                        JenkinsErrorKind::BuildNotFound(job_path, build_number)
+                    }
+                    (Some(job_path), None) => JenkinsErrorKind::JobNotFound(job_path),
+                    _ => JenkinsErrorKind::NotFound(url),
+                }
+            }
+            status if status.is_server_error() => JenkinsErrorKind::Server(status.as_u16()),
+            status => JenkinsErrorKind::Other(
+                formatx!(ERR_JENKINS_UNEXPECTED_STATUS, status.to_string(), url)
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// # get hint
+    ///
+    /// what the user can do about it.
+    pub fn get_hint(&self) -> Option<&'static str> {
+        match self {
+            JenkinsErrorKind::Auth => Some(HINT_JENKINS_AUTH),
+            JenkinsErrorKind::LoginPage => Some(HINT_JENKINS_LOGIN_PAGE),
+            JenkinsErrorKind::SessionExpired => Some(HINT_JENKINS_SESSION_EXPIRED),
+            JenkinsErrorKind::Permission(_) => Some(HINT_JENKINS_PERMISSION),
+            JenkinsErrorKind::JobNotFound(_) => Some(HINT_JENKINS_JOB_NOT_FOUND),
+            JenkinsErrorKind::BuildNotFound(..) => Some(HINT_JENKINS_BUILD_NOT_FOUND),
+            JenkinsErrorKind::NotFound(_) => None,
+            JenkinsErrorKind::Server(_) => Some(HINT_JENKINS_SERVER),
+            JenkinsErrorKind::Network(_) => Some(HINT_JENKINS_NETWORK),
+            JenkinsErrorKind::Parse(_) => Some(HINT_JENKINS_PARSE),
+            JenkinsErrorKind::Other(_) => None,
+        }
+    }
+}
+
+/// # JenkinsAuthError
+///
+/// Auth failures found by the client instead of by the status.
+/// [JenkinsError] of the sdk cannot be extended, so they are carried by
+/// [JenkinsError::RequestError], and are only made and told apart here.
+#[derive(Debug, PartialEq, Clone)]
+pub enum JenkinsAuthError {
+    /// a login page is returned for a json api.
+    LoginPage,
+    /// the session cookie or bearer token is expired and cannot be read again.
+    SessionExpired,
+    /// the session cannot be read from its file or command, with the reason.
+    SessionSourceFailed(String),
+}
+
+impl JenkinsAuthError {
+    pub fn from_error(e: &JenkinsError) -> Option<Self> {
+        let JenkinsError::RequestError(msg) = e else {
+            return None;
+        };
+
+        match msg.as_str() {
+            ERR_JENKINS_LOGIN_PAGE => Some(JenkinsAuthError::LoginPage),
+            ERR_JENKINS_SESSION_EXPIRED => Some(JenkinsAuthError::SessionExpired),
+            msg => msg
+                .strip_prefix(ERR_JENKINS_SESSION_SOURCE_FAILED.trim_end_matches("{}"))
+                .map(|reason| JenkinsAuthError::SessionSourceFailed(reason.to_string())),
+        }
+    }
+}
+
+impl Display for JenkinsAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            JenkinsAuthError::LoginPage => ERR_JENKINS_LOGIN_PAGE.to_string(),
+            JenkinsAuthError::SessionExpired => ERR_JENKINS_SESSION_EXPIRED.to_string(),
+            JenkinsAuthError::SessionSourceFailed(reason) => {
+                formatx!(ERR_JENKINS_SESSION_SOURCE_FAILED, reason).unwrap_or_default()
+            }
+        };
+        write!(f, "{}", str)
+    }
+}
+
+impl From<JenkinsAuthError> for JenkinsError {
+    fn from(e: JenkinsAuthError) -> Self {
+        JenkinsError::RequestError(e.to_string())
+    }
+}
+
+impl From<&JenkinsError> for JenkinsErrorKind {
+    fn from(e: &JenkinsError) -> Self {
+        match e {
+            JenkinsError::Reqwest(e) => match e.status() {
+                Some(status) => JenkinsErrorKind::from_status(status, e.url().map(|u| u.as_str())),
+                None if e.is_decode() => JenkinsErrorKind::Parse(e.to_string()),
+                None if e.is_builder() => JenkinsErrorKind::Other(e.to_string()),
+                None => JenkinsErrorKind::Network(e.to_string()),
+            },
+            JenkinsError::Json(e) => JenkinsErrorKind::Parse(e.to_string()),
+            e => match JenkinsAuthError::from_error(e) {
+                Some(JenkinsAuthError::LoginPage) => JenkinsErrorKind::LoginPage,
+                // the session is read again after logging in, like an expired one.
+                Some(
+                    JenkinsAuthError::SessionExpired | JenkinsAuthError::SessionSourceFailed(_),
+                ) => JenkinsErrorKind::SessionExpired,
+                None => JenkinsErrorKind::Other(e.to_string()),
+            },
+        }
+    }
+}
+
+impl Display for JenkinsErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            JenkinsErrorKind::Auth => ERR_JENKINS_AUTH.to_string(),
+            JenkinsErrorKind::LoginPage => ERR_JENKINS_LOGIN_PAGE.to_string(),
+            JenkinsErrorKind::SessionExpired => ERR_JENKINS_SESSION_EXPIRED.to_string(),
+            JenkinsErrorKind::Permission(url) => {
+                formatx!(ERR_JENKINS_PERMISSION, url).unwrap_or_default()
+            }
+            JenkinsErrorKind::JobNotFound(job_path) => {
+                formatx!(ERR_JENKINS_JOB_NOT_FOUND, job_path).unwrap_or_default()
+            }
+            JenkinsErrorKind::BuildNotFound(job_path, build_number) => {
+                formatx!(ERR_JENKINS_BUILD_NOT_FOUND, build_number, job_path).unwrap_or_default()
+            }
+            JenkinsErrorKind::NotFound(url) => {
+                formatx!(ERR_JENKINS_NOT_FOUND, url).unwrap_or_default()
+            }
+            JenkinsErrorKind::Server(status) => {
+                formatx!(ERR_JENKINS_SERVER, status).unwrap_or_default()
+            }
+            JenkinsErrorKind::Network(e) => formatx!(ERR_JENKINS_NETWORK, e).unwrap_or_default(),
+            JenkinsErrorKind::Parse(e) => formatx!(ERR_JENKINS_PARSE, e).unwrap_or_default(),
+            JenkinsErrorKind::Other(e) => e.clone(),
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// # is login page
+///
+/// whether an html page is returned for a json api, which is the login page of Jenkins or an SSO.
+pub fn is_login_page(endpoint: &str, content_type: Option<&str>) -> bool {
+    (endpoint.contains("api/json") || endpoint.contains("wfapi/"))
+        && content_type.is_some_and(|v| v.trim_start().starts_with("text/html"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status() {
+        assert_eq!(
+            JenkinsErrorKind::from_status(StatusCode::UNAUTHORIZED, None),
+            JenkinsErrorKind::Auth
+        );
+        assert_eq!(
+            JenkinsErrorKind::from_status(
+                StatusCode::NOT_FOUND,
+                Some("https://example.jenkins.com/job/Folder/job/Some-JOB/1234/api/json")
+            ),
+            JenkinsErrorKind::BuildNotFound(JobPath::from("Folder/Some-JOB"), 1234)
+        );
+        assert_eq!(
+            JenkinsErrorKind::from_status(
+                StatusCode::NOT_FOUND,
+                Some("https://example.jenkins.com/job/Some-JOB/config.xml")
+            ),
+            JenkinsErrorKind::JobNotFound(JobPath::from("Some-JOB"))
+        );
+        assert_eq!(
+            JenkinsErrorKind::from_status(
+                StatusCode::NOT_FOUND,
+                Some("https://example.jenkins.com/crumbIssuer/api/json")
+            ),
+            JenkinsErrorKind::NotFound(
+                "https://example.jenkins.com/crumbIssuer/api/json".to_string()
+            )
+        );
+        assert_eq!(
+            JenkinsErrorKind::from_status(StatusCode::BAD_GATEWAY, None),
+            JenkinsErrorKind::Server(502)
+        );
+        assert!(matches!(
+            JenkinsErrorKind::from_status(StatusCode::BAD_REQUEST, None),
+            JenkinsErrorKind::Other(_)
+        ));
+    }
+
+    #[test]
+    fn test_from_jenkins_error() {
+        let e = JenkinsError::from(JenkinsAuthError::LoginPage);
+        assert_eq!(JenkinsErrorKind::from(&e), JenkinsErrorKind::LoginPage);
+        let e = JenkinsError::from(JenkinsAuthError::SessionExpired);
+        assert_eq!(JenkinsErrorKind::from(&e), JenkinsErrorKind::SessionExpired);
+        let e = JenkinsError::from(JenkinsAuthError::SessionSourceFailed("cmd 1".to_string()));
+        assert_eq!(
+            JenkinsAuthError::from_error(&e),
+            Some(JenkinsAuthError::SessionSourceFailed("cmd 1".to_string()))
+        );
+        assert_eq!(JenkinsErrorKind::from(&e), JenkinsErrorKind::SessionExpired);
+        let e = JenkinsError::RequestError("other".to_string());
+        assert_eq!(JenkinsAuthError::from_error(&e), None);
+
+        let e = JenkinsError::from(serde_json::from_str::<u32>("<html>").unwrap_err());
+        let kind = JenkinsErrorKind::from(&e);
+        assert!(matches!(kind, JenkinsErrorKind::Parse(_)));
+        assert_eq!(kind.get_hint(), Some(HINT_JENKINS_PARSE));
+
+        assert!(is_login_page(
+            "api/json?tree=jobs[name]",
+            Some("text/html;charset=utf-8")
+        ));
+        assert!(!is_login_page("job/Some-JOB/config.xml", Some("text/html")));
+        assert!(!is_login_page("api/json", Some("application/json")));
+    }
+}
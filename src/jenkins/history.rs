@@ -0,0 +1,443 @@
+use crate::app_state::AppState;
+use crate::constant::log::*;
+use crate::constant::util::get_readable_duration;
+use crate::jenkins::jenkins_model::run_status::RunStatus;
+use crate::jenkins::jenkins_model::workflow_run::WorkflowRun;
+use crate::jenkins::query::VfpJenkinsClient;
+use crate::pretty_log::{colored_println, ThemeColor};
+use crate::service::jenkins_rpc_service::JenkinsRpcService;
+use crate::vfp_error::VfpFrontError;
+use chrono::{DateTime, Local};
+use formatx::formatx;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// # HistoryEntry
+///
+/// One row of the build history.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub number: u32,
+    pub user: Option<String>,
+    pub change_list: Option<u32>,
+    pub shelves: Option<String>,
+    #[serde(serialize_with = "serialize_status")]
+    pub result: RunStatus,
+
+    /// duration in milliseconds. None while the run task is in progress.
+    pub duration: Option<u64>,
+
+    /// start time in milliseconds since the epoch. exported as RFC 3339 like the csv.
+    #[serde(serialize_with = "serialize_started_at")]
+    pub started_at: Option<u64>,
+
+    /// name of the failed stage. only queried for the statistics and the export.
+    pub failed_stage: Option<String>,
+}
+
+impl From<&WorkflowRun> for HistoryEntry {
+    fn from(run: &WorkflowRun) -> Self {
+        let build_params = run.get_build_params();
+
+        Self {
+            number: run.number,
+            user: run.get_user_id().map(str::to_string),
+            change_list: run
+                .get_change_list_in_build_meta_data()
+                .or(build_params.get_change_list()),
+            shelves: build_params
+                .get_shelve_changes()
+                .map(|shelves| shelves.to_string()),
+            result: run.result,
+            duration: run.duration.filter(|_| run.result != RunStatus::Processing),
+            started_at: run.timestamp,
+            failed_stage: None,
+        }
+    }
+}
+
+impl HistoryEntry {
+    fn get_started_at_str(&self) -> Option<String> {
+        self.started_at
+            .and_then(get_local_time)
+            .map(|started_at| started_at.format("%Y-%m-%d %H:%M:%S").to_string())
+    }
+
+    /// the start time for the export, which keeps the time zone.
+    fn get_started_at_rfc3339(&self) -> Option<String> {
+        self.started_at
+            .and_then(get_local_time)
+            .map(|started_at| started_at.to_rfc3339())
+    }
+}
+
+fn get_local_time(timestamp: u64) -> Option<DateTime<Local>> {
+    DateTime::from_timestamp_millis(timestamp as i64).map(|time| time.with_timezone(&Local))
+}
+
+fn get_status_text(status: RunStatus) -> &'static str {
+    match status {
+        RunStatus::Success => INFO_STATUS_SUCCESS,
+        RunStatus::Failure => INFO_STATUS_FAILURE,
+        RunStatus::Processing => INFO_STATUS_IN_PROGRESS,
+    }
+}
+
+fn serialize_status<S: Serializer>(status: &RunStatus, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(get_status_text(*status))
+}
+
+fn serialize_started_at<S: Serializer>(
+    started_at: &Option<u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    started_at
+        .and_then(get_local_time)
+        .map(|started_at| started_at.to_rfc3339())
+        .serialize(serializer)
+}
+
+/// # HistoryStats
+///
+/// Statistics of the finished run tasks in the history.
+#[derive(Debug, PartialEq)]
+pub struct HistoryStats {
+    pub finished: usize,
+    pub success: usize,
+
+    /// median duration in milliseconds of the finished run tasks.
+    pub median_duration: Option<u64>,
+
+    /// the stage failed most and its count.
+    pub most_failed_stage: Option<(String, usize)>,
+}
+
+impl HistoryStats {
+    pub fn from_entries(entries: &[HistoryEntry]) -> Self {
+        let finished: Vec<&HistoryEntry> = entries
+            .iter()
+            .filter(|entry| entry.result != RunStatus::Processing)
+            .collect();
+
+        let mut durations: Vec<u64> = finished.iter().filter_map(|entry| entry.duration).collect();
+        durations.sort_unstable();
+        let median_duration = match durations.len() {
+            0 => None,
+            len if len.is_multiple_of(2) => Some((durations[len / 2 - 1] + durations[len / 2]) / 2),
+            len => Some(durations[len / 2]),
+        };
+
+        let mut failed_stage_counts: HashMap<&str, usize> = HashMap::new();
+        finished
+            .iter()
+            .filter_map(|entry| entry.failed_stage.as_deref())
+            .for_each(|stage| *failed_stage_counts.entry(stage).or_default() += 1);
+        // ties are broken by name to keep the result stable.
+        let most_failed_stage = failed_stage_counts
+            .into_iter()
+            .max_by(|(a_name, a_count), (b_name, b_count)| {
+                a_count.cmp(b_count).then(b_name.cmp(a_name))
+            })
+            .map(|(name, count)| (name.to_string(), count));
+
+        Self {
+            finished: finished.len(),
+            success: finished
+                .iter()
+                .filter(|entry| entry.result == RunStatus::Success)
+                .count(),
+            median_duration,
+            most_failed_stage,
+        }
+    }
+
+    pub fn get_success_rate(&self) -> Option<f64> {
+        (self.finished > 0).then(|| self.success as f64 / self.finished as f64)
+    }
+}
+
+/// # query history
+///
+/// Query the recent run tasks of a job as history entries. newest first.
+///
+/// ### Arguments
+///
+/// * `user`: keep only the run tasks started by the user.
+/// * `with_failed_stage`: also query the failed stage of failed run tasks.
+pub async fn query_history(
+    client: Arc<VfpJenkinsClient>,
+    job_name: &str,
+    count: u32,
+    user: Option<&str>,
+    with_failed_stage: bool,
+) -> Result<Vec<HistoryEntry>, VfpFrontError> {
+    let runs = JenkinsRpcService::query_recent_runs(client.clone(), job_name, count).await?;
+    let mut entries: Vec<HistoryEntry> = runs
+        .iter()
+        .filter(|run| user.is_none_or(|user| run.is_mine(user)))
+        .map(HistoryEntry::from)
+        .collect();
+
+    if with_failed_stage {
+        let failed_numbers: Vec<u32> = entries
+            .iter()
+            .filter(|entry| entry.result == RunStatus::Failure)
+            .map(|entry| entry.number)
+            .collect();
+        let mut failed_stages =
+            JenkinsRpcService::query_failed_stages(client, job_name, &failed_numbers).await;
+        entries.iter_mut().for_each(|entry| {
+            entry.failed_stage = failed_stages.remove(&entry.number);
+        });
+    }
+
+    Ok(entries)
+}
+
+/// # print history
+///
+/// Print the history entries as a table.
+pub fn print_history(app_state: &AppState, job_name: &str, entries: &[HistoryEntry]) {
+    let mut stdout = app_state.get_stdout();
+
+    colored_println(
+        &mut stdout,
+        ThemeColor::Main,
+        &formatx!(HISTORY_TITLE, job_name, entries.len()).unwrap_or_default(),
+    );
+
+    if entries.is_empty() {
+        colored_println(&mut stdout, ThemeColor::Second, HISTORY_EMPTY);
+        return;
+    }
+
+    colored_println(
+        &mut stdout,
+        ThemeColor::Second,
+        &get_history_row([
+            "#",
+            "User",
+            "CL",
+            "Shelves",
+            "Result",
+            "Duration",
+            "Started at",
+        ]),
+    );
+    for entry in entries {
+        let color = match entry.result {
+            RunStatus::Success => ThemeColor::Success,
+            RunStatus::Failure => ThemeColor::Error,
+            RunStatus::Processing => ThemeColor::Warn,
+        };
+
+        colored_println(
+            &mut stdout,
+            color,
+            &get_history_row([
+                &entry.number.to_string(),
+                entry.user.as_deref().unwrap_or("-"),
+                &entry
+                    .change_list
+                    .map(|cl| cl.to_string())
+                    .unwrap_or("-".to_string()),
+                entry.shelves.as_deref().unwrap_or("-"),
+                get_status_text(entry.result),
+                &entry
+                    .duration
+                    .map(get_readable_duration)
+                    .unwrap_or("-".to_string()),
+                &entry.get_started_at_str().unwrap_or("-".to_string()),
+            ]),
+        );
+    }
+}
+
+fn get_history_row(cells: [&str; 7]) -> String {
+    format!(
+        "{:<8} {:<16} {:<10} {:<16} {:<12} {:<10} {}",
+        cells[0], cells[1], cells[2], cells[3], cells[4], cells[5], cells[6]
+    )
+}
+
+/// # print history stats
+pub fn print_history_stats(app_state: &AppState, stats: &HistoryStats) {
+    let mut stdout = app_state.get_stdout();
+
+    colored_println(&mut stdout, ThemeColor::Main, HISTORY_STATS_TITLE);
+    colored_println(
+        &mut stdout,
+        ThemeColor::Second,
+        &match stats.get_success_rate() {
+            Some(rate) => formatx!(
+                HISTORY_STATS_SUCCESS_RATE,
+                format!("{:.1}%", rate * 100.0),
+                stats.success,
+                stats.finished
+            ),
+            None => formatx!(HISTORY_STATS_SUCCESS_RATE, "-", 0, 0),
+        }
+        .unwrap_or_default(),
+    );
+    colored_println(
+        &mut stdout,
+        ThemeColor::Second,
+        &formatx!(
+            HISTORY_STATS_MEDIAN_DURATION,
+            stats
+                .median_duration
+                .map(get_readable_duration)
+                .unwrap_or("-".to_string())
+        )
+        .unwrap_or_default(),
+    );
+    colored_println(
+        &mut stdout,
+        ThemeColor::Second,
+        &match stats.most_failed_stage {
+            Some((ref name, count)) => formatx!(HISTORY_STATS_MOST_FAILED_STAGE, name, count),
+            None => formatx!(HISTORY_STATS_MOST_FAILED_STAGE, "-", 0),
+        }
+        .unwrap_or_default(),
+    );
+}
+
+/// # export history
+///
+/// Export the history entries to a csv or json file, decided by the extension.
+pub fn export_history(path: &Path, entries: &[HistoryEntry]) -> Result<(), VfpFrontError> {
+    let content = match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("csv") => get_history_csv(entries),
+        Some("json") => serde_json::to_string_pretty(entries).map_err(|e| {
+            VfpFrontError::Custom(formatx!(ERR_HISTORY_EXPORT_FAILED, e).unwrap_or_default())
+        })?,
+        _ => {
+            return Err(VfpFrontError::Custom(
+                formatx!(ERR_HISTORY_EXPORT_UNKNOWN_FORMAT, path.display()).unwrap_or_default(),
+            ));
+        }
+    };
+
+    std::fs::write(path, content).map_err(|e| {
+        VfpFrontError::Custom(formatx!(ERR_HISTORY_EXPORT_FAILED, e).unwrap_or_default())
+    })
+}
+
+fn get_history_csv(entries: &[HistoryEntry]) -> String {
+    fn escape(cell: &str) -> String {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    }
+
+    let mut csv =
+        "number,user,change_list,shelves,result,duration,started_at,failed_stage\n".to_string();
+    for entry in entries {
+        let cells = [
+            entry.number.to_string(),
+            entry.user.clone().unwrap_or_default(),
+            entry
+                .change_list
+                .map(|cl| cl.to_string())
+                .unwrap_or_default(),
+            entry.shelves.clone().unwrap_or_default(),
+            get_status_text(entry.result).to_string(),
+            entry
+                .duration
+                .map(|duration| duration.to_string())
+                .unwrap_or_default(),
+            entry.get_started_at_rfc3339().unwrap_or_default(),
+            entry.failed_stage.clone().unwrap_or_default(),
+        ];
+        csv += &cells
+            .iter()
+            .map(|cell| escape(cell))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push('\n');
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        number: u32,
+        result: RunStatus,
+        duration: Option<u64>,
+        stage: Option<&str>,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            number,
+            user: Some("alice".to_string()),
+            change_list: Some(100 + number),
+            shelves: None,
+            result,
+            duration,
+            started_at: None,
+            failed_stage: stage.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_history_stats() {
+        let entries = vec![
+            entry(6, RunStatus::Processing, None, None),
+            entry(5, RunStatus::Success, Some(4000), None),
+            entry(4, RunStatus::Failure, Some(1000), Some("Compile")),
+            entry(3, RunStatus::Success, Some(3000), None),
+            entry(2, RunStatus::Failure, Some(2000), Some("Publish")),
+            entry(1, RunStatus::Failure, Some(5000), Some("Compile")),
+        ];
+
+        let stats = HistoryStats::from_entries(&entries);
+
+        assert_eq!(stats.finished, 5);
+        assert_eq!(stats.success, 2);
+        assert_eq!(stats.get_success_rate(), Some(0.4));
+        assert_eq!(stats.median_duration, Some(3000));
+        assert_eq!(stats.most_failed_stage, Some(("Compile".to_string(), 2)));
+
+        let stats = HistoryStats::from_entries(&entries[..1]);
+        assert_eq!(stats.get_success_rate(), None);
+        assert_eq!(stats.median_duration, None);
+    }
+
+    #[test]
+    fn test_get_history_csv() {
+        let mut failed = entry(2, RunStatus::Failure, Some(2000), Some("Build, \"fast\""));
+        failed.shelves = Some("11,12".to_string());
+        let entries = vec![entry(3, RunStatus::Success, Some(3000), None), failed];
+
+        assert_eq!(
+            get_history_csv(&entries),
+            "number,user,change_list,shelves,result,duration,started_at,failed_stage\n\
+             3,alice,103,,SUCCESS,3000,,\n\
+             2,alice,102,\"11,12\",FAILURE,2000,,\"Build, \"\"fast\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_export_json_like_csv() {
+        let mut success = entry(3, RunStatus::Success, Some(3000), None);
+        success.started_at = Some(1746759736434);
+        let started_at = success.get_started_at_rfc3339().unwrap();
+
+        let json = serde_json::to_value([&success]).unwrap();
+        assert_eq!(json[0]["result"], INFO_STATUS_SUCCESS);
+        assert_eq!(json[0]["started_at"], started_at.as_str());
+        assert!(get_history_csv(&[success]).contains(&started_at));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::constant::log::ERR_HTTP_CONFIG_INVALID;
+use crate::default_config::{self, HttpConfig};
+use formatx::formatx;
+use jenkins_sdk::JenkinsError;
+use reqwest::{Certificate, Client, Identity, NoProxy, Proxy, RequestBuilder, StatusCode};
+use std::fmt::Display;
+use std::time::Duration;
+
+/// # build http client
+///
+/// The reqwest client to the Jenkins at `url`, with the `[http]` settings of the runtime config.
+pub fn build_http_client(url: &str, cookie_store: bool) -> Result<Client, JenkinsError> {
+    build_http_client_with_config(url, cookie_store, &default_config::runtime().http)
+}
+
+fn build_http_client_with_config(
+    url: &str,
+    cookie_store: bool,
+    config: &HttpConfig,
+) -> Result<Client, JenkinsError> {
+    // the proxies of the system are ignored, only the configured or `HTTP(S)_PROXY` ones are used.
+    // the total timeout is set by each request, see `with_total_timeout`.
+    let mut builder = Client::builder()
+        .cookie_store(cookie_store)
+        .no_proxy()
+        .read_timeout(get_timeout(config))
+        .connect_timeout(Duration::from_secs(
+            config
+                .connect_timeout_secs
+                .unwrap_or(default_config::HTTP_CONNECT_TIMEOUT_SECS),
+        ));
+
+    if let Some(path) = &config.ca_bundle_path {
+        let pem = read_file("ca_bundle_path", path)?;
+        let certificates =
+            Certificate::from_pem_bundle(&pem).map_err(|e| invalid("ca_bundle_path", e))?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if is_insecure_server(url, &config.insecure_servers) {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    if let Some(proxy_url) = get_proxy(&config.http_proxy, &["HTTP_PROXY", "http_proxy"]) {
+        let proxy = Proxy::http(&proxy_url).map_err(|e| invalid("http_proxy", e))?;
+        builder = builder.proxy(proxy.no_proxy(get_no_proxy(config)));
+    }
+
+    if let Some(proxy_url) = get_proxy(&config.https_proxy, &["HTTPS_PROXY", "https_proxy"]) {
+        let proxy = Proxy::https(&proxy_url).map_err(|e| invalid("https_proxy", e))?;
+        builder = builder.proxy(proxy.no_proxy(get_no_proxy(config)));
+    }
+
+    if let Some(identity) = load_identity(config)? {
+        builder = builder.identity(identity);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// # with total timeout
+///
+/// the timeout of the `[http]` config for the whole request. the logs, which may be large,
+/// are only limited by the read timeout of the client, like the uploads.
+pub fn with_total_timeout(req: RequestBuilder, endpoint: &str) -> RequestBuilder {
+    if is_log_endpoint(endpoint) {
+        return req;
+    }
+
+    req.timeout(get_timeout(&default_config::runtime().http))
+}
+
+fn get_timeout(config: &HttpConfig) -> Duration {
+    Duration::from_secs(
+        config
+            .timeout_secs
+            .unwrap_or(default_config::HTTP_TIMEOUT_SECS),
+    )
+}
+
+/// # is log endpoint
+///
+/// the console log of a run, or the log of a pipeline node.
+fn is_log_endpoint(endpoint: &str) -> bool {
+    let path = endpoint.split('?').next().unwrap_or_default();
+    path.ends_with("/logText/progressiveText")
+        || path.ends_with("/consoleText")
+        || path.ends_with("/wfapi/log")
+}
+
+/// # RetryPolicy
+///
+/// How a failed idempotent request is retried, with jittered exponential backoff.
+#[derive(Debug, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &HttpConfig) -> Self {
+        Self {
+            max_retries: config
+                .max_retries
+                .unwrap_or(default_config::HTTP_MAX_RETRIES),
+            base_delay: Duration::from_millis(default_config::HTTP_RETRY_BASE_DELAY_MILLIS),
+            max_delay: Duration::from_millis(default_config::HTTP_RETRY_MAX_DELAY_MILLIS),
+        }
+    }
+
+    /// # get delay
+    ///
+    /// the delay before the `attempt`th retry, starting at 1. it is doubled for each attempt
+    /// up to the max delay, and then `jitter` in `[0, 1)` takes off up to half of it.
+    pub fn get_delay(&self, attempt: u32, jitter: f64) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+
+        delay.mul_f64(1.0 - jitter.clamp(0.0, 1.0) / 2.0)
+    }
+}
+
+/// # is idempotent
+///
+/// whether the request can be sent again without side effects.
+pub fn is_idempotent(method: &str) -> bool {
+    method.eq_ignore_ascii_case("GET") || method.eq_ignore_ascii_case("HEAD")
+}
+
+/// # is retryable status
+///
+/// statuses of a busy or restarting Jenkins, or of the proxy before it.
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// # is transient error
+///
+/// timeouts and failed connections, which may succeed next time.
+pub fn is_transient_error(e: &JenkinsError) -> bool {
+    match e {
+        JenkinsError::Reqwest(e) => e.is_timeout() || e.is_connect(),
+        _ => false,
+    }
+}
+
+/// # is insecure server
+///
+/// whether the host, or the host with the port, of the url is in `insecure_servers`.
+fn is_insecure_server(url: &str, insecure_servers: &[String]) -> bool {
+    let Some((host, port)) = url::Url::parse(url).ok().and_then(|url| {
+        Some((
+            url.host_str()?.to_ascii_lowercase(),
+            url.port_or_known_default(),
+        ))
+    }) else {
+        return false;
+    };
+
+    insecure_servers.iter().any(|server| {
+        let server = server.trim().to_ascii_lowercase();
+        match server.rsplit_once(':') {
+            Some((server_host, server_port)) => {
+                server_host == host && port.is_some_and(|port| port.to_string() == server_port)
+            }
+            None => server == host,
+        }
+    })
+}
+
+fn get_env(keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| std::env::var(key).ok())
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// # get proxy
+///
+/// the configured proxy, or the one in the environment variables `env_keys`.
+fn get_proxy(proxy: &Option<String>, env_keys: &[&str]) -> Option<String> {
+    proxy
+        .clone()
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| get_env(env_keys))
+}
+
+fn get_no_proxy(config: &HttpConfig) -> Option<NoProxy> {
+    config
+        .no_proxy
+        .clone()
+        .or_else(|| get_env(&["NO_PROXY", "no_proxy"]))
+        .and_then(|no_proxy| NoProxy::from_string(&no_proxy))
+}
+
+/// # load identity
+///
+/// the client certificate. a PEM certificate with its key, or a PKCS#12 file.
+fn load_identity(config: &HttpConfig) -> Result<Option<Identity>, JenkinsError> {
+    let Some(cert_path) = &config.client_cert_path else {
+        return Ok(None);
+    };
+    let cert = read_file("client_cert_path", cert_path)?;
+
+    let identity = match &config.client_key_path {
+        Some(key_path) => Identity::from_pkcs8_pem(&cert, &read_file("client_key_path", key_path)?),
+        None => Identity::from_pkcs12_der(
+            &cert,
+            config.client_cert_password.as_deref().unwrap_or_default(),
+        ),
+    };
+
+    identity
+        .map(Some)
+        .map_err(|e| invalid("client_cert_path", e))
+}
+
+fn read_file(key: &str, path: &str) -> Result<Vec<u8>, JenkinsError> {
+    std::fs::read(path).map_err(|e| invalid(key, format!("{} {}", path, e)))
+}
+
+fn invalid(key: &str, e: impl Display) -> JenkinsError {
+    JenkinsError::RequestError(
+        formatx!(ERR_HTTP_CONFIG_INVALID, key, e.to_string()).unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_insecure_server() {
+        let insecure_servers = vec![
+            "Jenkins.Internal".to_string(),
+            "partner.jenkins.com:8443".to_string(),
+        ];
+
+        assert!(is_insecure_server(
+            "https://jenkins.internal/job/main/",
+            &insecure_servers
+        ));
+        assert!(is_insecure_server(
+            "https://partner.jenkins.com:8443",
+            &insecure_servers
+        ));
+        assert!(!is_insecure_server(
+            "https://partner.jenkins.com",
+            &insecure_servers
+        ));
+        assert!(!is_insecure_server(
+            "https://example.jenkins.com",
+            &insecure_servers
+        ));
+        assert!(!is_insecure_server("not a url", &insecure_servers));
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let policy = RetryPolicy::from_config(&HttpConfig {
+            max_retries: Some(5),
+            ..Default::default()
+        });
+
+        assert_eq!(policy.max_retries, 5);
+        assert_eq!(policy.get_delay(1, 0.0), Duration::from_millis(500));
+        assert_eq!(policy.get_delay(3, 0.0), Duration::from_millis(2000));
+        assert_eq!(policy.get_delay(3, 0.5), Duration::from_millis(1500));
+        assert_eq!(policy.get_delay(10, 0.0), Duration::from_millis(8000));
+        assert!(policy.get_delay(10, 0.99) >= Duration::from_millis(4000));
+
+        assert!(is_log_endpoint("job/a/12/logText/progressiveText?start=0"));
+        assert!(is_log_endpoint("job/a/12/execution/node/7/wfapi/log"));
+        assert!(!is_log_endpoint("job/a/12/api/json"));
+
+        assert!(is_idempotent("get"));
+        assert!(!is_idempotent("POST"));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_build_http_client_with_config() {
+        let config = HttpConfig {
+            http_proxy: Some("http://proxy.example.com:8080".to_string()),
+            https_proxy: Some("http://proxy.example.com:8080".to_string()),
+            no_proxy: Some("localhost,.example.com".to_string()),
+            client_cert_password: Some("some-password".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            build_http_client_with_config("https://example.jenkins.com", true, &config).is_ok()
+        );
+        assert!(!format!("{:?}", config).contains("some-password"));
+
+        let config = HttpConfig {
+            ca_bundle_path: Some("NOT_EXIST.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            build_http_client_with_config("https://example.jenkins.com", false, &config).is_err()
+        );
+    }
+}
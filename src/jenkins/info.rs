@@ -0,0 +1,163 @@
+use crate::app_state::AppState;
+use crate::constant::log::*;
+use crate::constant::util::get_readable_duration;
+use crate::jenkins::jenkins_model::run_status::RunStatus;
+use crate::jenkins::jenkins_model::stage_status::StageStatus;
+use crate::jenkins::query::{query_run_describe, query_run_info, VfpJenkinsClient};
+use crate::jenkins::util::get_jenkins_workflow_run_url;
+use crate::pretty_log::{colored_println, ThemeColor};
+use crate::vfp_error::VfpFrontError;
+use chrono::{DateTime, Local, Utc};
+use formatx::formatx;
+
+/// # info
+///
+/// Print the summary of a run task.
+///
+/// ### Arguments
+///
+/// * `app_state`:
+/// * `client`:
+/// * `job_name`:
+/// * `build_number`:
+/// * `show_stages`: print the stage view of a pipeline run task.
+pub async fn info(
+    app_state: &AppState,
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    build_number: u32,
+    show_stages: bool,
+) -> Result<(), VfpFrontError> {
+    let run_info = query_run_info(client, job_name, build_number).await?;
+    let mut stdout = app_state.get_stdout();
+
+    colored_println(
+        &mut stdout,
+        ThemeColor::Main,
+        &formatx!(INFO_RUN_TASK_TITLE, build_number, job_name).unwrap_or_default(),
+    );
+
+    let (status_color, status) = match run_info.result {
+        RunStatus::Success => (ThemeColor::Success, INFO_STATUS_SUCCESS),
+        RunStatus::Failure => (ThemeColor::Error, INFO_STATUS_FAILURE),
+        RunStatus::Processing => (ThemeColor::Warn, INFO_STATUS_IN_PROGRESS),
+    };
+    colored_println(
+        &mut stdout,
+        status_color,
+        &formatx!(INFO_RUN_TASK_STATUS, status).unwrap_or_default(),
+    );
+
+    if let Some(user_id) = run_info.get_user_id() {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Second,
+            &formatx!(INFO_RUN_TASK_USER, user_id).unwrap_or_default(),
+        );
+    }
+
+    if let Some(started_at) = run_info
+        .timestamp
+        .and_then(|timestamp| DateTime::from_timestamp_millis(timestamp as i64))
+    {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Second,
+            &formatx!(
+                INFO_RUN_TASK_STARTED_AT,
+                started_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            )
+            .unwrap_or_default(),
+        );
+    }
+
+    match run_info.result {
+        RunStatus::Processing => {
+            if let Some(progress) =
+                run_info.get_progress(Utc::now().timestamp_millis().max(0) as u64)
+            {
+                colored_println(
+                    &mut stdout,
+                    ThemeColor::Second,
+                    &formatx!(
+                        INFO_RUN_TASK_ELAPSED,
+                        get_readable_duration(progress.elapsed)
+                    )
+                    .unwrap_or_default(),
+                );
+            }
+        }
+        _ => {
+            if let Some(duration) = run_info.duration {
+                colored_println(
+                    &mut stdout,
+                    ThemeColor::Second,
+                    &formatx!(INFO_RUN_TASK_DURATION, get_readable_duration(duration))
+                        .unwrap_or_default(),
+                );
+            }
+        }
+    }
+
+    if let Some(cl) = run_info.get_change_list_in_build_meta_data() {
+        colored_println(
+            &mut stdout,
+            ThemeColor::Second,
+            &formatx!(INFO_RUN_TASK_CL, cl).unwrap_or_default(),
+        );
+    }
+
+    colored_println(
+        &mut stdout,
+        ThemeColor::Second,
+        &format!(
+            "{} {}",
+            URL_OUTPUT,
+            get_jenkins_workflow_run_url(
+                app_state.get_db().get_jenkins_url().as_ref().unwrap(),
+                job_name,
+                build_number
+            )
+        ),
+    );
+
+    if !show_stages {
+        return Ok(());
+    }
+
+    let describe = match query_run_describe(client, job_name, build_number).await {
+        Ok(describe) if run_info.is_pipeline() => describe,
+        _ => {
+            colored_println(&mut stdout, ThemeColor::Warn, INFO_STAGE_VIEW_UNAVAILABLE);
+            return Ok(());
+        }
+    };
+
+    colored_println(&mut stdout, ThemeColor::Main, INFO_STAGES_TITLE);
+    for stage in &describe.stages {
+        let color = match stage.status {
+            StageStatus::Success => ThemeColor::Success,
+            StageStatus::Failed => ThemeColor::Error,
+            StageStatus::InProgress => ThemeColor::Main,
+            StageStatus::NotExecuted => ThemeColor::Second,
+            _ => ThemeColor::Warn,
+        };
+
+        colored_println(
+            &mut stdout,
+            color,
+            &formatx!(
+                INFO_STAGE_ITEM,
+                format!("{:<14}", stage.status.to_string()),
+                stage.name.as_str(),
+                get_readable_duration(stage.duration_millis)
+            )
+            .unwrap_or_default(),
+        );
+    }
+
+    Ok(())
+}
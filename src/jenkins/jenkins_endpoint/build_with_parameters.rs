@@ -0,0 +1,103 @@
+use serde_json::Value;
+
+/// trigger a build of the job with parameters.
+///
+/// unlike `jenkins_sdk::TriggerBuild`, values which are not strings are sent as text
+/// instead of empty strings.
+pub struct BuildWithParameters<'a> {
+    /// the url path of the job without the leading `job/`.
+    pub job_name: &'a str,
+
+    pub params: Vec<(String, String)>,
+}
+
+impl<'a> BuildWithParameters<'a> {
+    pub fn new(job_name: &'a str, params: &Value) -> Self {
+        let params = params
+            .as_object()
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(name, value)| {
+                        get_param_text(value).map(|value| (name.clone(), value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { job_name, params }
+    }
+}
+
+/// text of a param value. arrays are joined by `,` like a multi-select choice,
+/// and `null` is not sent.
+fn get_param_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Array(values) => Some(
+            values
+                .iter()
+                .filter_map(get_param_text)
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        _ => Some(value.to_string()),
+    }
+}
+
+impl jenkins_sdk::Endpoint for BuildWithParameters<'_> {
+    /// HTTP method used (POST).
+    fn method(&self) -> &str {
+        "POST"
+    }
+
+    /// API path to trigger a build with parameters.
+    fn endpoint(&self) -> String {
+        format!("job/{}/buildWithParameters", self.job_name)
+    }
+
+    /// Parameters to send with the request.
+    fn params(&self) -> Option<Vec<(&str, &str)>> {
+        Some(
+            self.params
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jenkins_sdk::Endpoint;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_with_parameters() {
+        let params = json!({
+            "Changelist": "1234",
+            "Count": 4,
+            "Publish": true,
+            "Platforms": ["win64", "android"],
+            "Empty": null,
+        });
+        let trigger = BuildWithParameters::new("Folder/job/main", &params);
+
+        assert_eq!(
+            trigger.endpoint(),
+            "job/Folder/job/main/buildWithParameters"
+        );
+        let mut sent = trigger.params().unwrap();
+        sent.sort();
+        assert_eq!(
+            sent,
+            vec![
+                ("Changelist", "1234"),
+                ("Count", "4"),
+                ("Platforms", "win64,android"),
+                ("Publish", "true"),
+            ]
+        );
+    }
+}
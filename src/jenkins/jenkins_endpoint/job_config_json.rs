@@ -1,7 +1,9 @@
+use crate::jenkins::job_path::JobPath;
+
 /// Endpoint for get job definitions and bypass config.xml.
 pub struct JobConfigJson {
-    /// Name of the Jenkins job.
-    pub job_name: String,
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
 }
 
 impl jenkins_sdk::Endpoint for JobConfigJson {
@@ -13,8 +15,8 @@ impl jenkins_sdk::Endpoint for JobConfigJson {
     /// API path for get job definitions.
     fn endpoint(&self) -> String {
         format!(
-            "job/{}/api/json?tree=property[parameterDefinitions[name,type,description,defaultParameterValue[value],choices]]",
-            self.job_name
+            "{}/api/json?tree=property[parameterDefinitions[name,type,description,defaultParameterValue[value],choices]]",
+            self.job_path.to_url_path()
         )
     }
 }
@@ -1,7 +1,9 @@
+use crate::jenkins::job_path::JobPath;
+
 /// Endpoint for get config.xml about Jenkins job.
 pub struct JobConfigXML {
-    /// Name of the Jenkins job.
-    pub job_name: String,
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
 }
 
 impl jenkins_sdk::Endpoint for JobConfigXML {
@@ -12,6 +14,6 @@ impl jenkins_sdk::Endpoint for JobConfigXML {
 
     /// API path for get job config.
     fn endpoint(&self) -> String {
-        format!("job/{}/config.xml", self.job_name)
+        format!("{}/config.xml", self.job_path.to_url_path())
     }
 }
@@ -1,7 +1,9 @@
+use crate::jenkins::job_path::JobPath;
+
 /// Endpoint for retrieving information about Jenkins job.
 pub struct JobInfo {
-    /// Name of the Jenkins job.
-    pub job_name: String,
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
 
     pub count: Option<u32>,
 }
@@ -19,8 +21,9 @@ impl jenkins_sdk::Endpoint for JobInfo {
             None => "".to_string(),
         };
         format!(
-            "job/{}/api/json/?tree=builds[number]{}",
-            self.job_name, count_str
+            "{}/api/json/?tree=builds[number]{}",
+            self.job_path.to_url_path(),
+            count_str
         )
     }
 }
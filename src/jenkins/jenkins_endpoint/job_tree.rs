@@ -0,0 +1,56 @@
+/// Endpoint for retrieving the jobs and views of Jenkins, recursively.
+pub struct JobTree {
+    /// How many levels of folders and nested views to descend.
+    pub depth: u32,
+}
+
+impl JobTree {
+    fn get_job_fields(depth: u32) -> String {
+        let fields = "name,url,color,lastBuild[number]".to_string();
+        if depth == 0 {
+            fields
+        } else {
+            format!("{},jobs[{}]", fields, Self::get_job_fields(depth - 1))
+        }
+    }
+
+    fn get_view_fields(depth: u32) -> String {
+        let fields = format!("jobs[{}]", Self::get_job_fields(0));
+        if depth == 0 {
+            fields
+        } else {
+            format!("{},views[{}]", fields, Self::get_view_fields(depth - 1))
+        }
+    }
+}
+
+impl jenkins_sdk::Endpoint for JobTree {
+    /// HTTP method used (GET).
+    fn method(&self) -> &str {
+        "GET"
+    }
+
+    /// API path for retrieving the jobs and views.
+    fn endpoint(&self) -> String {
+        format!(
+            "api/json?tree=jobs[{}],views[{}]",
+            Self::get_job_fields(self.depth),
+            Self::get_view_fields(self.depth)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jenkins_sdk::Endpoint;
+
+    #[test]
+    fn test_job_tree_endpoint() {
+        assert_eq!(
+            JobTree { depth: 1 }.endpoint(),
+            "api/json?tree=jobs[name,url,color,lastBuild[number],jobs[name,url,color,lastBuild[number]]],\
+            views[jobs[name,url,color,lastBuild[number]],views[jobs[name,url,color,lastBuild[number]]]]"
+        );
+    }
+}
@@ -1,7 +1,14 @@
+pub mod build_with_parameters;
 pub mod get_crumb;
 pub mod job_config_json;
 pub mod job_config_xml;
 pub mod job_info;
+pub mod job_tree;
+pub mod node_log;
 pub mod ping;
+pub mod queue_item;
+pub mod run_describe;
 pub mod run_info;
 pub mod run_log;
+pub mod stage_describe;
+pub mod who_am_i;
@@ -0,0 +1,30 @@
+use crate::jenkins::job_path::JobPath;
+
+/// Endpoint for retrieving the log of a Jenkins pipeline flow node.
+pub struct NodeLog {
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
+
+    /// Build number of the Jenkins run.
+    pub build_number: u32,
+
+    /// Flow node id of the step.
+    pub node_id: String,
+}
+
+impl jenkins_sdk::Endpoint for NodeLog {
+    /// HTTP method used (GET).
+    fn method(&self) -> &str {
+        "GET"
+    }
+
+    /// API path for retrieving flow node log by workflow api.
+    fn endpoint(&self) -> String {
+        format!(
+            "{}/{}/execution/node/{}/wfapi/log",
+            self.job_path.to_url_path(),
+            self.build_number,
+            self.node_id
+        )
+    }
+}
@@ -0,0 +1,36 @@
+use serde::Deserialize;
+
+/// an item of the build queue, like the one a triggered build returns in `Location`.
+pub struct QueueItem {
+    /// id of the queue item.
+    pub id: u64,
+}
+
+impl jenkins_sdk::Endpoint for QueueItem {
+    /// HTTP method used (GET).
+    fn method(&self) -> &str {
+        "GET"
+    }
+
+    /// API path for the queue item.
+    fn endpoint(&self) -> String {
+        format!(
+            "queue/item/{}/api/json?tree=cancelled,executable[number]",
+            self.id
+        )
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueueItemResult {
+    #[serde(default)]
+    pub cancelled: bool,
+
+    /// the run, once the item leaves the queue.
+    pub executable: Option<QueueExecutable>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueueExecutable {
+    pub number: u32,
+}
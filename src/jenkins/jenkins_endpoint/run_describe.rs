@@ -0,0 +1,26 @@
+use crate::jenkins::job_path::JobPath;
+
+/// Endpoint for retrieving the stage view of Jenkins pipeline run.
+pub struct RunDescribe {
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
+
+    /// Build number of the Jenkins run.
+    pub build_number: u32,
+}
+
+impl jenkins_sdk::Endpoint for RunDescribe {
+    /// HTTP method used (GET).
+    fn method(&self) -> &str {
+        "GET"
+    }
+
+    /// API path for retrieving pipeline stages by workflow api.
+    fn endpoint(&self) -> String {
+        format!(
+            "{}/{}/wfapi/describe",
+            self.job_path.to_url_path(),
+            self.build_number
+        )
+    }
+}
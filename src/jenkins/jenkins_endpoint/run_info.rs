@@ -1,7 +1,9 @@
+use crate::jenkins::job_path::JobPath;
+
 /// Endpoint for retrieving information about Jenkins run.
 pub struct RunInfo {
-    /// Name of the Jenkins job.
-    pub job_name: String,
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
 
     /// Build number of the Jenkins run.
     pub build_number: u32,
@@ -16,8 +18,8 @@ impl jenkins_sdk::Endpoint for RunInfo {
     /// API path for retrieving job information.
     fn endpoint(&self) -> String {
         format!(
-            "job/{}/{}/api/json?tree=number,actions[causes[userId],parameters[name,value],buildMetadata[description,name,stringValue,type]],result",
-            self.job_name, self.build_number
+            "{}/{}/api/json?tree=number,actions[causes[userId],parameters[name,value,jobName,number],buildMetadata[description,name,stringValue,type]],result,timestamp,duration,estimatedDuration",
+            self.job_path.to_url_path(), self.build_number
         )
     }
 }
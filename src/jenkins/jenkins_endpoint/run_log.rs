@@ -1,7 +1,9 @@
+use crate::jenkins::job_path::JobPath;
+
 /// Endpoint for retrieving information about Jenkins run.
 pub struct RunLog {
-    /// Name of the Jenkins job.
-    pub job_name: String,
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
 
     /// Build number of the Jenkins run.
     pub build_number: u32,
@@ -15,6 +17,10 @@ impl jenkins_sdk::Endpoint for RunLog {
 
     /// API path for retrieving job information.
     fn endpoint(&self) -> String {
-        format!("job/{}/{}/logText/progressiveText?start=0", self.job_name, self.build_number)
+        format!(
+            "{}/{}/logText/progressiveText?start=0",
+            self.job_path.to_url_path(),
+            self.build_number
+        )
     }
 }
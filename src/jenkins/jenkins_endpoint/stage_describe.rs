@@ -0,0 +1,30 @@
+use crate::jenkins::job_path::JobPath;
+
+/// Endpoint for retrieving the flow nodes of a Jenkins pipeline stage.
+pub struct StageDescribe {
+    /// Path of the Jenkins job.
+    pub job_path: JobPath,
+
+    /// Build number of the Jenkins run.
+    pub build_number: u32,
+
+    /// Flow node id of the stage.
+    pub node_id: String,
+}
+
+impl jenkins_sdk::Endpoint for StageDescribe {
+    /// HTTP method used (GET).
+    fn method(&self) -> &str {
+        "GET"
+    }
+
+    /// API path for retrieving stage flow nodes by workflow api.
+    fn endpoint(&self) -> String {
+        format!(
+            "{}/{}/execution/node/{}/wfapi/describe",
+            self.job_path.to_url_path(),
+            self.build_number,
+            self.node_id
+        )
+    }
+}
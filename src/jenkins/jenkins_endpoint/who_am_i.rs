@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+/// the user of the credentials. anonymous if the session is expired.
+pub struct WhoAmI;
+
+impl jenkins_sdk::Endpoint for WhoAmI {
+    /// HTTP method used (GET).
+    fn method(&self) -> &str {
+        "GET"
+    }
+
+    /// API path for the current user.
+    fn endpoint(&self) -> String {
+        "whoAmI/api/json".to_string()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WhoAmIResult {
+    #[serde(default)]
+    pub anonymous: bool,
+    #[serde(default)]
+    pub name: String,
+}
@@ -1,4 +1,7 @@
-use crate::jenkins::build::{ToVfpJobBuildParam, VfpJobBuildParam};
+use crate::constant::util::strip_html_tags;
+use crate::jenkins::build::{
+    ToVfpJobBuildParam, VfpBuildParamKind, VfpBuildParamSpec, VfpJobBuildParam,
+};
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -32,15 +35,24 @@ impl ToVfpJobBuildParam for JobDefinitionJson {
         let params: HashMap<String, Value> = self
             .get_parameters()
             .into_iter()
-            .filter(|item| !matches!(item, ParameterDefinition::Unknown))
-            .map(|param| {
+            .filter_map(|param| {
                 let necessary = param.is_necessary();
                 match param {
                     ParameterDefinition::String {
                         name,
                         default_value,
                         ..
-                    } => (
+                    }
+                    | ParameterDefinition::Text {
+                        name,
+                        default_value,
+                        ..
+                    }
+                    | ParameterDefinition::Credentials {
+                        name,
+                        default_value,
+                        ..
+                    } => Some((
                         name.clone(),
                         Value::String(
                             default_value
@@ -48,12 +60,12 @@ impl ToVfpJobBuildParam for JobDefinitionJson {
                                 .map(|item| item.value.clone())
                                 .unwrap_or_default(),
                         ),
-                    ),
+                    )),
                     ParameterDefinition::Bool {
                         name,
                         default_value,
                         ..
-                    } => (
+                    } => Some((
                         name.clone(),
                         Value::Bool(
                             default_value
@@ -61,7 +73,7 @@ impl ToVfpJobBuildParam for JobDefinitionJson {
                                 .map(|item| item.value)
                                 .unwrap_or_default(),
                         ),
-                    ),
+                    )),
                     ParameterDefinition::Choice {
                         name,
                         choices,
@@ -79,19 +91,115 @@ impl ToVfpJobBuildParam for JobDefinitionJson {
                                 .map(|item| &item.value)
                                 .or_else(|| choices.iter().next())
                         };
-                        (
+                        Some((
                             name.clone(),
                             Value::String(default_choice.cloned().unwrap_or_default()),
-                        )
-                    }
-                    ParameterDefinition::Unknown => {
-                        unreachable!()
+                        ))
                     }
+                    ParameterDefinition::ExtendedChoice {
+                        name,
+                        default_value,
+                        ..
+                    } => default_value
+                        .as_ref()
+                        .map(|item| (name.clone(), Value::String(item.value.clone()))),
+                    // left to the defaults of Jenkins, which can not be known or sent as is.
+                    ParameterDefinition::Password { .. }
+                    | ParameterDefinition::Run { .. }
+                    | ParameterDefinition::File { .. }
+                    | ParameterDefinition::ActiveChoice { .. }
+                    | ParameterDefinition::Unknown => None,
                 }
             })
             .collect();
         VfpJobBuildParam::new_with_override_recommend_param(params)
     }
+
+    fn to_vfp_build_param_specs(&self) -> Vec<VfpBuildParamSpec> {
+        self.get_parameters()
+            .into_iter()
+            .filter_map(|param| {
+                let kind = match param {
+                    ParameterDefinition::String { .. } => VfpBuildParamKind::String,
+                    ParameterDefinition::Text { .. } => VfpBuildParamKind::Text,
+                    ParameterDefinition::Password { .. } => VfpBuildParamKind::Password,
+                    ParameterDefinition::Bool { .. } => VfpBuildParamKind::Bool,
+                    ParameterDefinition::Choice { choices, .. } => {
+                        VfpBuildParamKind::Choice(choices.clone())
+                    }
+                    ParameterDefinition::ExtendedChoice {
+                        choice_type,
+                        value,
+                        delimiter,
+                        ..
+                    } => get_extended_choice_kind(choice_type, value, delimiter),
+                    ParameterDefinition::Run { project_name, .. } => VfpBuildParamKind::Run {
+                        project_name: project_name.clone(),
+                    },
+                    ParameterDefinition::File { .. } => VfpBuildParamKind::File,
+                    ParameterDefinition::Credentials { .. } => VfpBuildParamKind::Credentials,
+                    ParameterDefinition::ActiveChoice {
+                        referenced_parameters,
+                        ..
+                    } => VfpBuildParamKind::Dynamic {
+                        referenced_params: split_list(referenced_parameters.as_deref(), ","),
+                    },
+                    ParameterDefinition::Unknown => return None,
+                };
+
+                Some(VfpBuildParamSpec {
+                    name: param.get_name()?.to_string(),
+                    description: param
+                        .get_description()
+                        .map(strip_html_tags)
+                        .filter(|desc| !desc.is_empty()),
+                    kind,
+                    necessary: param.is_necessary(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// # get extended choice kind
+///
+/// the kind of an Extended Choice param by its `type`.
+/// it falls back to a string if the choices are provided by a property file or script.
+pub(crate) fn get_extended_choice_kind(
+    choice_type: &str,
+    value: &str,
+    delimiter: &str,
+) -> VfpBuildParamKind {
+    let choices = split_list(Some(value), ",");
+    if choices.is_empty() {
+        return VfpBuildParamKind::String;
+    }
+
+    match choice_type {
+        "PT_SINGLE_SELECT" | "PT_RADIO" => VfpBuildParamKind::Choice(choices),
+        "PT_CHECKBOX" | "PT_MULTI_SELECT" => VfpBuildParamKind::MultiChoice {
+            choices,
+            delimiter: if delimiter.is_empty() {
+                ",".to_string()
+            } else {
+                delimiter.to_string()
+            },
+        },
+        _ => VfpBuildParamKind::String,
+    }
+}
+
+pub(crate) fn split_list(value: Option<&str>, delimiter: &str) -> Vec<String> {
+    value
+        .map(|value| {
+            value
+                .split(delimiter)
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 #[derive(Deserialize, Debug)]
@@ -120,6 +228,26 @@ pub enum ParameterDefinition {
         default_value: Option<Val<String>>,
     },
 
+    #[serde(rename = "hudson.model.TextParameterDefinition")]
+    Text {
+        name: String,
+
+        #[serde(default)]
+        description: Option<String>,
+
+        #[serde(default, rename = "defaultParameterValue")]
+        default_value: Option<Val<String>>,
+    },
+
+    /// the default value is never exposed.
+    #[serde(rename = "hudson.model.PasswordParameterDefinition")]
+    Password {
+        name: String,
+
+        #[serde(default)]
+        description: Option<String>,
+    },
+
     #[serde(rename = "hudson.model.BooleanParameterDefinition")]
     Bool {
         name: String,
@@ -145,20 +273,121 @@ pub enum ParameterDefinition {
         choices: Vec<String>,
     },
 
+    #[serde(
+        rename = "com.cwctravel.hudson.plugins.extended_choice_parameter.ExtendedChoiceParameterDefinition"
+    )]
+    ExtendedChoice {
+        name: String,
+
+        #[serde(default)]
+        description: Option<String>,
+
+        #[serde(default, rename = "defaultParameterValue")]
+        default_value: Option<Val<String>>,
+
+        /// PT_SINGLE_SELECT, PT_MULTI_SELECT, PT_CHECKBOX, PT_RADIO, PT_TEXTBOX...
+        #[serde(default, rename = "type")]
+        choice_type: String,
+
+        /// choices separated by comma.
+        #[serde(default)]
+        value: String,
+
+        #[serde(default, rename = "multiSelectDelimiter")]
+        delimiter: String,
+    },
+
+    #[serde(rename = "hudson.model.RunParameterDefinition")]
+    Run {
+        name: String,
+
+        #[serde(default)]
+        description: Option<String>,
+
+        #[serde(default, rename = "projectName")]
+        project_name: String,
+    },
+
+    #[serde(
+        rename = "hudson.model.FileParameterDefinition",
+        alias = "io.jenkins.plugins.file_parameters.StashedFileParameterDefinition",
+        alias = "io.jenkins.plugins.file_parameters.Base64FileParameterDefinition"
+    )]
+    File {
+        name: String,
+
+        #[serde(default)]
+        description: Option<String>,
+    },
+
+    #[serde(rename = "com.cloudbees.plugins.credentials.CredentialsParameterDefinition")]
+    Credentials {
+        name: String,
+
+        #[serde(default)]
+        description: Option<String>,
+
+        #[serde(default, rename = "defaultParameterValue")]
+        default_value: Option<Val<String>>,
+    },
+
+    /// Active Choices, including the cascade and the dynamic reference ones.
+    #[serde(
+        rename = "org.biouno.unochoice.ChoiceParameter",
+        alias = "org.biouno.unochoice.CascadeChoiceParameter",
+        alias = "org.biouno.unochoice.DynamicReferenceParameter"
+    )]
+    ActiveChoice {
+        name: String,
+
+        #[serde(default)]
+        description: Option<String>,
+
+        /// names of the params it depends on, separated by comma.
+        #[serde(default, rename = "referencedParameters")]
+        referenced_parameters: Option<String>,
+    },
+
     #[serde(other)]
     Unknown,
 }
 
 impl ParameterDefinition {
+    pub fn get_name(&self) -> Option<&str> {
+        match self {
+            ParameterDefinition::String { name, .. }
+            | ParameterDefinition::Text { name, .. }
+            | ParameterDefinition::Password { name, .. }
+            | ParameterDefinition::Bool { name, .. }
+            | ParameterDefinition::Choice { name, .. }
+            | ParameterDefinition::ExtendedChoice { name, .. }
+            | ParameterDefinition::Run { name, .. }
+            | ParameterDefinition::File { name, .. }
+            | ParameterDefinition::Credentials { name, .. }
+            | ParameterDefinition::ActiveChoice { name, .. } => Some(name),
+            ParameterDefinition::Unknown => None,
+        }
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        match self {
+            ParameterDefinition::String { description, .. }
+            | ParameterDefinition::Text { description, .. }
+            | ParameterDefinition::Password { description, .. }
+            | ParameterDefinition::Bool { description, .. }
+            | ParameterDefinition::Choice { description, .. }
+            | ParameterDefinition::ExtendedChoice { description, .. }
+            | ParameterDefinition::Run { description, .. }
+            | ParameterDefinition::File { description, .. }
+            | ParameterDefinition::Credentials { description, .. }
+            | ParameterDefinition::ActiveChoice { description, .. } => description.as_deref(),
+            ParameterDefinition::Unknown => None,
+        }
+    }
+
     pub fn is_necessary(&self) -> bool {
-        let desc = match self {
-            ParameterDefinition::String { description, .. } => description,
-            ParameterDefinition::Bool { description, .. } => description,
-            ParameterDefinition::Choice { description, .. } => description,
-            ParameterDefinition::Unknown => return false,
-        };
-
-        desc.as_ref().is_some_and(|desc| desc.contains("color:red"))
+        self.get_description()
+            .is_some_and(|desc| desc.contains("color:red"))
     }
 }
 
@@ -258,6 +487,134 @@ mod tests {
     }
   ]
 }"#;
-        let _: JobDefinitionJson = serde_json::from_str(content).unwrap();
+        let definition: JobDefinitionJson = serde_json::from_str(content).unwrap();
+
+        let specs = definition.to_vfp_build_param_specs();
+        assert_eq!(specs.len(), 5);
+        assert_eq!(
+            specs[2],
+            VfpBuildParamSpec {
+                name: "TestType".to_string(),
+                description: Some("Please select TestType before build.".to_string()),
+                kind: VfpBuildParamKind::Choice(vec![
+                    "".to_string(),
+                    "Some".to_string(),
+                    "Other".to_string()
+                ]),
+                necessary: true,
+            }
+        );
+        assert_eq!(specs[3].kind, VfpBuildParamKind::Bool);
+        assert_eq!(specs[3].description, None);
+    }
+
+    #[test]
+    fn test_deserialize_more_parameter_types() {
+        let content = r#"{
+  "property": [
+    {
+      "_class": "hudson.model.ParametersDefinitionProperty",
+      "parameterDefinitions": [
+        {
+          "_class": "hudson.model.TextParameterDefinition",
+          "defaultParameterValue": {"_class": "hudson.model.StringParameterValue", "value": "line1\nline2"},
+          "description": "",
+          "name": "ReleaseNote"
+        },
+        {
+          "_class": "hudson.model.PasswordParameterDefinition",
+          "defaultParameterValue": {"_class": "hudson.model.PasswordParameterValue"},
+          "description": "",
+          "name": "SignPassword"
+        },
+        {
+          "_class": "hudson.model.RunParameterDefinition",
+          "description": "",
+          "name": "BaseRun",
+          "projectName": "Some.Job"
+        },
+        {
+          "_class": "io.jenkins.plugins.file_parameters.StashedFileParameterDefinition",
+          "description": "",
+          "name": "Patch"
+        },
+        {
+          "_class": "com.cloudbees.plugins.credentials.CredentialsParameterDefinition",
+          "defaultParameterValue": {"_class": "com.cloudbees.plugins.credentials.CredentialsParameterValue", "value": "deploy-key"},
+          "description": "",
+          "name": "DeployKey"
+        },
+        {
+          "_class": "com.cwctravel.hudson.plugins.extended_choice_parameter.ExtendedChoiceParameterDefinition",
+          "defaultParameterValue": {"_class": "hudson.model.StringParameterValue", "value": "Android"},
+          "description": "",
+          "name": "Platforms",
+          "type": "PT_CHECKBOX",
+          "value": "Android,iOS,Windows",
+          "multiSelectDelimiter": ","
+        },
+        {
+          "_class": "org.biouno.unochoice.CascadeChoiceParameter",
+          "description": "",
+          "name": "Branch",
+          "choiceType": "PT_SINGLE_SELECT",
+          "referencedParameters": "Platforms"
+        },
+        {
+          "_class": "some.plugin.UnsupportedParameterDefinition",
+          "name": "Unsupported"
+        }
+      ]
+    }
+  ]
+}"#;
+        let definition: JobDefinitionJson = serde_json::from_str(content).unwrap();
+
+        let kinds: Vec<VfpBuildParamKind> = definition
+            .to_vfp_build_param_specs()
+            .into_iter()
+            .map(|spec| spec.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                VfpBuildParamKind::Text,
+                VfpBuildParamKind::Password,
+                VfpBuildParamKind::Run {
+                    project_name: "Some.Job".to_string()
+                },
+                VfpBuildParamKind::File,
+                VfpBuildParamKind::Credentials,
+                VfpBuildParamKind::MultiChoice {
+                    choices: vec![
+                        "Android".to_string(),
+                        "iOS".to_string(),
+                        "Windows".to_string()
+                    ],
+                    delimiter: ",".to_string(),
+                },
+                VfpBuildParamKind::Dynamic {
+                    referenced_params: vec!["Platforms".to_string()]
+                },
+            ]
+        );
+
+        let params = definition.to_vfp_job_build_param().params;
+        assert_eq!(
+            params.get("ReleaseNote"),
+            Some(&Value::String("line1\nline2".to_string()))
+        );
+        assert_eq!(
+            params.get("DeployKey"),
+            Some(&Value::String("deploy-key".to_string()))
+        );
+        assert_eq!(
+            params.get("Platforms"),
+            Some(&Value::String("Android".to_string()))
+        );
+        assert!(!params.contains_key("SignPassword"));
+        assert!(!params.contains_key("BaseRun"));
+        assert!(!params.contains_key("Patch"));
+        assert!(!params.contains_key("Branch"));
     }
 }
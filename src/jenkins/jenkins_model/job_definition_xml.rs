@@ -1,4 +1,7 @@
-use crate::jenkins::build::{ToVfpJobBuildParam, VfpJobBuildParam};
+use crate::jenkins::build::{
+    ToVfpJobBuildParam, VfpBuildParamKind, VfpBuildParamSpec, VfpJobBuildParam,
+};
+use crate::jenkins::jenkins_model::job_definition_json::{get_extended_choice_kind, split_list};
 use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -25,22 +28,32 @@ impl ToVfpJobBuildParam for JobDefinitionXml {
         let params: HashMap<String, Value> = self
             .get_parameters()
             .iter()
-            .map(|param| {
+            .filter_map(|param| {
                 let necessary = param.is_necessary();
                 match param {
                     ParameterDefinition::String {
                         name,
                         default_value,
                         ..
-                    } => (
+                    }
+                    | ParameterDefinition::Text {
+                        name,
+                        default_value,
+                        ..
+                    }
+                    | ParameterDefinition::Credentials {
+                        name,
+                        default_value,
+                        ..
+                    } => Some((
                         name.clone(),
                         Value::String(default_value.clone().unwrap_or_default()),
-                    ),
+                    )),
                     ParameterDefinition::Bool {
                         name,
                         default_value,
                         ..
-                    } => (name.clone(), Value::Bool(*default_value)),
+                    } => Some((name.clone(), Value::Bool(*default_value))),
                     ParameterDefinition::Choice { name, choices, .. } => {
                         let default_choice = if necessary {
                             choices
@@ -50,17 +63,79 @@ impl ToVfpJobBuildParam for JobDefinitionXml {
                         } else {
                             choices.get_all_choices().into_iter().next()
                         };
-                        (
+                        Some((
                             name.clone(),
                             Value::String(default_choice.unwrap_or_default()),
-                        )
+                        ))
                     }
+                    ParameterDefinition::ExtendedChoice {
+                        name,
+                        default_value,
+                        ..
+                    } => default_value
+                        .clone()
+                        .map(|value| (name.clone(), Value::String(value))),
+                    // left to the defaults of Jenkins, which can not be known or sent as is.
+                    ParameterDefinition::Password { .. }
+                    | ParameterDefinition::Run { .. }
+                    | ParameterDefinition::File { .. }
+                    | ParameterDefinition::ActiveChoice { .. }
+                    | ParameterDefinition::Unknown => None,
                 }
             })
             .collect();
 
         VfpJobBuildParam::new_with_override_recommend_param(params)
     }
+
+    fn to_vfp_build_param_specs(&self) -> Vec<VfpBuildParamSpec> {
+        self.get_parameters()
+            .into_iter()
+            .filter_map(|param| {
+                let kind = match param {
+                    ParameterDefinition::String { .. } => VfpBuildParamKind::String,
+                    ParameterDefinition::Text { .. } => VfpBuildParamKind::Text,
+                    ParameterDefinition::Password { .. } => VfpBuildParamKind::Password,
+                    ParameterDefinition::Bool { .. } => VfpBuildParamKind::Bool,
+                    ParameterDefinition::Choice { choices, .. } => {
+                        VfpBuildParamKind::Choice(choices.get_all_choices())
+                    }
+                    ParameterDefinition::ExtendedChoice {
+                        choice_type,
+                        value,
+                        delimiter,
+                        ..
+                    } => get_extended_choice_kind(
+                        choice_type.as_deref().unwrap_or_default(),
+                        value.as_deref().unwrap_or_default(),
+                        delimiter.as_deref().unwrap_or_default(),
+                    ),
+                    ParameterDefinition::Run { project_name, .. } => VfpBuildParamKind::Run {
+                        project_name: project_name.clone().unwrap_or_default(),
+                    },
+                    ParameterDefinition::File { .. } => VfpBuildParamKind::File,
+                    ParameterDefinition::Credentials { .. } => VfpBuildParamKind::Credentials,
+                    ParameterDefinition::ActiveChoice {
+                        referenced_parameters,
+                        ..
+                    } => VfpBuildParamKind::Dynamic {
+                        referenced_params: split_list(referenced_parameters.as_deref(), ","),
+                    },
+                    ParameterDefinition::Unknown => return None,
+                };
+
+                Some(VfpBuildParamSpec {
+                    name: param.get_name()?.to_string(),
+                    description: param
+                        .get_description()
+                        .map(|desc| desc.to_plain_text())
+                        .filter(|desc| !desc.is_empty()),
+                    kind,
+                    necessary: param.is_necessary(),
+                })
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -93,6 +168,19 @@ pub enum ParameterDefinition {
         #[serde(rename = "defaultValue")]
         default_value: Option<String>,
     },
+    #[serde(rename = "hudson.model.TextParameterDefinition")]
+    Text {
+        name: String,
+        description: Option<XmlRichText>,
+        #[serde(rename = "defaultValue")]
+        default_value: Option<String>,
+    },
+    /// the default value is encrypted.
+    #[serde(rename = "hudson.model.PasswordParameterDefinition")]
+    Password {
+        name: String,
+        description: Option<XmlRichText>,
+    },
     #[serde(rename = "hudson.model.BooleanParameterDefinition")]
     Bool {
         name: String,
@@ -106,22 +194,97 @@ pub enum ParameterDefinition {
         description: Option<XmlRichText>,
         choices: ChoiceParameterWrapper,
     },
+    /// underscores in the package name are escaped as double by XStream.
+    #[serde(
+        rename = "com.cwctravel.hudson.plugins.extended__choice__parameter.ExtendedChoiceParameterDefinition"
+    )]
+    ExtendedChoice {
+        name: String,
+        description: Option<XmlRichText>,
+        #[serde(rename = "type")]
+        choice_type: Option<String>,
+        value: Option<String>,
+        #[serde(rename = "defaultValue")]
+        default_value: Option<String>,
+        #[serde(rename = "multiSelectDelimiter")]
+        delimiter: Option<String>,
+    },
+    #[serde(rename = "hudson.model.RunParameterDefinition")]
+    Run {
+        name: String,
+        description: Option<XmlRichText>,
+        #[serde(rename = "projectName")]
+        project_name: Option<String>,
+    },
+    #[serde(
+        rename = "hudson.model.FileParameterDefinition",
+        alias = "io.jenkins.plugins.file__parameters.StashedFileParameterDefinition",
+        alias = "io.jenkins.plugins.file__parameters.Base64FileParameterDefinition"
+    )]
+    File {
+        name: String,
+        description: Option<XmlRichText>,
+    },
+    #[serde(rename = "com.cloudbees.plugins.credentials.CredentialsParameterDefinition")]
+    Credentials {
+        name: String,
+        description: Option<XmlRichText>,
+        #[serde(rename = "defaultValue")]
+        default_value: Option<String>,
+    },
+    /// Active Choices, including the cascade and the dynamic reference ones.
+    #[serde(
+        rename = "org.biouno.unochoice.ChoiceParameter",
+        alias = "org.biouno.unochoice.CascadeChoiceParameter",
+        alias = "org.biouno.unochoice.DynamicReferenceParameter"
+    )]
+    ActiveChoice {
+        name: String,
+        description: Option<XmlRichText>,
+        #[serde(rename = "referencedParameters")]
+        referenced_parameters: Option<String>,
+    },
+    #[serde(other)]
+    Unknown,
 }
 
 impl ParameterDefinition {
-    pub fn is_necessary(&self) -> bool {
+    pub fn get_name(&self) -> Option<&str> {
         match self {
-            ParameterDefinition::String { description, .. } => {
-                description.as_ref().is_some_and(|desc| desc.is_necessary())
-            }
-            ParameterDefinition::Bool { description, .. } => {
-                description.as_ref().is_some_and(|desc| desc.is_necessary())
-            }
-            ParameterDefinition::Choice { description, .. } => {
-                description.as_ref().is_some_and(|desc| desc.is_necessary())
-            }
+            ParameterDefinition::String { name, .. }
+            | ParameterDefinition::Text { name, .. }
+            | ParameterDefinition::Password { name, .. }
+            | ParameterDefinition::Bool { name, .. }
+            | ParameterDefinition::Choice { name, .. }
+            | ParameterDefinition::ExtendedChoice { name, .. }
+            | ParameterDefinition::Run { name, .. }
+            | ParameterDefinition::File { name, .. }
+            | ParameterDefinition::Credentials { name, .. }
+            | ParameterDefinition::ActiveChoice { name, .. } => Some(name),
+            ParameterDefinition::Unknown => None,
         }
     }
+
+    pub fn get_description(&self) -> Option<&XmlRichText> {
+        match self {
+            ParameterDefinition::String { description, .. }
+            | ParameterDefinition::Text { description, .. }
+            | ParameterDefinition::Password { description, .. }
+            | ParameterDefinition::Bool { description, .. }
+            | ParameterDefinition::Choice { description, .. }
+            | ParameterDefinition::ExtendedChoice { description, .. }
+            | ParameterDefinition::Run { description, .. }
+            | ParameterDefinition::File { description, .. }
+            | ParameterDefinition::Credentials { description, .. }
+            | ParameterDefinition::ActiveChoice { description, .. } => description.as_ref(),
+            ParameterDefinition::Unknown => None,
+        }
+    }
+
+    pub fn is_necessary(&self) -> bool {
+        self.get_description()
+            .is_some_and(|desc| desc.is_necessary())
+    }
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -184,6 +347,18 @@ impl XmlRichText {
             }
         })
     }
+
+    pub fn to_plain_text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|item| match item {
+                XmlRichTextElem::Content(text) => Some(text.trim()),
+                XmlRichTextElem::Span { content, .. } => content.as_deref().map(str::trim),
+            })
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 impl Display for XmlRichText {
@@ -237,6 +412,7 @@ impl Display for XmlRichTextElem {
 mod tests {
     use crate::jenkins::jenkins_model::job_definition_xml::*;
     use serde::Deserialize;
+    use serde_json::Value;
 
     #[test]
     fn xml_de_lab() {
@@ -427,6 +603,16 @@ mod tests {
                         }
                     }]
                 );
+
+                let specs = result.to_vfp_build_param_specs();
+                assert_eq!(
+                    specs[0].description.as_deref(),
+                    Some("Some desc about choice type")
+                );
+                assert_eq!(
+                    specs[0].kind,
+                    VfpBuildParamKind::Choice(vec!["Content".to_string(), "Other".to_string()])
+                );
             }
             Err(e) => {
                 println!("Error: {:?}", e);
@@ -500,6 +686,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_more_parameter_types() {
+        let content = r##"<flow-definition plugin="workflow-job@1385.vb_58b_86ea_fff1">
+    <properties>
+        <hudson.model.ParametersDefinitionProperty>
+            <parameterDefinitions>
+                <hudson.model.TextParameterDefinition>
+                    <name>ReleaseNote</name>
+                    <defaultValue>some note</defaultValue>
+                    <trim>false</trim>
+                </hudson.model.TextParameterDefinition>
+                <hudson.model.PasswordParameterDefinition>
+                    <name>SignPassword</name>
+                    <defaultValue>{AQAAABAAAAAQ}</defaultValue>
+                </hudson.model.PasswordParameterDefinition>
+                <hudson.model.RunParameterDefinition>
+                    <name>BaseRun</name>
+                    <projectName>Some.Job</projectName>
+                    <filter>SUCCESSFUL</filter>
+                </hudson.model.RunParameterDefinition>
+                <io.jenkins.plugins.file__parameters.StashedFileParameterDefinition plugin="file-parameters@339.v4b_cc83e11455">
+                    <name>Patch</name>
+                </io.jenkins.plugins.file__parameters.StashedFileParameterDefinition>
+                <com.cloudbees.plugins.credentials.CredentialsParameterDefinition plugin="credentials@1405.vb_cda_74a_f8974">
+                    <name>DeployKey</name>
+                    <defaultValue>deploy-key</defaultValue>
+                    <credentialType>com.cloudbees.plugins.credentials.common.StandardCredentials</credentialType>
+                    <required>false</required>
+                </com.cloudbees.plugins.credentials.CredentialsParameterDefinition>
+                <com.cwctravel.hudson.plugins.extended__choice__parameter.ExtendedChoiceParameterDefinition plugin="extended-choice-parameter@382.v5697b_32134e8">
+                    <name>Platforms</name>
+                    <quoteValue>false</quoteValue>
+                    <visibleItemCount>3</visibleItemCount>
+                    <type>PT_CHECKBOX</type>
+                    <value>Android,iOS,Windows</value>
+                    <defaultValue>Android</defaultValue>
+                    <multiSelectDelimiter>,</multiSelectDelimiter>
+                </com.cwctravel.hudson.plugins.extended__choice__parameter.ExtendedChoiceParameterDefinition>
+                <org.biouno.unochoice.CascadeChoiceParameter plugin="uno-choice@2.8.3">
+                    <name>Branch</name>
+                    <randomName>choice-parameter-1</randomName>
+                    <script class="org.biouno.unochoice.model.GroovyScript">
+                        <secureScript plugin="script-security@1369.v9b_98a_4e95b_2d">
+                            <script>return ["main"]</script>
+                            <sandbox>true</sandbox>
+                        </secureScript>
+                    </script>
+                    <projectName>Some.Job</projectName>
+                    <choiceType>PT_SINGLE_SELECT</choiceType>
+                    <referencedParameters>Platforms</referencedParameters>
+                    <filterable>false</filterable>
+                </org.biouno.unochoice.CascadeChoiceParameter>
+                <some.plugin.UnsupportedParameterDefinition>
+                    <name>Unsupported</name>
+                </some.plugin.UnsupportedParameterDefinition>
+            </parameterDefinitions>
+        </hudson.model.ParametersDefinitionProperty>
+    </properties>
+</flow-definition>"##;
+
+        let definition = quick_xml::de::from_str::<JobDefinitionXml>(content).unwrap();
+
+        let kinds: Vec<VfpBuildParamKind> = definition
+            .to_vfp_build_param_specs()
+            .into_iter()
+            .map(|spec| spec.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                VfpBuildParamKind::Text,
+                VfpBuildParamKind::Password,
+                VfpBuildParamKind::Run {
+                    project_name: "Some.Job".to_string()
+                },
+                VfpBuildParamKind::File,
+                VfpBuildParamKind::Credentials,
+                VfpBuildParamKind::MultiChoice {
+                    choices: vec![
+                        "Android".to_string(),
+                        "iOS".to_string(),
+                        "Windows".to_string()
+                    ],
+                    delimiter: ",".to_string(),
+                },
+                VfpBuildParamKind::Dynamic {
+                    referenced_params: vec!["Platforms".to_string()]
+                },
+            ]
+        );
+
+        let params = definition.to_vfp_job_build_param().params;
+        assert_eq!(
+            params.get("ReleaseNote"),
+            Some(&Value::String("some note".to_string()))
+        );
+        assert_eq!(
+            params.get("Platforms"),
+            Some(&Value::String("Android".to_string()))
+        );
+        assert!(!params.contains_key("SignPassword"));
+        assert!(!params.contains_key("Branch"));
+    }
+
     #[test]
     fn test_deserialize_config_xml_with_empty_description() {
         let content = r##"<flow-definition plugin="workflow-job@1385.vb_58b_86ea_fff1">
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+/// # JobTree
+///
+/// Jobs and views of the Jenkins root, with the nested ones of folders and views.
+#[derive(Deserialize, Debug, Default)]
+pub struct JobTree {
+    #[serde(default)]
+    pub jobs: Vec<JobTreeItem>,
+
+    #[serde(default)]
+    pub views: Vec<ViewTreeItem>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JobTreeItem {
+    pub name: String,
+
+    pub url: String,
+
+    /// status color of the last build, like `blue` or `red_anime`. None for folders.
+    pub color: Option<String>,
+
+    #[serde(rename = "lastBuild")]
+    pub last_build: Option<JobTreeLastBuild>,
+
+    /// nested jobs of a folder or branches of a multibranch pipeline.
+    pub jobs: Option<Vec<JobTreeItem>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JobTreeLastBuild {
+    pub number: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ViewTreeItem {
+    #[serde(default)]
+    pub jobs: Vec<JobTreeItem>,
+
+    /// nested views of a nested view.
+    #[serde(default)]
+    pub views: Vec<ViewTreeItem>,
+}
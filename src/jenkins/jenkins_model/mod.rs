@@ -2,13 +2,19 @@ mod cause;
 pub mod crumb;
 pub mod job_definition_json;
 pub mod job_definition_xml;
+pub mod job_tree;
 pub mod parameters_action;
 pub mod reasoned_run_status;
+pub mod run_progress;
 pub mod run_status;
 pub mod shelves;
+pub mod stage_status;
 pub mod user_id_cause;
 pub mod workflow_action;
 pub mod workflow_build;
 mod workflow_build_metadata;
 pub mod workflow_builds;
+pub mod workflow_describe;
+pub mod workflow_node_log;
 pub mod workflow_run;
+pub mod workflow_stage_describe;
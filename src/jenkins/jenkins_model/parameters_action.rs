@@ -4,8 +4,32 @@ use serde::Deserialize;
 #[serde(tag = "_class")]
 #[allow(dead_code)]
 pub enum ParametersAction {
-    #[serde(rename = "hudson.model.StringParameterValue")]
+    #[serde(
+        rename = "hudson.model.StringParameterValue",
+        alias = "hudson.model.TextParameterValue",
+        alias = "com.cloudbees.plugins.credentials.CredentialsParameterValue"
+    )]
     StringParameterValue { name: String, value: String },
     #[serde(rename = "hudson.model.BooleanParameterValue")]
     BooleanParameterValue { name: String, value: bool },
+    #[serde(rename = "hudson.model.RunParameterValue")]
+    /// `jobName` and `number` are there only if the tree asks for them.
+    RunParameterValue {
+        name: String,
+        #[serde(rename = "jobName", default)]
+        job_name: Option<String>,
+        #[serde(default)]
+        number: Option<String>,
+    },
+    /// passwords, which jenkins never returns in plain text.
+    #[serde(rename = "hudson.model.PasswordParameterValue")]
+    PasswordParameterValue { name: String },
+    /// files, which can not be downloaded to be sent again.
+    #[serde(
+        rename = "hudson.model.FileParameterValue",
+        alias = "io.jenkins.plugins.file_parameters.FileParameterValue"
+    )]
+    FileParameterValue { name: String },
+    #[serde(other)]
+    Other,
 }
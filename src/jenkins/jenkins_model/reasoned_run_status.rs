@@ -1,6 +1,17 @@
+use crate::jenkins::jenkins_model::run_progress::RunProgress;
+
 #[derive(Debug, PartialEq)]
 pub enum ReasonedRunStatus {
     Success,
-    Failure(String),
-    Processing,
-}
\ No newline at end of file
+    Failure(RunFailure),
+    Processing(Option<RunProgress>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunFailure {
+    /// name of the failed stage. None if the stage view is not available.
+    pub failed_stage: Option<String>,
+
+    /// log of the failed stage, or the whole console log as fallback.
+    pub log: String,
+}
@@ -0,0 +1,68 @@
+/// # RunProgress
+///
+/// Progress of an in-progress run task, inferred from `timestamp` and `estimatedDuration`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RunProgress {
+    /// milliseconds since the run task started.
+    pub elapsed: u64,
+
+    /// estimated duration in milliseconds. None if Jenkins has no history to estimate from.
+    pub estimated_duration: Option<u64>,
+}
+
+impl RunProgress {
+    pub fn new(started_at: u64, estimated_duration: Option<i64>, now: u64) -> Self {
+        Self {
+            elapsed: now.saturating_sub(started_at),
+            estimated_duration: estimated_duration.filter(|d| *d > 0).map(|d| d as u64),
+        }
+    }
+
+    /// # ratio
+    ///
+    /// progress in `[0, 1]`. None if there is no estimated duration.
+    pub fn ratio(&self) -> Option<f64> {
+        self.estimated_duration
+            .map(|estimated| (self.elapsed as f64 / estimated as f64).min(1.0))
+    }
+
+    /// # remaining
+    ///
+    /// estimated remaining milliseconds. 0 when the estimated duration has been exceeded.
+    pub fn remaining(&self) -> Option<u64> {
+        self.estimated_duration
+            .map(|estimated| estimated.saturating_sub(self.elapsed))
+    }
+
+    /// # overtime
+    ///
+    /// milliseconds exceeding the estimated duration.
+    pub fn overtime(&self) -> Option<u64> {
+        self.estimated_duration
+            .filter(|estimated| self.elapsed > *estimated)
+            .map(|estimated| self.elapsed - estimated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_progress() {
+        let progress = RunProgress::new(1_000, Some(4_000), 2_000);
+        assert_eq!(progress.elapsed, 1_000);
+        assert_eq!(progress.ratio(), Some(0.25));
+        assert_eq!(progress.remaining(), Some(3_000));
+        assert_eq!(progress.overtime(), None);
+
+        let progress = RunProgress::new(1_000, Some(4_000), 7_000);
+        assert_eq!(progress.ratio(), Some(1.0));
+        assert_eq!(progress.remaining(), Some(0));
+        assert_eq!(progress.overtime(), Some(2_000));
+
+        let progress = RunProgress::new(1_000, Some(-1), 7_000);
+        assert_eq!(progress.ratio(), None);
+        assert_eq!(progress.remaining(), None);
+    }
+}
@@ -0,0 +1,39 @@
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum StageStatus {
+    #[serde(rename = "SUCCESS")]
+    Success,
+    #[serde(rename = "FAILED")]
+    Failed,
+    #[serde(rename = "IN_PROGRESS")]
+    InProgress,
+    #[serde(rename = "NOT_EXECUTED")]
+    NotExecuted,
+    #[serde(rename = "ABORTED")]
+    Aborted,
+    #[serde(rename = "UNSTABLE")]
+    Unstable,
+    #[serde(rename = "PAUSED_PENDING_INPUT")]
+    PausedPendingInput,
+    #[serde(other)]
+    Unknown,
+}
+
+impl Display for StageStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            StageStatus::Success => "SUCCESS",
+            StageStatus::Failed => "FAILED",
+            StageStatus::InProgress => "IN_PROGRESS",
+            StageStatus::NotExecuted => "NOT_EXECUTED",
+            StageStatus::Aborted => "ABORTED",
+            StageStatus::Unstable => "UNSTABLE",
+            StageStatus::PausedPendingInput => "PAUSED_PENDING_INPUT",
+            StageStatus::Unknown => "UNKNOWN",
+        };
+
+        write!(f, "{}", str)
+    }
+}
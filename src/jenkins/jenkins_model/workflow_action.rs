@@ -16,8 +16,10 @@ pub enum WorkflowAction {
     #[serde(rename = "org.jenkinsci.plugins.buildmetadata.plugin.action.BuildMetadataAction")]
     BuildMetadata {
         #[serde(rename = "buildMetadata")]
-        build_metadata: Vec<WorkflowBuildMetadata> 
+        build_metadata: Vec<WorkflowBuildMetadata>,
     },
+    #[serde(rename = "org.jenkinsci.plugins.workflow.job.views.FlowGraphAction")]
+    FlowGraph,
     #[serde(other)]
     Unknown,
 }
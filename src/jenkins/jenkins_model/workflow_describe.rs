@@ -0,0 +1,106 @@
+use crate::jenkins::jenkins_model::stage_status::StageStatus;
+use serde::Deserialize;
+
+/// # WorkflowDescribe
+///
+/// Stage view of a pipeline run from `wfapi/describe`.
+#[derive(Deserialize, Debug)]
+pub struct WorkflowDescribe {
+    #[serde(default)]
+    pub stages: Vec<WorkflowStage>,
+}
+
+impl WorkflowDescribe {
+    /// # get current stage
+    ///
+    /// the latest stage that is still running.
+    pub fn get_current_stage(&self) -> Option<&WorkflowStage> {
+        self.stages
+            .iter()
+            .rev()
+            .find(|stage| stage.status == StageStatus::InProgress)
+    }
+
+    /// # get failed stage
+    ///
+    /// the first stage that failed. aborted and unstable stages are taken as a fallback.
+    pub fn get_failed_stage(&self) -> Option<&WorkflowStage> {
+        self.stages
+            .iter()
+            .find(|stage| stage.status == StageStatus::Failed)
+            .or_else(|| {
+                self.stages.iter().find(|stage| {
+                    matches!(stage.status, StageStatus::Aborted | StageStatus::Unstable)
+                })
+            })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WorkflowStage {
+    /// flow node id of the stage.
+    pub id: String,
+
+    pub name: String,
+
+    pub status: StageStatus,
+
+    #[serde(default, rename = "durationMillis")]
+    pub duration_millis: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_workflow_describe() {
+        let content = r##"{
+  "_links": {"self": {"href": "/job/Some.Job/851/wfapi/describe"}},
+  "id": "851",
+  "name": "#851",
+  "status": "IN_PROGRESS",
+  "startTimeMillis": 1746759736434,
+  "endTimeMillis": 0,
+  "durationMillis": 519510,
+  "queueDurationMillis": 4,
+  "pauseDurationMillis": 0,
+  "stages": [
+    {"id": "6", "name": "Checkout", "execNode": "", "status": "SUCCESS", "startTimeMillis": 1746759736500, "durationMillis": 12000, "pauseDurationMillis": 0},
+    {"id": "18", "name": "Compile", "execNode": "", "status": "IN_PROGRESS", "startTimeMillis": 1746759748500, "durationMillis": 507000, "pauseDurationMillis": 0},
+    {"id": "42", "name": "Publish", "execNode": "", "status": "NOT_EXECUTED", "startTimeMillis": 0, "durationMillis": 0, "pauseDurationMillis": 0}
+  ]
+}"##;
+
+        let describe = serde_json::from_str::<WorkflowDescribe>(content).unwrap();
+
+        assert_eq!(describe.stages.len(), 3);
+        assert_eq!(
+            describe
+                .get_current_stage()
+                .map(|stage| stage.name.as_str()),
+            Some("Compile")
+        );
+        assert_eq!(describe.stages[0].duration_millis, 12000);
+        assert!(describe.get_failed_stage().is_none());
+    }
+
+    #[test]
+    fn test_get_failed_stage() {
+        let content = r#"{
+  "stages": [
+    {"id": "6", "name": "Checkout", "status": "SUCCESS"},
+    {"id": "18", "name": "Compile", "status": "FAILED"},
+    {"id": "42", "name": "Publish", "status": "ABORTED"}
+  ]
+}"#;
+
+        let describe = serde_json::from_str::<WorkflowDescribe>(content).unwrap();
+
+        assert_eq!(
+            describe.get_failed_stage().map(|stage| stage.id.as_str()),
+            Some("18")
+        );
+        assert!(describe.get_current_stage().is_none());
+    }
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+/// # WorkflowNodeLog
+///
+/// Log of a single pipeline flow node from `execution/node/{id}/wfapi/log`.
+#[derive(Deserialize, Debug)]
+pub struct WorkflowNodeLog {
+    #[serde(default)]
+    pub text: String,
+
+    /// the log is truncated by Jenkins.
+    #[serde(default, rename = "hasMore")]
+    pub has_more: bool,
+}
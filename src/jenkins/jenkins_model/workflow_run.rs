@@ -1,8 +1,13 @@
+use crate::jenkins::build::VfpJobBuildParam;
 use crate::jenkins::jenkins_model::cause::Cause;
+use crate::jenkins::jenkins_model::parameters_action::ParametersAction;
+use crate::jenkins::jenkins_model::run_progress::RunProgress;
 use crate::jenkins::jenkins_model::run_status::RunStatus;
 use crate::jenkins::jenkins_model::workflow_action::{MaybeWorkflowAction, WorkflowAction};
 use crate::jenkins::jenkins_model::workflow_build_metadata::WorkflowBuildMetadata::StringBuildMetadata;
 use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
 
 #[derive(Deserialize, Debug)]
 pub struct WorkflowRun {
@@ -12,6 +17,18 @@ pub struct WorkflowRun {
 
     #[serde(deserialize_with = "deserialize_run_status")]
     pub result: RunStatus,
+
+    /// start time in milliseconds since the epoch.
+    #[serde(default)]
+    pub timestamp: Option<u64>,
+
+    /// duration in milliseconds. 0 while the run task is in progress.
+    #[serde(default)]
+    pub duration: Option<u64>,
+
+    /// estimated duration in milliseconds. -1 if Jenkins cannot estimate it.
+    #[serde(default, rename = "estimatedDuration")]
+    pub estimated_duration: Option<i64>,
 }
 
 fn deserialize_run_status<'de, D>(deserializer: D) -> Result<RunStatus, D::Error>
@@ -42,6 +59,97 @@ impl WorkflowRun {
         false
     }
 
+    /// # is pipeline
+    ///
+    /// whether the run task is a pipeline run, which has a stage view.
+    pub fn is_pipeline(&self) -> bool {
+        self.actions.iter().any(|action| {
+            matches!(
+                action,
+                MaybeWorkflowAction::WorkflowAction(WorkflowAction::FlowGraph)
+            )
+        })
+    }
+
+    /// # get user id
+    ///
+    /// the user who started the run task.
+    pub fn get_user_id(&self) -> Option<&str> {
+        self.actions.iter().find_map(|action| match action {
+            MaybeWorkflowAction::WorkflowAction(WorkflowAction::Causes { causes }) => {
+                causes.iter().find_map(|cause| match cause {
+                    Cause::UserId(user_id_cause) => Some(user_id_cause.user_id.as_str()),
+                    _ => None,
+                })
+            }
+            _ => None,
+        })
+    }
+
+    /// # get progress
+    ///
+    /// progress of the run task at `now` (milliseconds since the epoch).
+    pub fn get_progress(&self, now: u64) -> Option<RunProgress> {
+        self.timestamp
+            .map(|started_at| RunProgress::new(started_at, self.estimated_duration, now))
+    }
+
+    /// # get build params
+    ///
+    /// the exact params the run task was started with, read from `ParametersAction`.
+    pub fn get_build_params(&self) -> VfpJobBuildParam {
+        let params: HashMap<String, Value> = self
+            .get_parameters()
+            .filter_map(|param| match param {
+                ParametersAction::StringParameterValue { name, value } => {
+                    Some((name.clone(), Value::String(value.clone())))
+                }
+                ParametersAction::BooleanParameterValue { name, value } => {
+                    Some((name.clone(), Value::Bool(*value)))
+                }
+                ParametersAction::RunParameterValue {
+                    name,
+                    job_name: Some(job_name),
+                    number: Some(number),
+                } => Some((
+                    name.clone(),
+                    Value::String(format!("{}#{}", job_name, number)),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        VfpJobBuildParam {
+            params,
+            from_default: false,
+        }
+    }
+
+    /// # get unrepeatable build params
+    ///
+    /// names of the passwords and files the run task was started with, which can not be sent again.
+    pub fn get_unrepeatable_build_params(&self) -> Vec<String> {
+        self.get_parameters()
+            .filter_map(|param| match param {
+                ParametersAction::PasswordParameterValue { name }
+                | ParametersAction::FileParameterValue { name } => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn get_parameters(&self) -> impl Iterator<Item = &ParametersAction> {
+        self.actions
+            .iter()
+            .filter_map(|action| match action {
+                MaybeWorkflowAction::WorkflowAction(WorkflowAction::Parameters { parameters }) => {
+                    Some(parameters)
+                }
+                _ => None,
+            })
+            .flatten()
+    }
+
     pub fn get_change_list_in_build_meta_data(&self) -> Option<u32> {
         for action in &self.actions {
             if let MaybeWorkflowAction::WorkflowAction(WorkflowAction::BuildMetadata {
@@ -91,6 +199,20 @@ mod tests {
           "_class": "hudson.model.BooleanParameterValue",
           "name": "Publish_Blast",
           "value": true
+        },
+        {
+          "_class": "hudson.model.RunParameterValue",
+          "name": "BaseRun",
+          "jobName": "Some.Job",
+          "number": "851"
+        },
+        {
+          "_class": "hudson.model.RunParameterValue",
+          "name": "UnknownRun"
+        },
+        {
+          "_class": "hudson.model.PasswordParameterValue",
+          "name": "SignPassword"
         }
       ]
     },
@@ -182,6 +304,7 @@ mod tests {
   "number": 851,
   "result": "SUCCESS",
   "timestamp": 1746759736434,
+  "estimatedDuration": 520000,
   "url": "https://eamc-fcmobile.eamobile.ad.ea.com/job/FCM.EAMC.FCM-Stage.Client.Blast.Opt/851/",
   "inProgress": false
 }
@@ -191,10 +314,40 @@ mod tests {
         match serde_json::from_str::<WorkflowRun>(content) {
             Ok(workflow_run) => {
                 println!("{:#?}", workflow_run);
+                assert!(workflow_run.is_pipeline());
+                assert_eq!(workflow_run.get_user_id(), Some("LviatYi@foxmail.com"));
+
+                let build_params = workflow_run.get_build_params();
+                assert_eq!(build_params.params.len(), 4);
+                assert_eq!(
+                    build_params.params.get("Publish_Blast"),
+                    Some(&Value::Bool(true))
+                );
+                assert_eq!(
+                    build_params.params.get("BaseRun"),
+                    Some(&Value::String("Some.Job#851".to_string()))
+                );
+                assert!(!build_params.from_default);
+                assert_eq!(
+                    workflow_run.get_unrepeatable_build_params(),
+                    vec!["SignPassword".to_string()]
+                );
             }
-            Err(e) => {
-                println!("Error: {}", e);
-            }
+            Err(e) => panic!("{}", e),
         }
     }
+
+    #[test]
+    fn test_get_progress() {
+        let content = r#"{"number":852,"actions":[],"result":null,"timestamp":1746759736434,"estimatedDuration":520000}"#;
+        let workflow_run = serde_json::from_str::<WorkflowRun>(content).unwrap();
+
+        let progress = workflow_run.get_progress(1746759736434 + 130000).unwrap();
+        assert_eq!(progress.ratio(), Some(0.25));
+        assert_eq!(progress.remaining(), Some(390000));
+
+        let content = r#"{"number":853,"actions":[],"result":null}"#;
+        let workflow_run = serde_json::from_str::<WorkflowRun>(content).unwrap();
+        assert!(workflow_run.get_progress(1746759736434).is_none());
+    }
 }
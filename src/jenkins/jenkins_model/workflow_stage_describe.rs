@@ -0,0 +1,85 @@
+use crate::jenkins::jenkins_model::stage_status::StageStatus;
+use serde::Deserialize;
+
+/// # WorkflowStageDescribe
+///
+/// Flow nodes (steps) of a pipeline stage from `execution/node/{id}/wfapi/describe`.
+#[derive(Deserialize, Debug)]
+pub struct WorkflowStageDescribe {
+    #[serde(default)]
+    pub error: Option<StageError>,
+
+    #[serde(default, rename = "stageFlowNodes")]
+    pub stage_flow_nodes: Vec<WorkflowStageFlowNode>,
+}
+
+impl WorkflowStageDescribe {
+    /// # get failed flow nodes
+    ///
+    /// the failed steps in the stage. all steps if none of them is marked as failed.
+    pub fn get_failed_flow_nodes(&self) -> Vec<&WorkflowStageFlowNode> {
+        let failed: Vec<&WorkflowStageFlowNode> = self
+            .stage_flow_nodes
+            .iter()
+            .filter(|node| node.status == StageStatus::Failed)
+            .collect();
+
+        if failed.is_empty() {
+            self.stage_flow_nodes.iter().collect()
+        } else {
+            failed
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StageError {
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WorkflowStageFlowNode {
+    pub id: String,
+
+    pub name: String,
+
+    pub status: StageStatus,
+
+    /// step argument like the executed shell script.
+    #[serde(default, rename = "parameterDescription")]
+    pub parameter_description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_workflow_stage_describe() {
+        let content = r#"{
+  "id": "18",
+  "name": "Compile",
+  "execNode": "",
+  "status": "FAILED",
+  "error": {"message": "script returned exit code 1", "type": "hudson.AbortException"},
+  "startTimeMillis": 1746759748500,
+  "durationMillis": 507000,
+  "stageFlowNodes": [
+    {"id": "19", "name": "Print Message", "execNode": "", "status": "SUCCESS", "parameterDescription": "compile", "parentNodes": ["18"]},
+    {"id": "21", "name": "Windows Batch Script", "execNode": "", "status": "FAILED", "parameterDescription": "build.bat", "parentNodes": ["19"]}
+  ]
+}"#;
+
+        let describe = serde_json::from_str::<WorkflowStageDescribe>(content).unwrap();
+
+        assert_eq!(
+            describe.error.as_ref().map(|e| e.message.as_str()),
+            Some("script returned exit code 1")
+        );
+
+        let failed = describe.get_failed_flow_nodes();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].id, "21");
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// # JobPath
+///
+/// Path of a Jenkins job, which may be nested in folders or be a branch of a multibranch pipeline.
+///
+/// It is written like `Folder/Sub/main`, while each segment is the item name in Jenkins.
+/// The url path like `job/Folder/job/Sub` is parsed by [JenkinsUrlFactor] instead,
+/// since a folder may be named `job`.
+///
+/// A branch like `feature/foo` has the item name `feature%2Ffoo` in Jenkins,
+/// so a segment never contains `/`.
+///
+/// [JenkinsUrlFactor]: crate::jenkins::jenkins_url_factor::JenkinsUrlFactor
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct JobPath(String);
+
+impl JobPath {
+    pub fn from_segments<'a>(segments: impl IntoIterator<Item = &'a str>) -> Self {
+        Self(
+            segments
+                .into_iter()
+                .map(str::trim)
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+
+    pub fn get_segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split('/').filter(|segment| !segment.is_empty())
+    }
+
+    /// # to url path
+    ///
+    /// like `job/Folder/job/Sub/job/feature%252Ffoo`.
+    pub fn to_url_path(&self) -> String {
+        self.get_segments()
+            .map(|segment| format!("job/{}", percent_encode(segment)))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// # to sdk job name
+    ///
+    /// the url path without the leading `job/`, for the endpoints of jenkins_sdk which prepend it.
+    pub fn to_sdk_job_name(&self) -> String {
+        self.to_url_path()
+            .strip_prefix("job/")
+            .map(str::to_string)
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for JobPath {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_segments(s.split('/')))
+    }
+}
+
+impl From<&str> for JobPath {
+    fn from(value: &str) -> Self {
+        Self::from_str(value).unwrap_or_default()
+    }
+}
+
+impl From<String> for JobPath {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<&String> for JobPath {
+    fn from(value: &String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl Display for JobPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for JobPath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for JobPath {
+    fn eq(&self, other: &str) -> bool {
+        self.get_segments().eq(other
+            .split('/')
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty()))
+    }
+}
+
+impl PartialEq<&str> for JobPath {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl Serialize for JobPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for JobPath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(JobPath::from(String::deserialize(deserializer)?))
+    }
+}
+
+/// keep the unreserved characters of RFC 3986 only.
+pub fn percent_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// decode a percent encoded url path segment. invalid escapes are kept as is.
+pub fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = (bytes[idx] == b'%')
+            .then(|| segment.get(idx + 1..idx + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            None => {
+                decoded.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_path() {
+        let job_path = JobPath::from("Some.Long-JOB_NAME");
+        assert_eq!(job_path.to_url_path(), "job/Some.Long-JOB_NAME");
+        assert_eq!(job_path.to_sdk_job_name(), "Some.Long-JOB_NAME");
+
+        let job_path = JobPath::from("Folder/Sub Folder/feature%2Ffoo/");
+        assert_eq!(job_path.to_string(), "Folder/Sub Folder/feature%2Ffoo");
+        assert_eq!(
+            job_path.to_url_path(),
+            "job/Folder/job/Sub%20Folder/job/feature%252Ffoo"
+        );
+        assert_eq!(
+            job_path.to_sdk_job_name(),
+            "Folder/job/Sub%20Folder/job/feature%252Ffoo"
+        );
+
+        // a folder named `job` is not taken as the url path.
+        let job_path = JobPath::from("job/Folder/job/main");
+        assert_eq!(job_path.get_segments().count(), 4);
+        assert_eq!(
+            job_path.to_url_path(),
+            "job/job/job/Folder/job/job/job/main"
+        );
+        assert_eq!(JobPath::from("Folder/Sub/main"), "Folder/Sub/main/");
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("feature%252Ffoo"), "feature%2Ffoo");
+        assert_eq!(percent_decode("Sub%20Folder"), "Sub Folder");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_serde_job_path() {
+        let job_path: JobPath = serde_json::from_str(r#""Folder/main/""#).unwrap();
+        assert_eq!(job_path, JobPath::from("Folder/main"));
+        assert_eq!(
+            serde_json::to_string(&job_path).unwrap(),
+            r#""Folder/main""#
+        );
+    }
+}
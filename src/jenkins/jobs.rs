@@ -0,0 +1,326 @@
+use crate::app_state::AppState;
+use crate::constant::log::*;
+use crate::default_config;
+use crate::jenkins::jenkins_model::job_tree::{JobTree, JobTreeItem, ViewTreeItem};
+use crate::jenkins::jenkins_url_factor::JenkinsUrlFactor;
+use crate::jenkins::job_path::JobPath;
+use crate::jenkins::query::{query_job_tree, VfpJenkinsClient};
+use crate::pretty_log::{colored_println, ThemeColor};
+use crate::vfp_error::VfpFrontError;
+use formatx::formatx;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// # JobStatus
+///
+/// Status of the last build of a job, read from the color of the job.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum JobStatus {
+    Success,
+    Failure,
+    Unstable,
+    Aborted,
+    NotBuilt,
+    Disabled,
+    Unknown,
+}
+
+impl JobStatus {
+    /// # from color
+    ///
+    /// parse the color like `blue` or `red_anime`. the `_anime` suffix means a build is running.
+    pub fn from_color(color: &str) -> (Self, bool) {
+        let (color, building) = match color.strip_suffix("_anime") {
+            Some(color) => (color, true),
+            None => (color, false),
+        };
+
+        let status = match color {
+            "blue" | "green" => JobStatus::Success,
+            "red" => JobStatus::Failure,
+            "yellow" => JobStatus::Unstable,
+            "aborted" => JobStatus::Aborted,
+            "notbuilt" | "grey" => JobStatus::NotBuilt,
+            "disabled" => JobStatus::Disabled,
+            _ => JobStatus::Unknown,
+        };
+
+        (status, building)
+    }
+
+    fn get_str(&self) -> &'static str {
+        match self {
+            JobStatus::Success => INFO_STATUS_SUCCESS,
+            JobStatus::Failure => INFO_STATUS_FAILURE,
+            JobStatus::Unstable => JOBS_STATUS_UNSTABLE,
+            JobStatus::Aborted => JOBS_STATUS_ABORTED,
+            JobStatus::NotBuilt => JOBS_STATUS_NOT_BUILT,
+            JobStatus::Disabled => JOBS_STATUS_DISABLED,
+            JobStatus::Unknown => "-",
+        }
+    }
+
+    fn get_theme_color(&self) -> ThemeColor {
+        match self {
+            JobStatus::Success => ThemeColor::Success,
+            JobStatus::Failure => ThemeColor::Error,
+            JobStatus::Unstable | JobStatus::Aborted => ThemeColor::Warn,
+            _ => ThemeColor::Second,
+        }
+    }
+}
+
+/// # JobEntry
+///
+/// A buildable job found on Jenkins.
+#[derive(Debug, PartialEq)]
+pub struct JobEntry {
+    pub path: JobPath,
+    pub status: JobStatus,
+
+    /// whether a build of the job is running.
+    pub building: bool,
+
+    pub last_build_number: Option<u32>,
+}
+
+/// # collect job entries
+///
+/// Flatten the jobs of the root, the folders and the views, sorted by path.
+/// Folders and multibranch pipelines themselves are not buildable and are skipped.
+pub fn collect_job_entries(tree: &JobTree) -> Vec<JobEntry> {
+    fn collect_jobs(items: &[JobTreeItem], seen: &mut HashSet<JobPath>, out: &mut Vec<JobEntry>) {
+        for item in items {
+            if let Some(jobs) = &item.jobs {
+                collect_jobs(jobs, seen, out);
+            }
+
+            let Some(color) = &item.color else {
+                continue;
+            };
+            // the url tells the full path, even for a job listed in a view.
+            let path = JenkinsUrlFactor::from_url(&item.url)
+                .ok()
+                .and_then(|factor| factor.get_job_path().cloned())
+                .unwrap_or_else(|| JobPath::from(item.name.as_str()));
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+
+            let (status, building) = JobStatus::from_color(color);
+            out.push(JobEntry {
+                path,
+                status,
+                building,
+                last_build_number: item.last_build.as_ref().map(|build| build.number),
+            });
+        }
+    }
+
+    fn collect_views(views: &[ViewTreeItem], seen: &mut HashSet<JobPath>, out: &mut Vec<JobEntry>) {
+        for view in views {
+            collect_jobs(&view.jobs, seen, out);
+            collect_views(&view.views, seen, out);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    collect_jobs(&tree.jobs, &mut seen, &mut entries);
+    collect_views(&tree.views, &mut seen, &mut entries);
+    entries.sort_by(|a, b| a.path.as_ref().cmp(b.path.as_ref()));
+
+    entries
+}
+
+/// # query jobs
+///
+/// Query all buildable jobs on Jenkins.
+pub async fn query_jobs(client: &VfpJenkinsClient) -> Result<Vec<JobEntry>, VfpFrontError> {
+    let tree = query_job_tree(client, default_config::JOB_TREE_DEPTH).await?;
+
+    Ok(collect_job_entries(&tree))
+}
+
+/// # filter job entries
+///
+/// Keep the jobs whose path matches the regex.
+pub fn filter_job_entries(
+    entries: Vec<JobEntry>,
+    filter: Option<&str>,
+) -> Result<Vec<JobEntry>, VfpFrontError> {
+    let Some(filter) = filter else {
+        return Ok(entries);
+    };
+
+    let regex = Regex::new(filter).map_err(|e| {
+        VfpFrontError::Custom(formatx!(ERR_INVALID_JOB_FILTER, filter, e).unwrap_or_default())
+    })?;
+
+    Ok(entries
+        .into_iter()
+        .filter(|entry| regex.is_match(entry.path.as_ref()))
+        .collect())
+}
+
+/// # print jobs
+///
+/// Print the jobs as a table.
+pub fn print_jobs(app_state: &AppState, entries: &[JobEntry]) {
+    let mut stdout = app_state.get_stdout();
+
+    colored_println(
+        &mut stdout,
+        ThemeColor::Main,
+        &formatx!(JOBS_TITLE, entries.len()).unwrap_or_default(),
+    );
+
+    if entries.is_empty() {
+        colored_println(&mut stdout, ThemeColor::Second, JOBS_EMPTY);
+        return;
+    }
+
+    let path_width = entries
+        .iter()
+        .map(|entry| entry.path.as_ref().len())
+        .max()
+        .unwrap_or_default()
+        .max("Job".len());
+    colored_println(
+        &mut stdout,
+        ThemeColor::Second,
+        &format!("{:<path_width$} {:<24} Last build", "Job", "Status"),
+    );
+    for entry in entries {
+        let status = if entry.building {
+            format!("{} {}", entry.status.get_str(), JOBS_BUILDING_SUFFIX)
+        } else {
+            entry.status.get_str().to_string()
+        };
+
+        colored_println(
+            &mut stdout,
+            entry.status.get_theme_color(),
+            &format!(
+                "{:<path_width$} {:<24} {}",
+                entry.path.as_ref(),
+                status,
+                entry
+                    .last_build_number
+                    .map(|number| format!("#{}", number))
+                    .unwrap_or("-".to_string())
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_job_entries() {
+        let tree: JobTree = serde_json::from_str(
+            r#"{
+                "jobs": [
+                    {
+                        "name": "Folder",
+                        "url": "https://example.jenkins.com/job/Folder/",
+                        "jobs": [
+                            {
+                                "name": "Multibranch",
+                                "url": "https://example.jenkins.com/job/Folder/job/Multibranch/",
+                                "jobs": [
+                                    {
+                                        "name": "feature%2Ffoo",
+                                        "url": "https://example.jenkins.com/job/Folder/job/Multibranch/job/feature%252Ffoo/",
+                                        "color": "red_anime",
+                                        "lastBuild": { "number": 12 }
+                                    }
+                                ]
+                            }
+                        ]
+                    },
+                    {
+                        "name": "Some.Long-JOB_NAME",
+                        "url": "https://example.jenkins.com/job/Some.Long-JOB_NAME/",
+                        "color": "blue",
+                        "lastBuild": { "number": 1234 }
+                    }
+                ],
+                "views": [
+                    {
+                        "jobs": [
+                            {
+                                "name": "Some.Long-JOB_NAME",
+                                "url": "https://example.jenkins.com/job/Some.Long-JOB_NAME/",
+                                "color": "blue"
+                            }
+                        ],
+                        "views": [
+                            {
+                                "jobs": [
+                                    {
+                                        "name": "Fresh",
+                                        "url": "https://example.jenkins.com/view/nested/job/Fresh/",
+                                        "color": "notbuilt",
+                                        "lastBuild": null
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            collect_job_entries(&tree),
+            vec![
+                JobEntry {
+                    path: JobPath::from("Folder/Multibranch/feature%2Ffoo"),
+                    status: JobStatus::Failure,
+                    building: true,
+                    last_build_number: Some(12),
+                },
+                JobEntry {
+                    path: JobPath::from("Fresh"),
+                    status: JobStatus::NotBuilt,
+                    building: false,
+                    last_build_number: None,
+                },
+                JobEntry {
+                    path: JobPath::from("Some.Long-JOB_NAME"),
+                    status: JobStatus::Success,
+                    building: false,
+                    last_build_number: Some(1234),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_job_entries() {
+        let entries = ["Client.Dev", "Client.Release", "Server.Dev"]
+            .into_iter()
+            .map(|path| JobEntry {
+                path: JobPath::from(path),
+                status: JobStatus::Success,
+                building: false,
+                last_build_number: None,
+            })
+            .collect::<Vec<_>>();
+
+        let filtered = filter_job_entries(entries, Some(r"\.Dev$")).unwrap();
+        assert_eq!(
+            filtered
+                .iter()
+                .map(|entry| entry.path.to_string())
+                .collect::<Vec<_>>(),
+            vec!["Client.Dev", "Server.Dev"]
+        );
+
+        assert!(filter_job_entries(filtered, Some("(")).is_err());
+    }
+}
@@ -1,14 +1,25 @@
+mod api_token_jenkins_async_client;
 pub mod build;
+pub mod diagnose;
+pub mod error_kind;
+pub mod history;
+mod http_client;
+pub mod info;
 pub mod jenkins_endpoint;
 pub mod jenkins_model;
 pub mod jenkins_url_factor;
+pub mod job_path;
+pub mod jobs;
 mod pwd_jenkins_async_client;
 pub mod query;
+pub mod session;
+mod session_jenkins_async_client;
 pub mod util;
 pub mod watch;
 
 #[cfg(test)]
 mod tests {
+    use crate::jenkins::api_token_jenkins_async_client::ApiTokenJenkinsAsyncClient;
     use crate::jenkins::build::ToVfpJobBuildParam;
     use crate::jenkins::jenkins_endpoint::get_crumb::GetCrumb;
     use crate::jenkins::jenkins_endpoint::run_info::RunInfo;
@@ -35,7 +46,7 @@ mod tests {
         let client = JenkinsAsyncClient::new(URL, USERNAME, API_TOKEN);
 
         // let jobs: serde_json::Value = AsyncQuery::query(&JobsInfo, &client).await.unwrap();
-        // 
+        //
         // println!("Jobs: {:#?}", jobs);
     }
 
@@ -178,24 +189,24 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_ping_jenkins() {
-        let client_valid =
-            VfpJenkinsClient::ApiTokenClient(JenkinsAsyncClient::new(URL, USERNAME, API_TOKEN));
+        let client_valid = VfpJenkinsClient::ApiTokenClient(
+            ApiTokenJenkinsAsyncClient::new(URL, USERNAME, API_TOKEN).unwrap(),
+        );
 
         assert!(ping_jenkins(&client_valid).await.is_ok());
 
-        let client_invalid = VfpJenkinsClient::ApiTokenClient(JenkinsAsyncClient::new(
-            "https://what",
-            "who?",
-            "none",
-        ));
+        let client_invalid = VfpJenkinsClient::ApiTokenClient(
+            ApiTokenJenkinsAsyncClient::new("https://what", "who?", "none").unwrap(),
+        );
         assert!(ping_jenkins(&client_invalid).await.is_err());
     }
 
     #[tokio::test]
     #[ignore]
     async fn test_query_builds_in_job() {
-        let client =
-            VfpJenkinsClient::ApiTokenClient(JenkinsAsyncClient::new(URL, USERNAME, API_TOKEN));
+        let client = VfpJenkinsClient::ApiTokenClient(
+            ApiTokenJenkinsAsyncClient::new(URL, USERNAME, API_TOKEN).unwrap(),
+        );
 
         let builds = query_builds_in_job(&client, JOB_NAME, Some(200))
             .await
@@ -208,8 +219,9 @@ mod tests {
     #[ignore]
     async fn test_query_runs_in_job() {
         let my_user_id = USERNAME;
-        let client =
-            VfpJenkinsClient::ApiTokenClient(JenkinsAsyncClient::new(URL, my_user_id, API_TOKEN));
+        let client = VfpJenkinsClient::ApiTokenClient(
+            ApiTokenJenkinsAsyncClient::new(URL, my_user_id, API_TOKEN).unwrap(),
+        );
         let job_name = JOB_NAME.to_string();
 
         match query_builds_in_job(&client, &job_name, Some(20)).await {
@@ -239,14 +251,15 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_query_run_info() {
-        let client =
-            VfpJenkinsClient::ApiTokenClient(JenkinsAsyncClient::new(URL, USERNAME, API_TOKEN));
+        let client = VfpJenkinsClient::ApiTokenClient(
+            ApiTokenJenkinsAsyncClient::new(URL, USERNAME, API_TOKEN).unwrap(),
+        );
         let job_name = JOB_NAME.to_string();
         let run_number = 851;
 
         let raw_content = AsyncRawQuery::raw_query(
             &RunInfo {
-                job_name: job_name.clone(),
+                job_path: job_name.as_str().into(),
                 build_number: run_number,
             },
             &client,
@@ -268,8 +281,9 @@ mod tests {
     #[ignore]
     async fn test_query_run_log() {
         let my_user_id = USERNAME;
-        let client =
-            VfpJenkinsClient::ApiTokenClient(JenkinsAsyncClient::new(URL, my_user_id, API_TOKEN));
+        let client = VfpJenkinsClient::ApiTokenClient(
+            ApiTokenJenkinsAsyncClient::new(URL, my_user_id, API_TOKEN).unwrap(),
+        );
         let job_name = JOB_NAME.to_string();
 
         match query_run_log(&client, &job_name, 2090).await {
@@ -369,8 +383,9 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn test_get_crumb() {
-        let client =
-            VfpJenkinsClient::PwdClient(PwdJenkinsAsyncClient::new(URL, USERNAME, JENKINS_PWD));
+        let client = VfpJenkinsClient::PwdClient(
+            PwdJenkinsAsyncClient::new(URL, USERNAME, JENKINS_PWD).unwrap(),
+        );
 
         match AsyncQuery::<Crumb>::query(&GetCrumb, &client).await {
             Ok(resp) => {
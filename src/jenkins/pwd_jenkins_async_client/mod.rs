@@ -1,18 +1,23 @@
+use crate::jenkins::http_client::{build_http_client, with_total_timeout};
 use crate::jenkins::jenkins_endpoint::get_crumb::GetCrumb;
 use crate::jenkins::jenkins_model::crumb::Crumb;
 use base64::Engine;
 use jenkins_sdk::client::AsyncClient;
 use jenkins_sdk::{AsyncQuery, JenkinsError};
 use reqwest::header::{HeaderValue, AUTHORIZATION};
-use reqwest::Client;
+use reqwest::multipart::Form;
+use reqwest::{Client, RequestBuilder, Response};
+use std::sync::RwLock;
 
 /// Asynchronous Jenkins API client.
 pub struct PwdJenkinsAsyncClient {
     url: String,
     username: String,
     pwd: String,
-    client: Client,
-    crumb: Option<Crumb>,
+
+    /// replaced when the session is renewed, which drops the cookies.
+    client: RwLock<Client>,
+    crumb: RwLock<Option<Crumb>>,
 }
 
 impl PwdJenkinsAsyncClient {
@@ -23,36 +28,55 @@ impl PwdJenkinsAsyncClient {
     /// * `url` - Base URL of the Jenkins server.
     /// * `username` - Username for authentication.
     /// * `pwd` - Password for authentication.
-    pub fn new(url: &str, username: &str, pwd: &str) -> Self {
-        Self {
+    ///
+    /// Fails if the `[http]` of the runtime config is invalid.
+    pub fn new(url: &str, username: &str, pwd: &str) -> Result<Self, JenkinsError> {
+        Ok(Self {
             url: url.into(),
             username: username.into(),
             pwd: pwd.into(),
-            client: Client::builder()
-                .cookie_store(true)
-                .danger_accept_invalid_certs(true)
-                .no_proxy()
-                .build()
-                .unwrap(),
-            crumb: None,
+            client: RwLock::new(build_http_client(url, true)?),
+            crumb: RwLock::new(None),
+        })
+    }
+
+    pub async fn attach_crumb(&self) -> Result<(), JenkinsError> {
+        let crumb = AsyncQuery::<Crumb>::query(&GetCrumb, self).await?;
+        if let Ok(mut curr) = self.crumb.write() {
+            *curr = Some(crumb);
         }
+
+        Ok(())
     }
 
-    pub async fn attach_crumb(&mut self) -> Result<(), JenkinsError> {
-        self.crumb = Some(AsyncQuery::<Crumb>::query(&GetCrumb, self).await?);
+    pub fn has_crumb(&self) -> bool {
+        self.crumb.read().is_ok_and(|crumb| crumb.is_some())
+    }
+
+    /// # renew session
+    ///
+    /// Drop the cookies and the crumb of an expired session, and get a new crumb if there was one.
+    pub async fn renew_session(&self) -> Result<(), JenkinsError> {
+        let had_crumb = self.has_crumb();
+        if let Ok(mut client) = self.client.write() {
+            *client = build_http_client(&self.url, true)?;
+        }
+        if let Ok(mut crumb) = self.crumb.write() {
+            *crumb = None;
+        }
+
+        if had_crumb {
+            self.attach_crumb().await?;
+        }
+
         Ok(())
     }
-}
 
-#[async_trait::async_trait]
-impl AsyncClient for PwdJenkinsAsyncClient {
-    /// Sends an asynchronous HTTP request to the Jenkins server.
-    async fn request(
+    fn get_request_builder(
         &self,
         method: &str,
         endpoint: &str,
-        params: Option<&[(&str, &str)]>,
-    ) -> Result<String, JenkinsError> {
+    ) -> Result<RequestBuilder, JenkinsError> {
         let url = format!("{}/{}", self.url, endpoint);
         let auth = format!(
             "BASIC {}",
@@ -60,25 +84,72 @@ impl AsyncClient for PwdJenkinsAsyncClient {
                 .encode(format!("{}:{}", self.username, self.pwd).as_bytes())
         );
 
-        let mut req = self
+        let client = self
             .client
+            .read()
+            .map(|client| client.clone())
+            .map_err(|e| JenkinsError::RequestError(e.to_string()))?;
+        let mut req = client
             .request(method.parse()?, url)
             .header(AUTHORIZATION, auth)
             .header("User-Agent", "jenkins-sdk-rust");
 
-        if let Some(ref crumb) = self.crumb {
+        if let Ok(crumb) = self.crumb.read()
+            && let Some(ref crumb) = *crumb
+        {
             req = req.header(
                 crumb.crumb_request_field.clone(),
-                HeaderValue::from_str(&crumb.crumb).map_err(|e| JenkinsError::RequestError(e.to_string()))?,
+                HeaderValue::from_str(&crumb.crumb)
+                    .map_err(|e| JenkinsError::RequestError(e.to_string()))?,
             );
         }
 
+        Ok(req)
+    }
+
+    /// Sends a request once, and returns the response whatever its status is.
+    pub async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<Response, JenkinsError> {
+        let req = with_total_timeout(self.get_request_builder(method, endpoint)?, endpoint);
+
         let resp = if let Some(p) = params {
             req.form(&p).send().await?
         } else {
             req.send().await?
         };
 
-        Ok(resp.text().await?)
+        Ok(resp)
+    }
+
+    /// Sends a multipart form, which is required to upload files.
+    /// The upload has no total timeout, only the read timeout of the client.
+    pub async fn send_multipart(
+        &self,
+        method: &str,
+        endpoint: &str,
+        form: Form,
+    ) -> Result<Response, JenkinsError> {
+        Ok(self
+            .get_request_builder(method, endpoint)?
+            .multipart(form)
+            .send()
+            .await?)
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncClient for PwdJenkinsAsyncClient {
+    /// Sends an asynchronous HTTP request to the Jenkins server.
+    async fn request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<String, JenkinsError> {
+        Ok(self.send(method, endpoint, params).await?.text().await?)
     }
 }
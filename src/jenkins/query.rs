@@ -1,32 +1,256 @@
 use crate::constant::log::*;
+use crate::default_config;
+use crate::jenkins::api_token_jenkins_async_client::ApiTokenJenkinsAsyncClient;
+use crate::jenkins::error_kind::{is_login_page, JenkinsAuthError};
+use crate::jenkins::http_client::{
+    is_idempotent, is_retryable_status, is_transient_error, RetryPolicy,
+};
 use crate::jenkins::jenkins_endpoint::job_info::JobInfo;
+use crate::jenkins::jenkins_endpoint::job_tree::JobTree;
+use crate::jenkins::jenkins_endpoint::node_log::NodeLog;
 use crate::jenkins::jenkins_endpoint::ping::{Ping, PingResult};
+use crate::jenkins::jenkins_endpoint::queue_item::{QueueItem, QueueItemResult};
+use crate::jenkins::jenkins_endpoint::run_describe::RunDescribe;
 use crate::jenkins::jenkins_endpoint::run_info::RunInfo;
 use crate::jenkins::jenkins_endpoint::run_log::RunLog;
+use crate::jenkins::jenkins_endpoint::stage_describe::StageDescribe;
+use crate::jenkins::jenkins_endpoint::who_am_i::{WhoAmI, WhoAmIResult};
+use crate::jenkins::jenkins_model::job_tree;
 use crate::jenkins::jenkins_model::workflow_builds::WorkflowBuilds;
+use crate::jenkins::jenkins_model::workflow_describe::WorkflowDescribe;
+use crate::jenkins::jenkins_model::workflow_node_log::WorkflowNodeLog;
 use crate::jenkins::jenkins_model::workflow_run::WorkflowRun;
+use crate::jenkins::jenkins_model::workflow_stage_describe::WorkflowStageDescribe;
 use crate::jenkins::pwd_jenkins_async_client::PwdJenkinsAsyncClient;
+use crate::jenkins::session::{JenkinsSession, SessionSource};
+use crate::jenkins::session_jenkins_async_client::SessionJenkinsAsyncClient;
 use jenkins_sdk::client::AsyncClient;
-use jenkins_sdk::{AsyncQuery, JenkinsAsyncClient, JenkinsError};
+use jenkins_sdk::{AsyncQuery, JenkinsError};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::multipart::Form;
+use reqwest::{Response, StatusCode};
 
+/// set by Jenkins to the user of a request, like on a 403.
+const HEADER_AUTHENTICATED_AS: &str = "X-You-Are-Authenticated-As";
+
+#[allow(clippy::enum_variant_names)]
 pub enum VfpJenkinsClient {
     PwdClient(PwdJenkinsAsyncClient),
-    ApiTokenClient(JenkinsAsyncClient),
+    ApiTokenClient(ApiTokenJenkinsAsyncClient),
+    SessionClient(SessionJenkinsAsyncClient),
 }
 
 #[async_trait::async_trait]
 impl AsyncClient for VfpJenkinsClient {
+    /// Sends a request with the timeouts of the `[http]` config.
+    ///
+    /// GET requests are retried with jittered exponential backoff on timeouts, failed connections
+    /// and 5xx of a busy Jenkins. An expired session is renewed once, which for the session
+    /// client reads it again from its file or command, and the crumb is refreshed once on 403.
+    ///
+    /// An error status, or a login page for a json api, is returned as an error
+    /// to be classified by [JenkinsErrorKind](crate::jenkins::error_kind::JenkinsErrorKind).
     async fn request(
         &self,
         method: &str,
         endpoint: &str,
         params: Option<&[(&str, &str)]>,
     ) -> Result<String, JenkinsError> {
+        Ok(self
+            .request_response(method, endpoint, params)
+            .await?
+            .text()
+            .await?)
+    }
+}
+
+impl VfpJenkinsClient {
+    /// # request response
+    ///
+    /// Send a request like [AsyncClient::request], and get the response of a success status,
+    /// whose headers are needed like the `Location` of a queued build.
+    pub async fn request_response(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<Response, JenkinsError> {
+        let policy = RetryPolicy::from_config(&default_config::runtime().http);
+        let idempotent = is_idempotent(method);
+        let mut attempt = 0;
+        let mut crumb_refreshed = false;
+        let mut session_renewed = false;
+
+        loop {
+            match self.send(method, endpoint, params).await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if self.is_session_expired(endpoint, &resp) {
+                        if !session_renewed && self.can_renew_session() {
+                            session_renewed = true;
+                            self.renew_session().await?;
+                            continue;
+                        }
+
+                        if let VfpJenkinsClient::SessionClient(_) = self {
+                            return Err(JenkinsAuthError::SessionExpired.into());
+                        }
+                    }
+
+                    if status == StatusCode::FORBIDDEN && !crumb_refreshed && self.has_crumb() {
+                        crumb_refreshed = true;
+                        self.attach_crumb().await?;
+                        continue;
+                    }
+
+                    if !(is_retryable_status(status) && idempotent && attempt < policy.max_retries)
+                    {
+                        return Self::check_response(endpoint, resp);
+                    }
+                }
+                Err(e) if is_transient_error(&e) && idempotent && attempt < policy.max_retries => {}
+                Err(e) => return Err(e),
+            }
+
+            attempt += 1;
+            tokio::time::sleep(policy.get_delay(attempt, rand::random::<f64>())).await;
+        }
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<Response, JenkinsError> {
         match self {
-            VfpJenkinsClient::PwdClient(c) => c.request(method, endpoint, params).await,
-            VfpJenkinsClient::ApiTokenClient(c) => c.request(method, endpoint, params).await,
+            VfpJenkinsClient::PwdClient(c) => c.send(method, endpoint, params).await,
+            VfpJenkinsClient::ApiTokenClient(c) => c.send(method, endpoint, params).await,
+            VfpJenkinsClient::SessionClient(c) => c.send(method, endpoint, params).await,
         }
     }
+
+    /// # is session expired
+    ///
+    /// a 401, or for the session client, any sign of an expired session.
+    fn is_session_expired(&self, endpoint: &str, resp: &Response) -> bool {
+        match self {
+            VfpJenkinsClient::SessionClient(_) => is_session_expired(endpoint, resp),
+            _ => resp.status() == StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    /// the api token never expires, and the session only if it has a source to read again.
+    fn can_renew_session(&self) -> bool {
+        match self {
+            VfpJenkinsClient::PwdClient(_) => true,
+            VfpJenkinsClient::ApiTokenClient(_) => false,
+            VfpJenkinsClient::SessionClient(c) => c.can_renew(),
+        }
+    }
+
+    async fn renew_session(&self) -> Result<(), JenkinsError> {
+        match self {
+            VfpJenkinsClient::PwdClient(c) => c.renew_session().await,
+            VfpJenkinsClient::ApiTokenClient(_) => Ok(()),
+            VfpJenkinsClient::SessionClient(c) => c.renew_session().await,
+        }
+    }
+
+    /// Jenkins does not ask for a crumb with an api token.
+    fn has_crumb(&self) -> bool {
+        match self {
+            VfpJenkinsClient::PwdClient(c) => c.has_crumb(),
+            VfpJenkinsClient::ApiTokenClient(_) => false,
+            VfpJenkinsClient::SessionClient(c) => c.has_crumb(),
+        }
+    }
+
+    async fn attach_crumb(&self) -> Result<(), JenkinsError> {
+        match self {
+            VfpJenkinsClient::PwdClient(c) => c.attach_crumb().await,
+            VfpJenkinsClient::ApiTokenClient(_) => Ok(()),
+            VfpJenkinsClient::SessionClient(c) => c.attach_crumb().await,
+        }
+    }
+
+    fn check_response(endpoint: &str, resp: Response) -> Result<Response, JenkinsError> {
+        let resp = resp.error_for_status()?;
+        if is_login_page(endpoint, get_content_type(&resp)) {
+            return Err(JenkinsAuthError::LoginPage.into());
+        }
+
+        Ok(resp)
+    }
+
+    /// # request multipart
+    ///
+    /// send a multipart form, which is required to upload files.
+    pub async fn request_multipart(
+        &self,
+        method: &str,
+        endpoint: &str,
+        form: Form,
+    ) -> Result<Response, JenkinsError> {
+        let resp = match self {
+            VfpJenkinsClient::PwdClient(c) => c.send_multipart(method, endpoint, form).await?,
+            VfpJenkinsClient::ApiTokenClient(c) => c.send_multipart(method, endpoint, form).await?,
+            VfpJenkinsClient::SessionClient(c) => c.send_multipart(method, endpoint, form).await?,
+        };
+
+        Self::check_response(endpoint, resp)
+    }
+}
+
+fn get_content_type(resp: &Response) -> Option<&str> {
+    resp.headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+}
+
+/// # is session expired
+///
+/// Jenkins treats a request with an expired session as anonymous, and an SSO returns its login page.
+fn is_session_expired(endpoint: &str, resp: &Response) -> bool {
+    let anonymous = resp
+        .headers()
+        .get(HEADER_AUTHENTICATED_AS)
+        .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"anonymous"));
+
+    resp.status() == StatusCode::UNAUTHORIZED
+        || (resp.status() == StatusCode::FORBIDDEN && anonymous)
+        || is_login_page(endpoint, get_content_type(resp))
+}
+
+/// # JenkinsCredential
+///
+/// How to log in to Jenkins.
+#[derive(Debug, Clone)]
+pub enum JenkinsCredential {
+    Pwd(String),
+    ApiToken(String),
+    Session(JenkinsSession, Option<SessionSource>),
+}
+
+/// # new jenkins async client
+///
+/// the client with the credential, which is not checked yet.
+pub fn new_jenkins_async_client(
+    url: &str,
+    username: &str,
+    credential: &JenkinsCredential,
+) -> Result<VfpJenkinsClient, JenkinsError> {
+    Ok(match credential {
+        JenkinsCredential::Pwd(pwd) => {
+            VfpJenkinsClient::PwdClient(PwdJenkinsAsyncClient::new(url, username, pwd)?)
+        }
+        JenkinsCredential::ApiToken(api_token) => VfpJenkinsClient::ApiTokenClient(
+            ApiTokenJenkinsAsyncClient::new(url, username, api_token)?,
+        ),
+        JenkinsCredential::Session(session, source) => VfpJenkinsClient::SessionClient(
+            SessionJenkinsAsyncClient::new(url, session.clone(), source.clone())?,
+        ),
+    })
 }
 
 pub async fn ping_jenkins(client: &VfpJenkinsClient) -> Result<(), JenkinsError> {
@@ -58,11 +282,11 @@ pub async fn try_get_jenkins_async_client_by_api_token(
             ERR_JENKINS_CLIENT_INVALID_SIMPLE.to_string(),
         ));
     }
-    let client = VfpJenkinsClient::ApiTokenClient(JenkinsAsyncClient::new(
+    let client = VfpJenkinsClient::ApiTokenClient(ApiTokenJenkinsAsyncClient::new(
         url.as_deref().unwrap(),
         username.as_deref().unwrap(),
         api_token.as_deref().unwrap(),
-    ));
+    )?);
     let result = ping_jenkins(&client).await;
 
     match result {
@@ -85,7 +309,7 @@ pub async fn try_get_jenkins_async_client_by_pwd(
         url.as_deref().unwrap(),
         username.as_deref().unwrap(),
         pwd.as_deref().unwrap(),
-    ));
+    )?);
     let result = ping_jenkins(&client).await;
 
     match result {
@@ -94,6 +318,43 @@ pub async fn try_get_jenkins_async_client_by_pwd(
     }
 }
 
+/// # try get jenkins async client by session
+///
+/// The session is checked by `whoAmI`, because an anonymous user may be able to read Jenkins.
+pub async fn try_get_jenkins_async_client_by_session(
+    url: &Option<String>,
+    session: &Option<JenkinsSession>,
+    source: &Option<SessionSource>,
+) -> Result<VfpJenkinsClient, JenkinsError> {
+    let (Some(url), Some(session)) = (url, session) else {
+        return Err(JenkinsError::RequestError(
+            ERR_JENKINS_CLIENT_INVALID_SIMPLE.to_string(),
+        ));
+    };
+    let client = VfpJenkinsClient::SessionClient(SessionJenkinsAsyncClient::new(
+        url,
+        session.clone(),
+        source.clone(),
+    )?);
+
+    let who_am_i: WhoAmIResult = AsyncQuery::query(&WhoAmI, &client).await?;
+    if who_am_i.anonymous || who_am_i.name.eq_ignore_ascii_case("anonymous") {
+        return Err(JenkinsAuthError::SessionExpired.into());
+    }
+
+    Ok(client)
+}
+
+/// # query job tree
+///
+/// query the jobs and views of Jenkins, descending `depth` levels of folders and nested views.
+pub async fn query_job_tree(
+    client: &VfpJenkinsClient,
+    depth: u32,
+) -> Result<job_tree::JobTree, JenkinsError> {
+    AsyncQuery::query(&JobTree { depth }, client).await
+}
+
 pub async fn query_builds_in_job(
     client: &VfpJenkinsClient,
     job_name: &str,
@@ -101,7 +362,7 @@ pub async fn query_builds_in_job(
 ) -> Result<WorkflowBuilds, JenkinsError> {
     AsyncQuery::query(
         &JobInfo {
-            job_name: job_name.into(),
+            job_path: job_name.into(),
             count,
         },
         client,
@@ -109,6 +370,16 @@ pub async fn query_builds_in_job(
     .await
 }
 
+/// # query queue item
+///
+/// query an item of the build queue, which has the run once it leaves the queue.
+pub async fn query_queue_item(
+    client: &VfpJenkinsClient,
+    id: u64,
+) -> Result<QueueItemResult, JenkinsError> {
+    AsyncQuery::query(&QueueItem { id }, client).await
+}
+
 pub async fn query_run_info(
     client: &VfpJenkinsClient,
     job_name: &str,
@@ -116,8 +387,61 @@ pub async fn query_run_info(
 ) -> Result<WorkflowRun, JenkinsError> {
     AsyncQuery::query(
         &RunInfo {
-            job_name: job_name.into(),
+            job_path: job_name.into(),
+            build_number,
+        },
+        client,
+    )
+    .await
+}
+
+/// # query run describe
+///
+/// query the stage view of a pipeline run.
+/// only available for pipeline jobs with the Pipeline Stage View plugin.
+pub async fn query_run_describe(
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    build_number: u32,
+) -> Result<WorkflowDescribe, JenkinsError> {
+    AsyncQuery::query(
+        &RunDescribe {
+            job_path: job_name.into(),
+            build_number,
+        },
+        client,
+    )
+    .await
+}
+
+pub async fn query_stage_describe(
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    build_number: u32,
+    node_id: &str,
+) -> Result<WorkflowStageDescribe, JenkinsError> {
+    AsyncQuery::query(
+        &StageDescribe {
+            job_path: job_name.into(),
+            build_number,
+            node_id: node_id.into(),
+        },
+        client,
+    )
+    .await
+}
+
+pub async fn query_node_log(
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    build_number: u32,
+    node_id: &str,
+) -> Result<WorkflowNodeLog, JenkinsError> {
+    AsyncQuery::query(
+        &NodeLog {
+            job_path: job_name.into(),
             build_number,
+            node_id: node_id.into(),
         },
         client,
     )
@@ -147,7 +471,7 @@ pub async fn query_run_log(
 ) -> Result<String, JenkinsError> {
     jenkins_sdk::AsyncRawQuery::raw_query(
         &RunLog {
-            job_name: job_name.into(),
+            job_path: job_name.into(),
             build_number,
         },
         client,
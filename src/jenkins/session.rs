@@ -0,0 +1,168 @@
+use crate::jenkins::error_kind::JenkinsAuthError;
+use crate::run::shell_command;
+use jenkins_sdk::JenkinsError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// # JenkinsSession
+///
+/// A session copied from the browser, for a Jenkins behind an SSO
+/// where neither the password nor the api token works.
+#[derive(Debug, PartialEq, Clone)]
+pub enum JenkinsSession {
+    /// sent as the `Cookie` header, like `JSESSIONID.abcd=xyz; other=1`.
+    Cookie(String),
+    /// sent as `Authorization: Bearer <token>`.
+    Bearer(String),
+}
+
+impl JenkinsSession {
+    /// # parse
+    ///
+    /// `name=value` pairs separated by `;` are cookies, and others, like `Bearer <token>`
+    /// or a bare token even with `=` of base64, are bearer tokens.
+    /// A leading `Cookie:` copied with the header is dropped.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+
+        if let Some((scheme, token)) = value.split_once(' ')
+            && scheme.eq_ignore_ascii_case("bearer")
+        {
+            return Some(JenkinsSession::Bearer(token.trim().to_string()));
+        }
+
+        let cookie = match value.split_once(':') {
+            Some((name, cookie)) if name.trim().eq_ignore_ascii_case("cookie") => cookie.trim(),
+            _ => value,
+        };
+        if is_cookie(cookie) {
+            Some(JenkinsSession::Cookie(cookie.to_string()))
+        } else {
+            Some(JenkinsSession::Bearer(cookie.to_string()))
+        }
+    }
+
+    /// a crumb is needed to post with a cookie, but not with a bearer token.
+    pub fn needs_crumb(&self) -> bool {
+        matches!(self, JenkinsSession::Cookie(_))
+    }
+}
+
+/// # is cookie
+///
+/// whether every pair is `name=value`, whose name is a token of RFC 6265
+/// and whose value is not only the padding of base64.
+fn is_cookie(value: &str) -> bool {
+    let is_cookie_name = |name: &str| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(c))
+    };
+
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .all(|pair| {
+            pair.split_once('=').is_some_and(|(name, value)| {
+                is_cookie_name(name) && !value.trim_matches('=').is_empty()
+            })
+        })
+}
+
+/// # SessionSource
+///
+/// Where the session is read every time, so that it can be refreshed out of fp.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionSource {
+    /// a file with the cookie or the token.
+    File(PathBuf),
+    /// a shell command printing the cookie or the token.
+    Command(String),
+}
+
+impl SessionSource {
+    pub fn read(&self) -> Result<String, JenkinsError> {
+        match self {
+            SessionSource::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| session_source_failed(format!("{} {}", path.display(), e))),
+            SessionSource::Command(cmd) => {
+                let output = shell_command(cmd)
+                    .output()
+                    .map_err(|e| session_source_failed(format!("{} {}", cmd, e)))?;
+                if !output.status.success() {
+                    return Err(session_source_failed(format!(
+                        "{} {}",
+                        cmd,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    )));
+                }
+
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            }
+        }
+    }
+}
+
+fn session_source_failed(e: String) -> JenkinsError {
+    JenkinsAuthError::SessionSourceFailed(e).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_session() {
+        assert_eq!(
+            JenkinsSession::parse("Cookie: JSESSIONID.abcd=xyz; other=1"),
+            Some(JenkinsSession::Cookie(
+                "JSESSIONID.abcd=xyz; other=1".to_string()
+            ))
+        );
+        assert_eq!(
+            JenkinsSession::parse(" bearer some-token\n"),
+            Some(JenkinsSession::Bearer("some-token".to_string()))
+        );
+        assert_eq!(
+            JenkinsSession::parse("some-token"),
+            Some(JenkinsSession::Bearer("some-token".to_string()))
+        );
+        assert_eq!(
+            JenkinsSession::parse("c29tZS10b2tlbg=="),
+            Some(JenkinsSession::Bearer("c29tZS10b2tlbg==".to_string()))
+        );
+        assert_eq!(
+            JenkinsSession::parse("some token=1"),
+            Some(JenkinsSession::Bearer("some token=1".to_string()))
+        );
+        assert_eq!(JenkinsSession::parse("  "), None);
+    }
+
+    #[test]
+    fn test_read_session_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cookie");
+        std::fs::write(&path, "JSESSIONID=xyz\n").unwrap();
+
+        assert_eq!(
+            SessionSource::File(path).read().unwrap().trim(),
+            "JSESSIONID=xyz"
+        );
+        assert_eq!(
+            SessionSource::Command("echo JSESSIONID=xyz".to_string())
+                .read()
+                .unwrap()
+                .trim(),
+            "JSESSIONID=xyz"
+        );
+        assert!(SessionSource::File(dir.path().join("missing"))
+            .read()
+            .is_err());
+    }
+}
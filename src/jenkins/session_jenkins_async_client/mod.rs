@@ -0,0 +1,182 @@
+use crate::jenkins::http_client::{build_http_client, with_total_timeout};
+use crate::jenkins::jenkins_endpoint::get_crumb::GetCrumb;
+use crate::jenkins::jenkins_model::crumb::Crumb;
+use crate::jenkins::session::{JenkinsSession, SessionSource};
+use jenkins_sdk::client::AsyncClient;
+use jenkins_sdk::{AsyncQuery, JenkinsError};
+use reqwest::header::{HeaderValue, AUTHORIZATION, COOKIE};
+use reqwest::multipart::Form;
+use reqwest::{Client, RequestBuilder, Response};
+use std::sync::RwLock;
+
+/// Asynchronous Jenkins API client.
+pub struct SessionJenkinsAsyncClient {
+    url: String,
+
+    /// replaced when the session is read again from its source.
+    session: RwLock<JenkinsSession>,
+    source: Option<SessionSource>,
+
+    client: Client,
+    crumb: RwLock<Option<Crumb>>,
+}
+
+impl SessionJenkinsAsyncClient {
+    /// Creates a new asynchronous Jenkins API client authentic by a session cookie or a bearer token.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Base URL of the Jenkins server.
+    /// * `session` - Session copied from the browser.
+    /// * `source` - Where the session is read again when it expires.
+    ///
+    /// Fails if the `[http]` of the runtime config is invalid.
+    pub fn new(
+        url: &str,
+        session: JenkinsSession,
+        source: Option<SessionSource>,
+    ) -> Result<Self, JenkinsError> {
+        Ok(Self {
+            url: url.into(),
+            session: RwLock::new(session),
+            source,
+            client: build_http_client(url, false)?,
+            crumb: RwLock::new(None),
+        })
+    }
+
+    pub async fn attach_crumb(&self) -> Result<(), JenkinsError> {
+        let crumb = AsyncQuery::<Crumb>::query(&GetCrumb, self).await?;
+        if let Ok(mut curr) = self.crumb.write() {
+            *curr = Some(crumb);
+        }
+
+        Ok(())
+    }
+
+    pub fn has_crumb(&self) -> bool {
+        self.crumb.read().is_ok_and(|crumb| crumb.is_some())
+    }
+
+    pub fn needs_crumb(&self) -> bool {
+        self.session
+            .read()
+            .is_ok_and(|session| session.needs_crumb())
+    }
+
+    /// only a session from a file or a command can be renewed.
+    pub fn can_renew(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// # renew session
+    ///
+    /// Read the session again from its source, and get a new crumb if there was one.
+    pub async fn renew_session(&self) -> Result<(), JenkinsError> {
+        let Some(source) = &self.source else {
+            return Ok(());
+        };
+        let session = JenkinsSession::parse(&source.read()?);
+
+        let had_crumb = self.has_crumb();
+        if let Some(session) = session
+            && let Ok(mut curr) = self.session.write()
+        {
+            *curr = session;
+        }
+        if let Ok(mut crumb) = self.crumb.write() {
+            *crumb = None;
+        }
+
+        if had_crumb {
+            self.attach_crumb().await?;
+        }
+
+        Ok(())
+    }
+
+    fn get_request_builder(
+        &self,
+        method: &str,
+        endpoint: &str,
+    ) -> Result<RequestBuilder, JenkinsError> {
+        let url = format!("{}/{}", self.url, endpoint);
+        let mut req = self
+            .client
+            .request(method.parse()?, url)
+            .header("User-Agent", "jenkins-sdk-rust");
+
+        let session = self
+            .session
+            .read()
+            .map(|session| session.clone())
+            .map_err(|e| JenkinsError::RequestError(e.to_string()))?;
+        req = match session {
+            JenkinsSession::Cookie(cookie) => req.header(COOKIE, header_value(&cookie)?),
+            JenkinsSession::Bearer(token) => {
+                req.header(AUTHORIZATION, header_value(&format!("Bearer {}", token))?)
+            }
+        };
+
+        if let Ok(crumb) = self.crumb.read()
+            && let Some(ref crumb) = *crumb
+        {
+            req = req.header(
+                crumb.crumb_request_field.clone(),
+                header_value(&crumb.crumb)?,
+            );
+        }
+
+        Ok(req)
+    }
+
+    /// Sends a request once, and returns the response whatever its status is.
+    pub async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<Response, JenkinsError> {
+        let req = with_total_timeout(self.get_request_builder(method, endpoint)?, endpoint);
+
+        let resp = if let Some(p) = params {
+            req.form(&p).send().await?
+        } else {
+            req.send().await?
+        };
+
+        Ok(resp)
+    }
+
+    /// Sends a multipart form, which is required to upload files.
+    /// The upload has no total timeout, only the read timeout of the client.
+    pub async fn send_multipart(
+        &self,
+        method: &str,
+        endpoint: &str,
+        form: Form,
+    ) -> Result<Response, JenkinsError> {
+        Ok(self
+            .get_request_builder(method, endpoint)?
+            .multipart(form)
+            .send()
+            .await?)
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, JenkinsError> {
+    HeaderValue::from_str(value).map_err(|e| JenkinsError::RequestError(e.to_string()))
+}
+
+#[async_trait::async_trait]
+impl AsyncClient for SessionJenkinsAsyncClient {
+    /// Sends an asynchronous HTTP request to the Jenkins server.
+    async fn request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: Option<&[(&str, &str)]>,
+    ) -> Result<String, JenkinsError> {
+        Ok(self.send(method, endpoint, params).await?.text().await?)
+    }
+}
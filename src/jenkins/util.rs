@@ -1,7 +1,18 @@
+use crate::jenkins::job_path::JobPath;
+
 pub fn get_jenkins_workflow_run_url(
     jenkins_url: &str,
     job_name: &str,
     build_number: u32,
 ) -> String {
-    format!("{}/job/{}/{}", jenkins_url, job_name, build_number)
+    format!(
+        "{}/{}/{}",
+        jenkins_url,
+        JobPath::from(job_name).to_url_path(),
+        build_number
+    )
+}
+
+pub fn get_jenkins_job_url(jenkins_url: &str, job_name: &str) -> String {
+    format!("{}/{}", jenkins_url, JobPath::from(job_name).to_url_path())
 }
@@ -1,17 +1,43 @@
 use crate::app_state::AppState;
 use crate::constant::log::*;
+use crate::constant::util::get_readable_duration;
+use crate::default_config;
 use crate::interact::input_ci_for_watch;
-use crate::jenkins::jenkins_model::reasoned_run_status::ReasonedRunStatus;
+use crate::jenkins::error_kind::JenkinsErrorKind;
+use crate::jenkins::jenkins_model::reasoned_run_status::{ReasonedRunStatus, RunFailure};
+use crate::jenkins::jenkins_model::run_progress::RunProgress;
 use crate::jenkins::jenkins_model::run_status::RunStatus;
-use crate::jenkins::query::{query_run_info, query_run_log, VfpJenkinsClient};
+use crate::jenkins::jenkins_model::stage_status::StageStatus;
+use crate::jenkins::jenkins_model::workflow_describe::{WorkflowDescribe, WorkflowStage};
+use crate::jenkins::jenkins_model::workflow_run::WorkflowRun;
+use crate::jenkins::query::{
+    query_node_log, query_run_describe, query_run_info, query_run_log, query_stage_describe,
+    VfpJenkinsClient,
+};
 use crate::jenkins::util::get_jenkins_workflow_run_url;
-use crate::pretty_log::{clean_one_line, colored_println, ThemeColor};
+use crate::pretty_log::{clean_one_line, colored_println, get_progress_bar, ThemeColor};
 use crate::service::jenkins_rpc_service::JenkinsRpcService;
 use crate::vfp_error::VfpFrontError;
-use chrono::Local;
+use chrono::{Local, Utc};
 use formatx::formatx;
 use jenkins_sdk::JenkinsError;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// # is not pipeline
+///
+/// whether the stage view query tells the run has no stage view for sure, by a 404 or an empty or
+/// invalid describe. other errors may be transient, so the stage view is queried again next time.
+fn is_not_pipeline(kind: &JenkinsErrorKind) -> bool {
+    matches!(
+        kind,
+        JenkinsErrorKind::NotFound(_)
+            | JenkinsErrorKind::JobNotFound(_)
+            | JenkinsErrorKind::BuildNotFound(..)
+            | JenkinsErrorKind::Parse(_)
+    )
+}
 
 async fn get_reasoned_run_status(
     client: &VfpJenkinsClient,
@@ -22,12 +48,199 @@ async fn get_reasoned_run_status(
 
     match run_info.result {
         RunStatus::Success => Ok(ReasonedRunStatus::Success),
-        RunStatus::Processing => Ok(ReasonedRunStatus::Processing),
-        RunStatus::Failure => {
-            let result = query_run_log(client, job_name, build_number).await?;
-            Ok(ReasonedRunStatus::Failure(result))
+        RunStatus::Processing => Ok(ReasonedRunStatus::Processing(
+            run_info.get_progress(Utc::now().timestamp_millis().max(0) as u64),
+        )),
+        RunStatus::Failure => Ok(ReasonedRunStatus::Failure(
+            get_run_failure(client, job_name, build_number).await?,
+        )),
+    }
+}
+
+/// # query reasoned run statuses
+///
+/// Query the status of several run tasks concurrently. results are in the order of `runs`.
+pub async fn query_reasoned_run_statuses(
+    client: Arc<VfpJenkinsClient>,
+    runs: &[(String, u32)],
+) -> Vec<Result<ReasonedRunStatus, VfpFrontError>> {
+    let mut tasks_set = JoinSet::new();
+    for (idx, (job_name, build_number)) in runs.iter().enumerate() {
+        let arc_client = client.clone();
+        let job_name = job_name.clone();
+        let build_number = *build_number;
+        tasks_set.spawn(async move {
+            let status = get_reasoned_run_status(arc_client.as_ref(), &job_name, build_number)
+                .await
+                .map_err(VfpFrontError::from);
+            (idx, status)
+        });
+    }
+
+    let mut results: Vec<Result<ReasonedRunStatus, VfpFrontError>> =
+        std::iter::repeat_with(|| Err(VfpFrontError::JenkinsTimeout))
+            .take(runs.len())
+            .collect();
+    while let Some(joined) = tasks_set.join_next().await {
+        if let Ok((idx, status)) = joined {
+            results[idx] = status;
         }
     }
+
+    results
+}
+
+/// # get run failure
+///
+/// Name the failed stage and fetch only its log by workflow api.
+/// Fallback to the whole console log if the stage view is not available.
+pub async fn get_run_failure(
+    client: &VfpJenkinsClient,
+    job_name: &str,
+    build_number: u32,
+) -> Result<RunFailure, JenkinsError> {
+    let failed_stage = query_run_describe(client, job_name, build_number)
+        .await
+        .ok()
+        .and_then(|describe| {
+            describe
+                .get_failed_stage()
+                .map(|stage| (stage.id.clone(), stage.name.clone()))
+        });
+
+    if let Some((stage_id, stage_name)) = failed_stage {
+        let mut log = String::new();
+
+        if let Ok(stage_describe) =
+            query_stage_describe(client, job_name, build_number, &stage_id).await
+        {
+            if let Some(ref error) = stage_describe.error {
+                log += formatx!(STAGE_ERROR_MESSAGE, error.message.as_str())
+                    .unwrap_or_default()
+                    .as_str();
+            }
+
+            for node in stage_describe.get_failed_flow_nodes() {
+                if let Ok(node_log) = query_node_log(client, job_name, build_number, &node.id).await
+                {
+                    log += formatx!(
+                        STAGE_FLOW_NODE_LOG_TITLE,
+                        node.name.as_str(),
+                        node.parameter_description.as_deref().unwrap_or_default()
+                    )
+                    .unwrap_or_default()
+                    .as_str();
+                    log += node_log.text.as_str();
+                    if node_log.has_more {
+                        log += STAGE_FLOW_NODE_LOG_TRUNCATED;
+                    }
+                }
+            }
+        }
+
+        if log.is_empty() {
+            log = query_run_log(client, job_name, build_number).await?;
+        }
+
+        return Ok(RunFailure {
+            failed_stage: Some(stage_name),
+            log,
+        });
+    }
+
+    Ok(RunFailure {
+        failed_stage: None,
+        log: query_run_log(client, job_name, build_number).await?,
+    })
+}
+
+/// # get stage transitions
+///
+/// Compare the stage status with the last check and record the changes.
+///
+/// ### Returns
+///
+/// stages whose status changed since the last check.
+fn get_stage_transitions<'a>(
+    last_stage_status: &mut HashMap<String, StageStatus>,
+    describe: &'a WorkflowDescribe,
+) -> Vec<&'a WorkflowStage> {
+    describe
+        .stages
+        .iter()
+        .filter(|stage| stage.status != StageStatus::NotExecuted)
+        .filter(|stage| {
+            last_stage_status
+                .insert(stage.id.clone(), stage.status)
+                .is_none_or(|last| last != stage.status)
+        })
+        .collect()
+}
+
+fn print_stage_transition(app_state: &AppState, stage: &WorkflowStage) {
+    let (color, msg) = match stage.status {
+        StageStatus::InProgress => (
+            ThemeColor::Main,
+            formatx!(WATCHING_STAGE_STARTED, stage.name.as_str()),
+        ),
+        status => (
+            match status {
+                StageStatus::Success => ThemeColor::Success,
+                StageStatus::Failed => ThemeColor::Error,
+                _ => ThemeColor::Warn,
+            },
+            formatx!(
+                WATCHING_STAGE_FINISHED,
+                stage.name.as_str(),
+                status,
+                get_readable_duration(stage.duration_millis)
+            ),
+        ),
+    };
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        color,
+        msg.unwrap_or_default().as_str(),
+    );
+}
+
+/// # get progress hint
+///
+/// like ` [#####-----] 50% | elapsed 5m00s | remaining 5m00s | stage: Compile`
+pub fn get_progress_hint(progress: Option<&RunProgress>, stage_name: Option<&str>) -> String {
+    let mut hint = match progress {
+        Some(progress) => match (progress.ratio(), progress.remaining(), progress.overtime()) {
+            (Some(ratio), _, Some(overtime)) => formatx!(
+                WATCHING_RUN_TASK_PROGRESS_OVERTIME,
+                get_progress_bar(ratio, default_config::WATCH_PROGRESS_BAR_WIDTH),
+                (ratio * 100.0) as u32,
+                get_readable_duration(progress.elapsed),
+                get_readable_duration(overtime)
+            ),
+            (Some(ratio), Some(remaining), None) => formatx!(
+                WATCHING_RUN_TASK_PROGRESS,
+                get_progress_bar(ratio, default_config::WATCH_PROGRESS_BAR_WIDTH),
+                (ratio * 100.0) as u32,
+                get_readable_duration(progress.elapsed),
+                get_readable_duration(remaining)
+            ),
+            _ => formatx!(
+                WATCHING_RUN_TASK_PROGRESS_NO_ESTIMATE,
+                get_readable_duration(progress.elapsed)
+            ),
+        }
+        .unwrap_or_default(),
+        None => String::new(),
+    };
+
+    if let Some(stage_name) = stage_name {
+        hint += formatx!(WATCHING_RUN_TASK_STAGE, stage_name)
+            .unwrap_or_default()
+            .as_str();
+    }
+
+    hint
 }
 
 /// # watch
@@ -41,6 +254,7 @@ async fn get_reasoned_run_status(
 /// * `username`:
 /// * `job_name`:
 /// * `ci`: focus build number of run task. if None, will query the latest run task.
+/// * `show_stage_transitions`: print a line whenever a pipeline stage starts or finishes.
 ///
 /// ### Returns
 ///
@@ -50,6 +264,7 @@ pub async fn watch(
     client: VfpJenkinsClient,
     job_name: &str,
     ci: Option<u32>,
+    show_stage_transitions: bool,
 ) -> Result<u32, VfpFrontError> {
     let build_number;
     let db = app_state.get_db();
@@ -73,7 +288,7 @@ pub async fn watch(
         if let Some(in_progress) = latest_info.in_progress {
             build_number = in_progress.number;
         } else if let Some(failed) = latest_info.failed {
-            let log = query_run_log(arc_client.as_ref(), job_name, failed.number).await?;
+            let failure = get_run_failure(arc_client.as_ref(), job_name, failed.number).await?;
 
             return Err(VfpFrontError::RunTaskBuildFailed {
                 build_number: failed.number,
@@ -83,7 +298,8 @@ pub async fn watch(
                     job_name,
                     failed.number,
                 ),
-                log,
+                failed_stage: failure.failed_stage,
+                log: failure.log,
             });
         } else if let Some(latest_success) = latest_info.latest_success {
             return Ok(latest_success.number);
@@ -125,33 +341,61 @@ pub async fn watch(
     );
 
     let mut clean_able = false;
+    let mut stage_view_available = true;
+    let mut last_stage_status: HashMap<String, StageStatus> = HashMap::new();
     loop {
         let get_reasoned_run_status =
             get_reasoned_run_status(arc_client.as_ref(), job_name, build_number).await?;
 
+        let describe = if stage_view_available
+            && (show_stage_transitions
+                || matches!(get_reasoned_run_status, ReasonedRunStatus::Processing(_)))
+        {
+            match query_run_describe(arc_client.as_ref(), job_name, build_number).await {
+                Ok(describe) => Some(describe),
+                Err(e) => {
+                    stage_view_available = !is_not_pipeline(&JenkinsErrorKind::from(&e));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         if clean_able {
             clean_one_line(&mut app_state.get_stdout());
         }
         clean_able = true;
 
+        if show_stage_transitions && let Some(ref describe) = describe {
+            for stage in get_stage_transitions(&mut last_stage_status, describe) {
+                print_stage_transition(app_state, stage);
+            }
+        }
+        let current_stage_name = describe
+            .as_ref()
+            .and_then(|describe| describe.get_current_stage())
+            .map(|stage| stage.name.as_str());
+
         match get_reasoned_run_status {
-            ReasonedRunStatus::Processing => {
+            ReasonedRunStatus::Processing(progress) => {
                 colored_println(
                     &mut app_state.get_stdout(),
                     ThemeColor::Warn,
-                    &formatx!(
+                    &(formatx!(
                         WATCHING_RUN_TASK_IN_PROGRESS,
                         build_number,
                         job_name,
                         Local::now().format("%Y-%m-%d %H:%M:%S")
                     )
-                    .unwrap_or_default(),
+                    .unwrap_or_default()
+                        + get_progress_hint(progress.as_ref(), current_stage_name).as_str()),
                 );
             }
             ReasonedRunStatus::Success => {
                 return Ok(build_number);
             }
-            ReasonedRunStatus::Failure(log) => {
+            ReasonedRunStatus::Failure(failure) => {
                 let db = app_state.get_db();
                 return Err(VfpFrontError::RunTaskBuildFailed {
                     build_number,
@@ -161,14 +405,198 @@ pub async fn watch(
                         job_name,
                         build_number,
                     ),
-                    log,
+                    failed_stage: failure.failed_stage,
+                    log: failure.log,
                 });
             }
         }
 
         tokio::time::sleep(tokio::time::Duration::from_secs(
-            crate::default_config::WATCH_INTERVAL,
+            default_config::WATCH_INTERVAL,
+        ))
+        .await;
+    }
+}
+
+/// # find first success with change list
+///
+/// the earliest success run task built on a change list not older than `min_cl`.
+fn find_first_success_with_change_list(runs: &[WorkflowRun], min_cl: u32) -> Option<&WorkflowRun> {
+    runs.iter()
+        .filter(|run| {
+            run.result == RunStatus::Success
+                && run
+                    .get_change_list_in_build_meta_data()
+                    .is_some_and(|cl| cl >= min_cl)
+        })
+        .min_by_key(|run| run.number)
+}
+
+/// # watch change list
+///
+/// Watch the recent run tasks of a job by interval, whoever started them,
+/// until one built on a change list not older than `min_cl` succeeds.
+///
+/// ### Returns
+///
+/// the build number of the success run task.
+pub async fn watch_change_list(
+    app_state: &mut AppState,
+    client: VfpJenkinsClient,
+    job_name: &str,
+    min_cl: u32,
+) -> Result<u32, VfpFrontError> {
+    let arc_client = Arc::new(client);
+
+    colored_println(
+        &mut app_state.get_stdout(),
+        ThemeColor::Warn,
+        &formatx!(WATCHING_CHANGE_LIST_PREPARE, job_name, min_cl).unwrap_or_default(),
+    );
+
+    let mut clean_able = false;
+    loop {
+        let runs = JenkinsRpcService::query_recent_runs(
+            arc_client.clone(),
+            job_name,
+            default_config::WATCH_QUERY_BUILDS_COUNT,
+        )
+        .await?;
+
+        if clean_able {
+            clean_one_line(&mut app_state.get_stdout());
+        }
+        clean_able = true;
+
+        if let Some(run) = find_first_success_with_change_list(&runs, min_cl) {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Second,
+                &format!(
+                    "{} {}",
+                    URL_OUTPUT,
+                    get_jenkins_workflow_run_url(
+                        app_state.get_db().get_jenkins_url().as_ref().unwrap(),
+                        job_name,
+                        run.number
+                    )
+                ),
+            );
+
+            return Ok(run.number);
+        }
+
+        colored_println(
+            &mut app_state.get_stdout(),
+            ThemeColor::Warn,
+            &formatx!(
+                WATCHING_CHANGE_LIST_IN_PROGRESS,
+                min_cl,
+                job_name,
+                runs.iter()
+                    .filter(|run| run.result == RunStatus::Processing)
+                    .count(),
+                Local::now().format("%Y-%m-%d %H:%M:%S")
+            )
+            .unwrap_or_default(),
+        );
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(
+            default_config::WATCH_INTERVAL,
         ))
         .await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_not_pipeline() {
+        assert!(is_not_pipeline(&JenkinsErrorKind::NotFound(
+            "http://jenkins/job/foo/1/wfapi/describe".to_string()
+        )));
+        assert!(is_not_pipeline(&JenkinsErrorKind::Parse(
+            "EOF while parsing a value".to_string()
+        )));
+
+        assert!(!is_not_pipeline(&JenkinsErrorKind::Network(
+            "operation timed out".to_string()
+        )));
+        assert!(!is_not_pipeline(&JenkinsErrorKind::Server(503)));
+        assert!(!is_not_pipeline(&JenkinsErrorKind::Other(
+            "429 Too Many Requests".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_get_stage_transitions() {
+        let mut last_stage_status = HashMap::new();
+
+        let describe = serde_json::from_str::<WorkflowDescribe>(
+            r#"{"stages": [
+                {"id": "6", "name": "Checkout", "status": "IN_PROGRESS"},
+                {"id": "18", "name": "Compile", "status": "NOT_EXECUTED"}
+            ]}"#,
+        )
+        .unwrap();
+        let transitions = get_stage_transitions(&mut last_stage_status, &describe);
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].name, "Checkout");
+
+        let transitions = get_stage_transitions(&mut last_stage_status, &describe);
+        assert!(transitions.is_empty());
+
+        let describe = serde_json::from_str::<WorkflowDescribe>(
+            r#"{"stages": [
+                {"id": "6", "name": "Checkout", "status": "SUCCESS"},
+                {"id": "18", "name": "Compile", "status": "IN_PROGRESS"}
+            ]}"#,
+        )
+        .unwrap();
+        let transitions = get_stage_transitions(&mut last_stage_status, &describe);
+        assert_eq!(
+            transitions
+                .iter()
+                .map(|stage| (stage.name.as_str(), stage.status))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Checkout", StageStatus::Success),
+                ("Compile", StageStatus::InProgress)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_first_success_with_change_list() {
+        fn run(number: u32, result: &str, cl: u32) -> WorkflowRun {
+            serde_json::from_str(&format!(
+                r#"{{"number": {}, "result": "{}", "actions": [{{
+                    "_class": "org.jenkinsci.plugins.buildmetadata.plugin.action.BuildMetadataAction",
+                    "buildMetadata": [{{
+                        "_class": "org.jenkinsci.plugins.buildmetadata.plugin.StringBuildMetadata",
+                        "name": "P4CL",
+                        "stringValue": "{}",
+                        "type": "StringBuildMetadata"
+                    }}]
+                }}]}}"#,
+                number, result, cl
+            ))
+            .unwrap()
+        }
+
+        let runs = vec![
+            run(14, "SUCCESS", 532110),
+            run(13, "SUCCESS", 532100),
+            run(12, "FAILURE", 532097),
+            run(11, "SUCCESS", 532090),
+        ];
+
+        assert_eq!(
+            find_first_success_with_change_list(&runs, 532097).map(|run| run.number),
+            Some(13)
+        );
+        assert!(find_first_success_with_change_list(&runs, 532111).is_none());
+    }
+}
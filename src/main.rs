@@ -1,12 +1,15 @@
 mod app_state;
 mod cli;
 mod constant;
+mod credential;
 mod db;
 mod default_config;
 pub mod distribute;
+mod doctor;
 mod extract;
 mod interact;
 mod jenkins;
+mod p4;
 mod pretty_log;
 mod run;
 mod service;
@@ -14,14 +17,20 @@ mod update;
 mod vfp_error;
 
 use crate::app_state::AppState;
-use crate::cli::{cli_do_login, cli_do_run, cli_try_first_login, input_job_name_with_err_handling};
+use crate::cli::{
+    cli_do_login, cli_do_run, cli_fetch_used_change_list, cli_get_jenkins_client_with_crumb,
+    cli_request_build, cli_resolve_build_params, cli_save_build_params, cli_try_first_login,
+    input_job_name_with_err_handling, parse_min_change_list, parse_param_pairs, BuildParamOptions,
+    LoginSecret,
+};
 use crate::constant::log::*;
+use crate::default_config::ConfigLayerKind;
 use crate::extract::extract_params::ExtractParams;
 use crate::interact::*;
-use crate::jenkins::build::{query_job_config_json, query_job_config_xml, request_build};
 use crate::jenkins::jenkins_model::shelves::Shelves;
 use crate::jenkins::jenkins_url_factor::JenkinsUrlFactor;
-use crate::jenkins::query::{query_builds_in_job, query_run_info, VfpJenkinsClient};
+use crate::jenkins::query::{query_builds_in_job, query_run_info};
+use crate::jenkins::session::SessionSource;
 use crate::jenkins::util::get_jenkins_workflow_run_url;
 use crate::pretty_log::{colored_println, ThemeColor};
 use crate::run::{kill_by_pid, run_instance, RunStatus};
@@ -31,6 +40,7 @@ use clap::{Parser, Subcommand};
 use formatx::formatx;
 use rand::Rng;
 use semver::Version;
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -49,6 +59,22 @@ use strum_macros::Display;
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// use the Jenkins server profile, like `fp --profile partner watch`.
+    /// `fp login` creates it if it does not exist.
+    #[arg(long, value_name = "PROFILE", global = true)]
+    profile: Option<String>,
+
+    /// override a key of the runtime config, like `fp --config repo_template=D:\builds extract`.
+    /// a nested key is like `http.timeout_secs=60`, and a key of a job is like
+    /// `jobs."Client.*".exe_file_name=Client.exe`. can be repeated.
+    #[arg(
+        long,
+        value_name = "KEY=VALUE",
+        value_parser = default_config::parse_config_override,
+        global = true
+    )]
+    config: Vec<(String, String)>,
 }
 
 #[derive(Subcommand, Display)]
@@ -135,6 +161,28 @@ enum Commands {
         /// Password of Jenkins.
         #[arg(short, long)]
         pwd: Option<String>,
+
+        /// Session cookie copied from the browser like "JSESSIONID.abcd=xyz",
+        /// or a bearer token like "Bearer <TOKEN>". For a Jenkins behind SSO.
+        #[arg(long, conflicts_with_all = ["session_file", "session_cmd"])]
+        session: Option<String>,
+
+        /// Read the session cookie or bearer token from the file on every run.
+        #[arg(long, conflicts_with("session_cmd"))]
+        session_file: Option<PathBuf>,
+
+        /// Run the command on every run, and use its output as the session cookie or bearer token.
+        #[arg(long)]
+        session_cmd: Option<String>,
+
+        /// Check the saved login step by step instead of logging in: the server, the credentials,
+        /// the user, the job permissions and the crumb issuer.
+        #[arg(long)]
+        check: bool,
+
+        /// The job to check the permissions of. The last used job by default.
+        #[arg(short, long, requires = "check")]
+        job_name: Option<String>,
     },
     /// Watch a Jenkins build task.
     Watch {
@@ -156,12 +204,110 @@ enum Commands {
         /// the Jenkins run task URL.
         #[arg(short, long)]
         url: Option<String>,
+
+        /// print a line whenever a pipeline stage starts or finishes.
+        #[arg(long)]
+        stages: bool,
+
+        /// watch the run tasks of anyone until one with the change list at least this succeeds.
+        /// like: --cl ">=532097"
+        #[arg(long, value_parser = parse_min_change_list, conflicts_with_all = ["ci", "url"])]
+        cl: Option<u32>,
+    },
+    /// Show the summary of a Jenkins build task.
+    Info {
+        /// job name.
+        #[arg(short, long)]
+        job_name: Option<String>,
+
+        /// locator identity.
+        #[arg(short = '#', long)]
+        ci: Option<u32>,
+
+        /// the Jenkins run task URL.
+        #[arg(short, long)]
+        url: Option<String>,
+
+        /// show the stage view of a pipeline run task.
+        #[arg(long)]
+        stages: bool,
+    },
+    /// Show the recent run tasks of a Jenkins job.
+    History {
+        /// job name.
+        #[arg(short, long)]
+        job_name: Option<String>,
+
+        /// show only the run tasks started by you.
+        #[arg(long)]
+        mine: bool,
+
+        /// how many recent run tasks to query.
+        #[arg(short = 'n', long, default_value_t = 50)]
+        count: u32,
+
+        /// show the success rate, median duration and the most failed stage.
+        #[arg(long)]
+        stats: bool,
+
+        /// export the history to a .csv or .json file.
+        #[arg(long)]
+        export: Option<PathBuf>,
+    },
+    /// List the jobs on Jenkins with the status of their last build.
+    Jobs {
+        /// show only the jobs whose path matches the regex.
+        #[arg(short, long)]
+        filter: Option<String>,
+    },
+    /// Abort a Jenkins build task. Your latest in-progress one by default.
+    Abort {
+        /// job name.
+        #[arg(short, long)]
+        job_name: Option<String>,
+
+        /// locator identity.
+        #[arg(short = '#', long)]
+        ci: Option<u32>,
+
+        /// the Jenkins run task URL.
+        #[arg(short, long)]
+        url: Option<String>,
+    },
+    /// Re-trigger a Jenkins build task with the exact params it used. Your latest one by default.
+    Rebuild {
+        /// job name.
+        #[arg(short, long)]
+        job_name: Option<String>,
+
+        /// locator identity.
+        #[arg(short = '#', long)]
+        ci: Option<u32>,
+
+        /// the Jenkins run task URL.
+        #[arg(short, long)]
+        url: Option<String>,
+
+        /// override build params.
+        /// repeated input --param can accept multiple sets of parameters
+        /// like: --param "CustomServer" "http://127.0.0.1:8080"
+        #[arg(long = "param",
+            num_args = 2,
+            value_names = ["PARAM_NAME", "PARAM_VALUE"],
+            action = clap::ArgAction::Append
+        )]
+        params: Vec<String>,
     },
     /// Request start a Jenkins build task.
     Build {
         /// job name.
+        /// repeated input -j builds multiple jobs with the shared change lists and params.
+        #[arg(short = 'j', long = "job-name")]
+        job_names: Vec<String>,
+
+        /// build all jobs of a group in the runtime config.
         #[arg(short, long)]
-        job_name: Option<String>,
+        group: Option<String>,
 
         /// change list number.
         #[arg(long)]
@@ -182,6 +328,33 @@ enum Commands {
         )]
         params: Vec<String>,
 
+        /// custom build params that skip the validation against the job definition.
+        /// like: --force-param "HiddenParam" "value"
+        #[arg(long = "force-param",
+            num_args = 2,
+            value_names = ["PARAM_NAME", "PARAM_VALUE"],
+            action = clap::ArgAction::Append
+        )]
+        force_params: Vec<String>,
+
+        /// apply a saved preset on top of the job's default params instead of the last used ones.
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// save the params that differ from the job's default as a preset.
+        /// change list and shelved change list are not saved.
+        #[arg(long)]
+        save_preset: Option<String>,
+
+        /// edit build params by a form driven by the job definition.
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// resolve the build params and print them with the changes,
+        /// but do not trigger the build or save anything.
+        #[arg(long)]
+        dry_run: bool,
+
         /// do not automatically watch and extract the package after success.
         #[arg(long)]
         no_watch_and_extract: bool,
@@ -193,6 +366,11 @@ enum Commands {
         #[command(flatten)]
         extract_params: ExtractParams,
     },
+    /// Manage build param presets of a job.
+    Preset {
+        #[command(subcommand)]
+        command: PresetCommands,
+    },
     /// Distribute the haxe compiled file(pt) to all blast packages in the same branch.
     Distr {
         /// job name.
@@ -231,6 +409,19 @@ enum Commands {
         #[arg(short, long)]
         version: Option<String>,
     },
+    /// Check the environment: the runtime config, the repo, the blast path,
+    /// the process tools and the login of Jenkins.
+    Doctor {
+        /// The job to check the directories, the blast path and the permissions of.
+        /// The last used job by default.
+        #[arg(short, long)]
+        job_name: Option<String>,
+    },
+    /// Show, get, set and edit the runtime config.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
     /// Clean cache.
     Clean,
     /// Open memory file directly.
@@ -240,10 +431,94 @@ enum Commands {
     Debug,
 }
 
+#[derive(Subcommand)]
+enum PresetCommands {
+    /// List presets of a job.
+    List {
+        /// job name.
+        #[arg(short, long)]
+        job_name: Option<String>,
+    },
+    /// Show params of a preset.
+    Show {
+        /// preset name.
+        name: String,
+
+        /// job name.
+        #[arg(short, long)]
+        job_name: Option<String>,
+    },
+    /// Remove a preset.
+    Rm {
+        /// preset name.
+        name: String,
+
+        /// job name.
+        #[arg(short, long)]
+        job_name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Show every effective value of the runtime config.
+    /// The layers from low to high priority: built-in, `fp-config.toml` next to fp,
+    /// `~/.config/fp/config.toml`, `.fp.toml` of the project, `FP_*` env vars and `--config`.
+    /// `fp config list` is the same.
+    #[command(visible_alias = "list")]
+    Show {
+        /// show the layer each value comes from.
+        #[arg(long)]
+        origin: bool,
+
+        /// show the config of a job, with the overrides of `[jobs]`.
+        #[arg(short, long)]
+        job_name: Option<String>,
+    },
+    /// Show the effective value of a key, like `repo_template` or `http`.
+    Get {
+        key: String,
+
+        /// get the value of a job, with the overrides of `[jobs]`.
+        #[arg(short, long)]
+        job_name: Option<String>,
+    },
+    /// Set a key in a layer of the runtime config, like `fp config set http.timeout_secs 60`.
+    Set {
+        key: String,
+
+        /// the value. a string does not need quotes, like `https://jenkins.example.com`.
+        value: String,
+
+        /// the layer to write to.
+        #[arg(long, value_enum, default_value_t = ConfigLayerKind::User)]
+        layer: ConfigLayerKind,
+
+        /// set `repo_template` even if it does not exist.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove a key from a layer of the runtime config.
+    Unset {
+        key: String,
+
+        /// the layer to remove from.
+        #[arg(long, value_enum, default_value_t = ConfigLayerKind::User)]
+        layer: ConfigLayerKind,
+    },
+    /// Open a layer of the runtime config with `$VISUAL`, `$EDITOR` or the default app.
+    Edit {
+        /// the layer to edit.
+        #[arg(long, value_enum, default_value_t = ConfigLayerKind::User)]
+        layer: ConfigLayerKind,
+    },
+}
+
 #[derive(Debug)]
 enum LoginMethod {
     Pwd,
     ApiToken,
+    Session,
 }
 
 impl Display for LoginMethod {
@@ -251,6 +526,7 @@ impl Display for LoginMethod {
         match self {
             LoginMethod::Pwd => write!(f, "Password"),
             LoginMethod::ApiToken => write!(f, "API Token"),
+            LoginMethod::Session => write!(f, "Session Cookie / Bearer Token"),
         }
     }
 }
@@ -258,12 +534,20 @@ impl Display for LoginMethod {
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
+    default_config::set_cli_overrides(cli.config);
     let mut app_state = AppState::new(None);
 
     if let Some(command) = cli.command {
         let command_name = command.to_string();
         show_welcome(Some(command_name.as_str()));
 
+        // only a login may create the profile.
+        let allow_new_profile = matches!(command, Commands::Login { check: false, .. });
+        let profile_selected = match cli.profile {
+            Some(profile) => select_profile(&mut app_state, &profile, allow_new_profile),
+            None => Ok(()),
+        };
+
         {
             let db = app_state.get_mut_db();
             let mut upgrade_info_usable = false;
@@ -285,7 +569,11 @@ async fn main() {
             }
         }
 
-        if let Err(err) = main_cli(&mut app_state, command).await {
+        let result = match profile_selected {
+            Ok(()) => main_cli(&mut app_state, command).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
             err.colored_println(&mut app_state.get_stdout());
         }
 
@@ -302,19 +590,13 @@ async fn main() {
 async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), VfpFrontError> {
     match command {
         Commands::Extract {
-            mut job_name,
-            mut ci,
+            job_name,
+            ci,
             extract_params,
             url,
         } => {
             // fp extract
-            let url_factor = url.and_then(|str| JenkinsUrlFactor::from_url(str.as_str()).ok());
-            job_name = job_name.or(url_factor
-                .as_ref()
-                .and_then(|factor| factor.get_job_name().map(|str| str.to_string())));
-            ci = ci.or(url_factor
-                .as_ref()
-                .and_then(|factor| factor.get_build_number()));
+            let (job_name, ci) = merge_url_factor(app_state, job_name, ci, url);
 
             cli::cli_do_extract(app_state, job_name, ci, extract_params, false).await?;
         }
@@ -340,241 +622,145 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
                 check_exe_file_name,
                 force,
                 server,
-            )?;
+            )
+            .await?;
         }
         Commands::Login {
             url,
             username,
             api_token,
             pwd,
+            session,
+            session_file,
+            session_cmd,
+            check,
+            job_name,
         } => {
             // fp login
-            cli_do_login(app_state, false, url, username, api_token, pwd).await?;
+            if check {
+                cli::cli_do_login_check(app_state, job_name).await?;
+                return Ok(());
+            }
+
+            let session_source = session_file
+                .map(SessionSource::File)
+                .or(session_cmd.map(SessionSource::Command));
+            cli_do_login(
+                app_state,
+                false,
+                url,
+                username,
+                LoginSecret {
+                    api_token,
+                    pwd,
+                    session,
+                    session_source,
+                },
+            )
+            .await?;
             colored_println(
                 &mut app_state.get_stdout(),
                 ThemeColor::Success,
-                JENKINS_LOGIN_RESULT,
+                &formatx!(JENKINS_LOGIN_RESULT, app_state.get_db().get_profile_name())
+                    .unwrap_or_default(),
             );
         }
         Commands::Build {
-            job_name,
+            job_names,
+            group,
             cl,
             sl,
             params,
+            force_params,
+            preset,
+            save_preset,
+            interactive,
+            dry_run,
             no_extract,
             no_watch_and_extract,
             extract_params,
         } => {
             // fp build
-            if params.len() % 2 != 0 {
-                return Err(VfpFrontError::Custom(ERR_NEED_EVEN_PARAM.to_string()));
-            }
-
-            cli_try_first_login(app_state, false).await?;
+            let param_pairs = parse_param_pairs(params)?;
+            let force_param_pairs = parse_param_pairs(force_params)?;
 
-            let db = app_state.get_db();
-            let mut client = db
-                .try_get_jenkins_async_client(&mut app_state.get_stdout(), true)
-                .await
-                .map_err(|_| VfpFrontError::JenkinsClientInvalid)?;
-            if let VfpJenkinsClient::PwdClient(ref mut client) = client {
-                client.attach_crumb().await.map_err(|e| {
+            let mut job_names = job_names;
+            if let Some(group) = group {
+                let group_job_names = default_config::runtime().job_groups.get(&group).ok_or(
                     VfpFrontError::Custom(
-                        formatx!(ERR_JENKINS_CLIENT_GET_CRUMB_FAILED, e.to_string())
-                            .unwrap_or_default(),
-                    )
-                })?;
-            }
-
-            let db = app_state.get_mut_db();
-            let job_name = input_job_name_with_err_handling(job_name, db)?;
-
-            db.insert_job_name(job_name.as_str());
-
-            let param_pairs: Vec<(String, serde_json::Value)> = params
-                .chunks(2)
-                .map(|chunk| (chunk[0].clone(), chunk[1].clone()))
-                .map(|(k, v)| {
-                    if let Ok(val) = v.parse::<bool>() {
-                        (k, serde_json::Value::Bool(val))
-                    } else {
-                        (k, serde_json::Value::String(v))
-                    }
-                })
-                .collect();
-
-            let config_params_result =
-                query_job_config_json(&client, &job_name)
-                    .await
-                    .inspect_err(|e| {
-                        #[cfg(debug_assertions)]
-                        {
-                            colored_println(
-                                &mut app_state.get_stdout(),
-                                ThemeColor::Warn,
-                                &e.to_string(),
-                            );
-                        }
-                    });
-
-            let config_params_result = if config_params_result.is_ok() {
-                config_params_result
-            } else {
-                let config_param_from_xml = query_job_config_xml(&client, &job_name)
-                    .await
-                    .inspect_err(|e| {
-                        #[cfg(debug_assertions)]
-                        {
-                            colored_println(
-                                &mut app_state.get_stdout(),
-                                ThemeColor::Warn,
-                                &e.to_string(),
-                            );
-                        }
-                    });
-
-                if config_param_from_xml.is_ok() {
-                    config_param_from_xml
-                } else {
-                    config_params_result
-                }
-            };
-
-            if let Err(ref e) = config_params_result {
-                e.colored_println(&mut app_state.get_stdout());
-            }
-
-            let db = app_state.get_db();
-            let build_params_template = config_params_result.unwrap_or_default();
-
-            let mut build_params = build_params_template.clone();
-
-            let mut used_cl: Option<u32> = None;
-            let mut used_sl: Option<Shelves> = None;
-
-            let db_latest_build_param = db.get_jenkins_build_param(job_name.as_ref());
-
-            if let Some(db_params) = db_latest_build_param {
-                used_cl = db_params.get_change_list();
-                used_sl = db_params.get_shelve_changes();
-
-                if build_params.from_default {
-                    build_params.merge_from(db_params);
-
-                    colored_println(
-                        &mut app_state.get_stdout(),
-                        ThemeColor::Warn,
-                        DB_BUILD_PARAM_DIRECTLY_ADOPTED,
-                    );
-                    colored_println(
-                        &mut app_state.get_stdout(),
-                        ThemeColor::Second,
-                        HINT_USE_PARAM_OPERATION,
-                    );
-                } else {
-                    let excluded = build_params.exclusive_merge_from(db_params);
-                    if !excluded.is_empty() {
-                        colored_println(
-                            &mut app_state.get_stdout(),
-                            ThemeColor::Warn,
-                            DB_BUILD_PARAM_NOT_IN_USED,
-                        );
-
-                        let mut stdout = app_state.get_stdout();
-                        excluded.iter().for_each(|(k, v)| {
-                            colored_println(
-                                &mut stdout,
-                                ThemeColor::Second,
-                                &format!("{}: {}", k, v),
-                            )
-                        })
-                    }
-                }
+                        formatx!(ERR_JOB_GROUP_NOT_FOUND, &group).unwrap_or_default(),
+                    ),
+                )?;
+                job_names.extend(group_job_names.iter().cloned());
             }
+            let mut seen = HashSet::new();
+            job_names.retain(|job_name| seen.insert(job_name.clone()));
 
-            build_params.set_change_list(input_cl(
-                cl,
-                &db_latest_build_param.and_then(|build_param| build_param.get_change_list()),
-            )?);
+            cli_try_first_login(app_state, false).await?;
 
             let sl = sl
                 .filter(|str| !str.is_empty())
                 .and_then(|v| Shelves::from_str(&v).ok());
-            build_params.set_shelve_changes(input_sl(
+            let options = BuildParamOptions {
+                cl,
                 sl,
-                &db_latest_build_param.and_then(|build_param| build_param.get_shelve_changes()),
-            )?);
+                param_pairs,
+                force_param_pairs,
+                preset,
+                interactive,
+                skip_undefined_params: job_names.len() > 1,
+            };
 
-            param_pairs.into_iter().for_each(|(k, v)| {
-                build_params.params.insert(k, v);
-            });
+            let client = cli_get_jenkins_client_with_crumb(app_state).await?;
 
-            let mut build_params_to_save = build_params.clone();
-            build_params_to_save.retain_differing_params(&build_params_template);
+            if job_names.len() <= 1 {
+                let job_name = input_job_name_with_err_handling(app_state, job_names.pop()).await?;
+                job_names = vec![job_name];
+            }
 
-            if build_params_to_save.get_change_list().is_none() {
-                build_params_to_save.set_change_list(used_cl);
+            // nothing is saved by a dry run, so it returns before the job is used.
+            if dry_run {
+                return cli::cli_do_build_dry_run(app_state, &client, job_names, &options).await;
             }
-            if build_params_to_save.get_shelve_changes().is_none() {
-                build_params_to_save.set_shelve_changes(used_sl);
+
+            if job_names.len() > 1 {
+                return cli::cli_do_build_fan_out(
+                    app_state,
+                    client,
+                    job_names,
+                    options,
+                    save_preset,
+                    no_watch_and_extract,
+                    no_extract,
+                    extract_params,
+                )
+                .await;
             }
 
-            let db = app_state.get_mut_db();
-            db.set_jenkins_build_param(job_name.as_ref(), Some(build_params_to_save));
-            app_state.commit(false);
+            let job_name = job_names.pop().unwrap_or_default();
 
-            let need_query_used_cl = build_params.get_change_list().is_none();
+            app_state.get_mut_db().insert_job_name(job_name.as_str());
 
-            request_build(&client, &job_name, &build_params)
-                .await
-                .map_err(|e| {
-                    VfpFrontError::Custom(
-                        formatx!(ERR_REQUEST_BUILD_FAILED, e.to_string()).unwrap_or_default(),
-                    )
-                })?;
+            let resolved =
+                cli_resolve_build_params(app_state, &client, job_name.clone(), &options, &mut None)
+                    .await?;
 
-            colored_println(
-                &mut app_state.get_stdout(),
-                ThemeColor::Success,
-                REQUEST_BUILD_SUCCESS,
-            );
-            colored_println(
-                &mut app_state.get_stdout(),
-                ThemeColor::Main,
-                BUILD_USED_PARAMS,
-            );
+            cli_save_build_params(app_state, &resolved, save_preset.as_deref());
 
-            let mut sorted_params_for_show: Vec<(&String, &serde_json::Value)> =
-                build_params.params.iter().collect();
-
-            sorted_params_for_show.sort_by(|&(lk, lv), &(rk, rv)| {
-                if (lv.is_string() && rv.is_string()) || (lv.is_boolean() && rv.is_boolean()) {
-                    lk.cmp(rk)
-                } else if lv.is_string() {
-                    std::cmp::Ordering::Less
-                } else if rv.is_string() {
-                    std::cmp::Ordering::Greater
-                } else if lv.is_boolean() {
-                    std::cmp::Ordering::Less
-                } else if rv.is_boolean() {
-                    std::cmp::Ordering::Greater
-                } else {
-                    lk.cmp(rk)
-                }
-            });
-            sorted_params_for_show.iter().for_each(|(k, v)| {
-                colored_println(
-                    &mut app_state.get_stdout(),
-                    ThemeColor::Main,
-                    &format!("{}: {}", k, v),
-                );
-            });
+            let need_query_used_cl = resolved.build_params.get_change_list().is_none();
 
-            let db = app_state.get_db();
-            if let Ok(builds) = query_builds_in_job(&client, &job_name, Some(3))
+            let queue_id = cli_request_build(app_state, &client, &resolved).await?;
+
+            let mut build_number = None;
+            if queue_id.is_some() {
+                build_number =
+                    cli::cli_find_triggered_run(app_state, &client, &job_name, queue_id).await;
+            } else if let Ok(builds) = query_builds_in_job(&client, &job_name, Some(3))
                 .await
                 .map(|b| b.builds)
             {
+                // without the queue item, the latest run of the user is taken as the triggered one.
+                let db = app_state.get_db();
                 for build in builds {
                     if let Ok(run) = query_run_info(&client, &job_name, build.number).await
                         && run.is_mine(db.get_jenkins_username().as_ref().unwrap())
@@ -592,6 +778,7 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
                                 )
                             ),
                         );
+                        build_number = Some(build.number);
                         break;
                     }
                 }
@@ -602,58 +789,11 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
             }
 
             let (used_job_name, success_build_number) =
-                cli::cli_do_watch(app_state, Some(job_name.clone()), None).await?;
+                cli::cli_do_watch(app_state, Some(job_name.clone()), build_number, None, false)
+                    .await?;
 
             if let (true, Some(build_number)) = (need_query_used_cl, success_build_number) {
-                let mut trial_count = 2;
-                loop {
-                    match query_run_info(&client, &job_name, build_number).await {
-                        Ok(workflow_run) => {
-                            if let Some(changelist) =
-                                workflow_run.get_change_list_in_build_meta_data()
-                            {
-                                let db = app_state.get_mut_db();
-                                let params =
-                                    db.get_mut_jenkins_build_param(job_name.as_str()).unwrap();
-                                params.set_change_list(Some(changelist));
-                                colored_println(
-                                    &mut app_state.get_stdout(),
-                                    ThemeColor::Second,
-                                    &formatx!(AUTO_FETCH_LATEST_USED_CL, changelist)
-                                        .unwrap_or_default(),
-                                );
-                                app_state.commit(false);
-                                break;
-                            } else if trial_count == 0 {
-                                colored_println(
-                                    &mut app_state.get_stdout(),
-                                    ThemeColor::Warn,
-                                    AUTO_FETCH_LATEST_USED_CL_FAILED,
-                                );
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            #[cfg(debug_assertions)]
-                            {
-                                colored_println(
-                                    &mut app_state.get_stdout(),
-                                    ThemeColor::Error,
-                                    &format!("Query build info failed because: {}", e),
-                                );
-                            }
-                        }
-                    }
-
-                    colored_println(
-                        &mut app_state.get_stdout(),
-                        ThemeColor::Second,
-                        AUTO_FETCH_LATEST_USED_CL_FAILED_AND_RETRY,
-                    );
-
-                    trial_count -= 1;
-                    tokio::time::sleep(Duration::from_secs_f32(0.5)).await;
-                }
+                cli_fetch_used_change_list(app_state, &client, &job_name, build_number).await;
             }
 
             if no_extract {
@@ -668,25 +808,21 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
             }
         }
         Commands::Watch {
-            mut job_name,
-            mut ci,
+            job_name,
+            ci,
             no_extract,
             extract_params,
             url,
+            stages,
+            cl,
         } => {
             // fp watch
-            let url_factor = url.and_then(|str| JenkinsUrlFactor::from_url(str.as_str()).ok());
-            job_name = job_name.or(url_factor
-                .as_ref()
-                .and_then(|factor| factor.get_job_name().map(|str| str.to_string())));
-            ci = ci.or(url_factor
-                .as_ref()
-                .and_then(|factor| factor.get_build_number()));
+            let (job_name, ci) = merge_url_factor(app_state, job_name, ci, url);
 
             cli_try_first_login(app_state, false).await?;
 
             let (used_job_name, success_build_number) =
-                cli::cli_do_watch(app_state, job_name, ci).await?;
+                cli::cli_do_watch(app_state, job_name, ci, cl, stages).await?;
 
             if !no_extract && let Some(build_number) = success_build_number {
                 let job_name = used_job_name;
@@ -695,18 +831,86 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
                 cli::cli_do_extract(app_state, job_name, ci, extract_params, true).await?;
             }
         }
+        Commands::Info {
+            job_name,
+            ci,
+            url,
+            stages,
+        } => {
+            // fp info
+            let (job_name, ci) = merge_url_factor(app_state, job_name, ci, url);
+
+            cli_try_first_login(app_state, false).await?;
+
+            cli::cli_do_info(app_state, job_name, ci, stages).await?;
+        }
+        Commands::History {
+            job_name,
+            mine,
+            count,
+            stats,
+            export,
+        } => {
+            // fp history
+            cli_try_first_login(app_state, false).await?;
+
+            cli::cli_do_history(app_state, job_name, mine, count, stats, export).await?;
+        }
+        Commands::Jobs { filter } => {
+            // fp jobs
+            cli_try_first_login(app_state, false).await?;
+
+            cli::cli_do_jobs(app_state, filter).await?;
+        }
+        Commands::Abort { job_name, ci, url } => {
+            // fp abort
+            let (job_name, ci) = merge_url_factor(app_state, job_name, ci, url);
+
+            cli_try_first_login(app_state, false).await?;
+
+            cli::cli_do_abort(app_state, job_name, ci).await?;
+        }
+        Commands::Rebuild {
+            job_name,
+            ci,
+            url,
+            params,
+        } => {
+            // fp rebuild
+            let param_pairs = parse_param_pairs(params)?;
+            let (job_name, ci) = merge_url_factor(app_state, job_name, ci, url);
+
+            cli_try_first_login(app_state, false).await?;
+
+            cli::cli_do_rebuild(app_state, job_name, ci, param_pairs).await?;
+        }
+        Commands::Preset { command } => match command {
+            PresetCommands::List { job_name } => {
+                // fp preset list
+                cli::cli_do_preset_list(app_state, job_name).await?;
+            }
+            PresetCommands::Show { name, job_name } => {
+                // fp preset show
+                cli::cli_do_preset_show(app_state, job_name, &name).await?;
+            }
+            PresetCommands::Rm { name, job_name } => {
+                // fp preset rm
+                cli::cli_do_preset_rm(app_state, job_name, &name).await?;
+            }
+        },
         Commands::Distr {
             job_name,
             src_blast_index,
             no_run,
         } => {
             // fp distr
+            let job_name = input_job_name_with_err_handling(app_state, job_name).await?;
             let db = app_state.get_db();
-
-            let job_name = input_job_name_with_err_handling(job_name, db)?;
             let last_player_count = db.get_last_player_count(job_name.as_str());
 
-            if let Some(count) = last_player_count && count <= 1 {
+            if let Some(count) = last_player_count
+                && count <= 1
+            {
                 colored_println(
                     &mut app_state.get_stdout(),
                     ThemeColor::Warn,
@@ -723,7 +927,7 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
                     .and_then(|i| formatx!(HINT_INPUT_SRC_BLAST_INDEX_LAST_USED, i).ok())
                     .unwrap_or_default()
             )
-                .unwrap_or_default();
+            .unwrap_or_default();
 
             let src_blast_index = input_directly_with_default(
                 src_blast_index,
@@ -752,7 +956,8 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
                 None,
                 true,
                 None,
-            )?;
+            )
+            .await?;
         }
         Commands::Update {
             auto_update,
@@ -800,6 +1005,28 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
             app_state.commit(false);
             return Ok(());
         }
+        Commands::Doctor { job_name } => {
+            // fp doctor
+            cli::cli_do_doctor(app_state, job_name).await?;
+        }
+        Commands::Config { command } => match command {
+            ConfigCommands::Show { origin, job_name } => {
+                cli::cli_do_config_show(app_state, origin, job_name.as_deref())
+            }
+            ConfigCommands::Get { key, job_name } => {
+                cli::cli_do_config_get(app_state, &key, job_name.as_deref())?
+            }
+            ConfigCommands::Set {
+                key,
+                value,
+                layer,
+                force,
+            } => cli::cli_do_config_set(app_state, &key, &value, layer, force)?,
+            ConfigCommands::Unset { key, layer } => {
+                cli::cli_do_config_unset(app_state, &key, layer)?
+            }
+            ConfigCommands::Edit { layer } => cli::cli_do_config_edit(app_state, layer)?,
+        },
         Commands::Clean => {
             // fp clean
             app_state.clean();
@@ -818,14 +1045,8 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
             println!("REPO_TEMPLATE: {:#?}", runtime_config.repo_template);
             println!("LOCATOR_PATTERN: {:#?}", runtime_config.locator_pattern);
             println!("LOCATOR_TEMPLATE: {:#?}", runtime_config.locator_template);
-            println!(
-                "MENDING_FILE_PATH: {:#?}",
-                runtime_config.mending_file_path
-            );
-            println!(
-                "PACKAGE_FILE_STEM: {:#?}",
-                runtime_config.package_file_stem
-            );
+            println!("MENDING_FILE_PATH: {:#?}", runtime_config.mending_file_path);
+            println!("PACKAGE_FILE_STEM: {:#?}", runtime_config.package_file_stem);
             println!("EXE_FILE_NAME: {:#?}", runtime_config.exe_file_name);
             println!(
                 "CHECK_EXE_FILE_NAME: {:#?}",
@@ -836,12 +1057,79 @@ async fn main_cli(app_state: &mut AppState, command: Commands) -> Result<(), Vfp
                 "QUERY_TOKEN_GITHUB: {:#?}",
                 runtime_config.query_token_github
             );
+            println!("P4_DEPOT_PATH: {:#?}", runtime_config.p4_depot_path);
+            println!("CREDENTIAL_STORE: {:#?}", runtime_config.credential_store);
+            println!("JOB_GROUPS: {:#?}", runtime_config.job_groups);
+            println!("HTTP: {:#?}", runtime_config.http);
+
+            let db = app_state.get_db();
+            println!("SERVER_PROFILE: {:#?}", db.get_profile_name());
+            println!("SERVER_PROFILES: {:#?}", db.get_profile_names());
         }
     }
 
     Ok(())
 }
 
+/// # select profile
+///
+/// use the server profile for this run. it must exist unless `allow_new` is set,
+/// so that a typo does not start a new profile.
+fn select_profile(
+    app_state: &mut AppState,
+    profile: &str,
+    allow_new: bool,
+) -> Result<(), VfpFrontError> {
+    if !allow_new && !app_state.get_db().has_profile(profile) {
+        return Err(VfpFrontError::Custom(
+            formatx!(ERR_PROFILE_NOT_FOUND, profile, profile).unwrap_or_default(),
+        ));
+    }
+
+    app_state.get_mut_db().select_profile(profile);
+    Ok(())
+}
+
+/// # merge url factor
+///
+/// fill the job name and build number from the Jenkins run task URL.
+/// values given explicitly take precedence.
+/// the profile whose Jenkins matches the URL is used if no profile is specified.
+fn merge_url_factor(
+    app_state: &mut AppState,
+    job_name: Option<String>,
+    ci: Option<u32>,
+    url: Option<String>,
+) -> (Option<String>, Option<u32>) {
+    if let Some(url) = url.as_deref()
+        && !app_state.get_db().is_profile_selected()
+        && let Some(profile) = app_state
+            .get_db()
+            .find_profile_by_url(url)
+            .map(str::to_string)
+    {
+        if profile != app_state.get_db().get_profile_name() {
+            colored_println(
+                &mut app_state.get_stdout(),
+                ThemeColor::Second,
+                &formatx!(PROFILE_SELECTED_BY_URL, &profile).unwrap_or_default(),
+            );
+        }
+        app_state.get_mut_db().select_profile(&profile);
+    }
+
+    let url_factor = url.and_then(|str| JenkinsUrlFactor::from_url(str.as_str()).ok());
+
+    (
+        job_name.or(url_factor
+            .as_ref()
+            .and_then(|factor| factor.get_job_path().map(|path| path.to_string()))),
+        ci.or(url_factor
+            .as_ref()
+            .and_then(|factor| factor.get_build_number())),
+    )
+}
+
 fn show_welcome(title: Option<&str>) {
     let title = if let Some(t) = title {
         format!("| {}", t.to_uppercase())
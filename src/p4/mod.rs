@@ -0,0 +1,290 @@
+use crate::constant::log::*;
+use crate::jenkins::jenkins_model::shelves::Shelves;
+use formatx::formatx;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::process::Command;
+
+/// # P4Command
+///
+/// Run a `p4` command and return its stdout. Stubbed in tests.
+pub trait P4Command {
+    fn run(&self, args: &[&str]) -> Result<String, P4Error>;
+}
+
+/// # P4Cli
+///
+/// Shell out to the `p4` on PATH. The connection follows the P4 environment of the user.
+pub struct P4Cli;
+
+impl P4Command for P4Cli {
+    fn run(&self, args: &[&str]) -> Result<String, P4Error> {
+        let output = Command::new("p4")
+            .args(args)
+            .output()
+            .map_err(|e| P4Error::NotAvailable(e.to_string()))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if !output.status.success() || !stderr.is_empty() {
+            return Err(P4Error::CommandFailed(stderr));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum P4Error {
+    /// `p4` is not found or cannot be started.
+    NotAvailable(String),
+
+    /// `p4` reported an error, like a failed connection or an unknown change.
+    CommandFailed(String),
+}
+
+impl Display for P4Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            P4Error::NotAvailable(e) => formatx!(ERR_P4_NOT_AVAILABLE, e),
+            P4Error::CommandFailed(e) => formatx!(ERR_P4_COMMAND_FAILED, e),
+        };
+
+        write!(f, "{}", str.unwrap_or_default())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct P4Change {
+    pub number: u32,
+    pub status: String,
+    pub description: String,
+
+    /// whether the change has shelved files.
+    pub shelved: bool,
+}
+
+impl P4Change {
+    fn from_tagged(record: &HashMap<String, String>) -> Option<Self> {
+        Some(Self {
+            number: record.get("change")?.parse().ok()?,
+            status: record.get("status").cloned().unwrap_or_default(),
+            description: record
+                .get("desc")
+                .map(|desc| desc.trim().to_string())
+                .unwrap_or_default(),
+            shelved: record.contains_key("shelved"),
+        })
+    }
+
+    fn is_submitted(&self) -> bool {
+        self.status == "submitted"
+    }
+}
+
+impl Display for P4Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.number, self.description)
+    }
+}
+
+/// # ShelveProblem
+///
+/// Why a shelved change list given to the build may not work.
+#[derive(Debug, PartialEq)]
+pub enum ShelveProblem {
+    NotFound(u32),
+    AlreadySubmitted(u32),
+    NotShelved(u32),
+}
+
+impl Display for ShelveProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ShelveProblem::NotFound(number) => formatx!(WARN_SHELVE_NOT_FOUND, number),
+            ShelveProblem::AlreadySubmitted(number) => {
+                formatx!(WARN_SHELVE_ALREADY_SUBMITTED, number)
+            }
+            ShelveProblem::NotShelved(number) => formatx!(WARN_SHELVE_NOT_SHELVED, number),
+        };
+
+        write!(f, "{}", str.unwrap_or_default())
+    }
+}
+
+/// # parse tagged
+///
+/// Parse the `-ztag` output into records. Records are separated by blank lines,
+/// and each field is like `... key value`.
+fn parse_tagged(output: &str) -> Vec<HashMap<String, String>> {
+    let mut records = Vec::new();
+    let mut record = HashMap::new();
+    for line in output.lines() {
+        match line.strip_prefix("... ") {
+            Some(field) => {
+                let (key, value) = field.split_once(' ').unwrap_or((field, ""));
+                record.insert(key.to_string(), value.to_string());
+            }
+            None if line.trim().is_empty() && !record.is_empty() => {
+                records.push(std::mem::take(&mut record));
+            }
+            None => {}
+        }
+    }
+
+    if !record.is_empty() {
+        records.push(record);
+    }
+
+    records
+}
+
+/// # query have change list
+///
+/// The latest change list synced to the workspace under `depot_path`, like `//stream/...`.
+pub fn query_have_change_list(
+    p4: &impl P4Command,
+    depot_path: &str,
+) -> Result<Option<u32>, P4Error> {
+    let output = p4.run(&["-ztag", "changes", "-m1", &format!("{}#have", depot_path)])?;
+
+    Ok(parse_tagged(&output)
+        .iter()
+        .find_map(P4Change::from_tagged)
+        .map(|change| change.number))
+}
+
+/// # query my shelved changes
+///
+/// The pending change lists of the current p4 user that have shelved files.
+pub fn query_my_shelved_changes(p4: &impl P4Command) -> Result<Vec<P4Change>, P4Error> {
+    let output = p4.run(&["-ztag", "changes", "-s", "shelved", "--me"])?;
+
+    Ok(parse_tagged(&output)
+        .iter()
+        .filter_map(P4Change::from_tagged)
+        .filter(|change| !change.is_submitted())
+        .collect())
+}
+
+/// # check shelves
+///
+/// Find the shelved change lists that do not exist, are already submitted or have no shelved files.
+pub fn check_shelves(
+    p4: &impl P4Command,
+    shelves: &Shelves,
+) -> Result<Vec<ShelveProblem>, P4Error> {
+    let mut problems = Vec::new();
+    for number in shelves.0.iter().copied() {
+        let change = match p4.run(&["-ztag", "describe", "-s", "-S", &number.to_string()]) {
+            Ok(output) => parse_tagged(&output).iter().find_map(P4Change::from_tagged),
+            Err(P4Error::CommandFailed(e)) if e.contains("unknown") => None,
+            Err(e) => return Err(e),
+        };
+
+        match change {
+            None => problems.push(ShelveProblem::NotFound(number)),
+            Some(change) if change.is_submitted() => {
+                problems.push(ShelveProblem::AlreadySubmitted(number))
+            }
+            Some(change) if !change.shelved => problems.push(ShelveProblem::NotShelved(number)),
+            Some(_) => {}
+        }
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    struct StubP4(HashMap<String, Result<String, P4Error>>);
+
+    impl P4Command for StubP4 {
+        fn run(&self, args: &[&str]) -> Result<String, P4Error> {
+            match self.0.get(&args.join(" ")) {
+                Some(Ok(output)) => Ok(output.clone()),
+                Some(Err(P4Error::CommandFailed(e))) => Err(P4Error::CommandFailed(e.clone())),
+                _ => Err(P4Error::NotAvailable("stub".to_string())),
+            }
+        }
+    }
+
+    fn stub(responses: &[(&str, Result<&str, &str>)]) -> StubP4 {
+        StubP4(
+            responses
+                .iter()
+                .map(|(args, resp)| {
+                    (
+                        args.to_string(),
+                        resp.map(str::to_string)
+                            .map_err(|e| P4Error::CommandFailed(e.to_string())),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_query_have_change_list() {
+        let p4 = stub(&[(
+            "-ztag changes -m1 //stream/main/...#have",
+            Ok("... change 12345\n... time 1760000000\n... user alice\n... client alice-ws\n... status submitted\n... changeType public\n... path //stream/main/...\n... desc Merge from dev\n\n"),
+        )]);
+
+        assert_eq!(
+            query_have_change_list(&p4, "//stream/main/..."),
+            Ok(Some(12345))
+        );
+
+        let p4 = stub(&[("-ztag changes -m1 //stream/main/...#have", Ok(""))]);
+        assert_eq!(query_have_change_list(&p4, "//stream/main/..."), Ok(None));
+    }
+
+    #[test]
+    fn test_query_my_shelved_changes() {
+        let p4 = stub(&[(
+            "-ztag changes -s shelved --me",
+            Ok("... change 201\n... status pending\n... shelved \n... desc Fix crash on login\n\n... change 202\n... status pending\n... shelved \n... desc WIP: new shop\n\n"),
+        )]);
+
+        let changes = query_my_shelved_changes(&p4).unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.number).collect::<Vec<_>>(),
+            vec![201, 202]
+        );
+        assert_eq!(changes[0].to_string(), "201 Fix crash on login");
+    }
+
+    #[test]
+    fn test_check_shelves() {
+        let p4 = stub(&[
+            (
+                "-ztag describe -s -S 201",
+                Ok("... change 201\n... status pending\n... shelved \n... desc Fix crash\n"),
+            ),
+            (
+                "-ztag describe -s -S 202",
+                Ok("... change 202\n... status submitted\n... desc Fix crash\n"),
+            ),
+            (
+                "-ztag describe -s -S 203",
+                Ok("... change 203\n... status pending\n... desc Empty\n"),
+            ),
+            ("-ztag describe -s -S 204", Err("Change 204 unknown.")),
+        ]);
+
+        assert_eq!(
+            check_shelves(&p4, &Shelves::from_str("201,202,203,204").unwrap()),
+            Ok(vec![
+                ShelveProblem::AlreadySubmitted(202),
+                ShelveProblem::NotShelved(203),
+                ShelveProblem::NotFound(204),
+            ])
+        );
+
+        let p4 = stub(&[]);
+        assert!(check_shelves(&p4, &Shelves::from_str("201").unwrap()).is_err());
+    }
+}
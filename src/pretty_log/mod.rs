@@ -186,6 +186,15 @@ pub fn clean_one_line<W: Write>(stdout: &mut W) {
     let _ = execute!(stdout, MoveUp(1), Clear(ClearType::CurrentLine),);
 }
 
+/// # get progress bar
+///
+/// render a progress bar like `[#####-----]` with `width` cells.
+pub fn get_progress_bar(ratio: f64, width: usize) -> String {
+    let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
 pub fn toast(title: &str, msg: Vec<&str>) {
     WinToastNotify::new()
         .set_title(&format!("V-F Platform | {}", title))
@@ -193,3 +202,15 @@ pub fn toast(title: &str, msg: Vec<&str>) {
         .show()
         .expect(ERR_TOAST_SHOW_FAILED)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_progress_bar() {
+        assert_eq!(get_progress_bar(0.0, 10), "[----------]");
+        assert_eq!(get_progress_bar(0.45, 10), "[#####-----]");
+        assert_eq!(get_progress_bar(1.5, 10), "[##########]");
+    }
+}
@@ -1,7 +1,8 @@
 use crate::constant::log::*;
 use configparser::ini::Ini;
 use formatx::formatx;
-use std::path::Path;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 pub enum RunStatus {
@@ -103,6 +104,51 @@ pub fn check_running(exe_path: &Path) -> Vec<u32> {
     }
 }
 
+/// # Find program
+///
+/// find the executable file of a command like `wmic` in `PATH`.
+pub fn find_program(name: &str) -> Option<PathBuf> {
+    find_program_in(name, &std::env::var_os("PATH")?)
+}
+
+fn find_program_in(name: &str, paths: &OsStr) -> Option<PathBuf> {
+    let file_names = if cfg!(windows) && Path::new(name).extension().is_none() {
+        vec![format!("{}.exe", name), name.to_string()]
+    } else {
+        vec![name.to_string()]
+    };
+
+    std::env::split_paths(paths).find_map(|dir| {
+        file_names
+            .iter()
+            .map(|file_name| dir.join(file_name))
+            .find(|path| path.is_file())
+    })
+}
+
+/// # Shell command
+///
+/// run `cmd` in the shell, like `code --wait`. `cmd` is passed as is on Windows,
+/// for `cmd` does not take the quotes escaped with backslashes.
+#[cfg(windows)]
+pub fn shell_command(cmd: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut command = Command::new("cmd");
+    command.arg("/C").raw_arg(cmd);
+    command
+}
+
+/// # Shell command
+///
+/// run `cmd` in the shell, like `code --wait`.
+#[cfg(not(windows))]
+pub fn shell_command(cmd: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.args(["-c", cmd]);
+    command
+}
+
 pub fn set_server(
     home_path: &Path,
     package_name: &str,
@@ -132,11 +178,25 @@ pub fn set_server(
 
 #[cfg(test)]
 mod tests {
-    use crate::run::check_running;
+    use crate::run::{check_running, find_program_in};
     use std::path::PathBuf;
 
     #[test]
     fn test_check_running() {
         assert!(!check_running(PathBuf::from("C:\\Windows\\explorer.exe").as_path()).is_empty());
     }
+
+    #[test]
+    fn test_find_program_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_name = if cfg!(windows) { "tool.exe" } else { "tool" };
+        std::fs::write(dir.path().join(file_name), "").unwrap();
+        let paths = std::env::join_paths([dir.path()]).unwrap();
+
+        assert_eq!(
+            find_program_in("tool", &paths),
+            Some(dir.path().join(file_name))
+        );
+        assert_eq!(find_program_in("missing", &paths), None);
+    }
 }
@@ -3,11 +3,13 @@ use crate::jenkins::jenkins_model::run_status::RunStatus;
 use crate::jenkins::jenkins_model::workflow_build::WorkflowBuild;
 use crate::jenkins::jenkins_model::workflow_run::WorkflowRun;
 use crate::jenkins::query::{
-    query_builds_in_job, query_run_info, UserLatestWorkflowInfo, VfpJenkinsClient,
+    query_builds_in_job, query_run_describe, query_run_info, UserLatestWorkflowInfo,
+    VfpJenkinsClient,
 };
 use crate::vfp_error::VfpFrontError;
+use jenkins_sdk::JenkinsError;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::task::JoinSet;
 
 pub struct JenkinsRpcService;
@@ -49,15 +51,9 @@ impl JenkinsRpcService {
                 let job_name = job_name.to_string();
                 let arc_client = client.to_owned();
                 tasks_set.spawn(async move {
-                    return match tokio::time::timeout(
-                        Duration::from_secs(10),
-                        query_run_info(arc_client.as_ref(), job_name.as_ref(), build_number),
-                    )
-                    .await
-                    {
-                        Ok(resp) => (joined_idx, resp.map_err(VfpFrontError::from)),
-                        Err(_) => (joined_idx, Err(VfpFrontError::JenkinsTimeout)),
-                    };
+                    let run_info =
+                        query_run_info(arc_client.as_ref(), job_name.as_ref(), build_number).await;
+                    (joined_idx, run_info.map_err(VfpFrontError::from))
                 });
             }
         }
@@ -147,6 +143,91 @@ impl JenkinsRpcService {
             failed: latest_failed_idx.and_then(|idx| results[idx].take()),
         })
     }
+
+    /// # query recent runs
+    ///
+    /// Query the latest `count` run tasks of a job concurrently. newest first.
+    /// Run tasks failed to query are skipped.
+    pub async fn query_recent_runs(
+        client: Arc<VfpJenkinsClient>,
+        job_name: &str,
+        count: u32,
+    ) -> Result<Vec<WorkflowRun>, VfpFrontError> {
+        let builds = query_builds_in_job(client.as_ref(), job_name, Some(count)).await?;
+        let build_numbers: Vec<u32> = builds.builds.iter().map(|build| build.number).collect();
+
+        let results = query_concurrently(&build_numbers, |build_number| {
+            let job_name = job_name.to_string();
+            let arc_client = client.clone();
+            async move { query_run_info(arc_client.as_ref(), job_name.as_ref(), build_number).await }
+        })
+        .await;
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// # query failed stages
+    ///
+    /// Query the failed stage name of run tasks concurrently by the stage view.
+    /// Run tasks without a stage view are not in the result.
+    pub async fn query_failed_stages(
+        client: Arc<VfpJenkinsClient>,
+        job_name: &str,
+        build_numbers: &[u32],
+    ) -> HashMap<u32, String> {
+        let describes =
+            query_concurrently(build_numbers, |build_number| {
+                let job_name = job_name.to_string();
+                let arc_client = client.clone();
+                async move {
+                    query_run_describe(arc_client.as_ref(), job_name.as_ref(), build_number).await
+                }
+            })
+            .await;
+
+        build_numbers
+            .iter()
+            .zip(describes)
+            .filter_map(|(build_number, describe)| {
+                describe?
+                    .get_failed_stage()
+                    .map(|stage| (*build_number, stage.name.clone()))
+            })
+            .collect()
+    }
+}
+
+/// # query concurrently
+///
+/// Run a query for each key, with at most [default_config::JENKINS_QUERY_CONCURRENCY_COUNT]
+/// at the same time. Results are in the order of the keys, and None if the query failed.
+async fn query_concurrently<K, T, F, Fut>(keys: &[K], query: F) -> Vec<Option<T>>
+where
+    K: Clone,
+    T: Send + 'static,
+    F: Fn(K) -> Fut,
+    Fut: Future<Output = Result<T, JenkinsError>> + Send + 'static,
+{
+    let mut tasks_set = JoinSet::new();
+    let mut results: Vec<Option<T>> = std::iter::repeat_with(|| None).take(keys.len()).collect();
+
+    let mut pending = keys.iter().cloned().enumerate();
+    loop {
+        while tasks_set.len() < default_config::JENKINS_QUERY_CONCURRENCY_COUNT
+            && let Some((idx, key)) = pending.next()
+        {
+            let task = query(key);
+            tasks_set.spawn(async move { (idx, task.await) });
+        }
+
+        match tasks_set.join_next().await {
+            Some(Ok((idx, Ok(result)))) => results[idx] = Some(result),
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    results
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 use crate::constant::log::*;
 use crate::constant::util::get_hidden_sensitive_string;
+use crate::jenkins::build::BuildParamError;
+use crate::jenkins::error_kind::JenkinsErrorKind;
 use crate::pretty_log::{colored_println, ThemeColor};
 use crate::LoginMethod;
 use formatx::formatx;
@@ -24,11 +26,18 @@ pub enum VfpFrontError {
     },
     JenkinsClientInvalid,
     JenkinsTimeout,
+    /// a classified error of a request to Jenkins.
+    /// `context` is the template of what was being done, like [ERR_REQUEST_BUILD_FAILED].
+    Jenkins {
+        context: Option<&'static str>,
+        kind: JenkinsErrorKind,
+    },
     MissingParam(String),
     RunTaskBuildFailed {
         build_number: u32,
         job_name: String,
         run_url: String,
+        failed_stage: Option<String>,
         log: String,
     },
     VersionParseFailed(String),
@@ -40,6 +49,7 @@ pub enum VfpFrontError {
     },
     OpenDbFailed(String),
     DistributeError(String),
+    BuildParamInvalid(Vec<BuildParamError>),
 }
 
 impl From<InquireError> for VfpFrontError {
@@ -53,8 +63,23 @@ impl From<InquireError> for VfpFrontError {
 }
 
 impl From<JenkinsError> for VfpFrontError {
-    fn from(_: JenkinsError) -> Self {
-        VfpFrontError::JenkinsClientInvalid
+    fn from(value: JenkinsError) -> Self {
+        VfpFrontError::Jenkins {
+            context: None,
+            kind: JenkinsErrorKind::from(&value),
+        }
+    }
+}
+
+impl VfpFrontError {
+    /// # jenkins error with context
+    ///
+    /// a classified Jenkins error, prefixed by `context` like [ERR_REQUEST_BUILD_FAILED].
+    pub fn jenkins_with_context(context: &'static str, e: JenkinsError) -> Self {
+        VfpFrontError::Jenkins {
+            context: Some(context),
+            kind: JenkinsErrorKind::from(&e),
+        }
     }
 }
 
@@ -89,6 +114,17 @@ impl Display for VfpFrontError {
                             )
                         )
                     }
+                    LoginMethod::Session => {
+                        formatx!(
+                            ERR_JENKINS_CLIENT_INVALID_MAY_BE_SESSION_INVALID,
+                            url,
+                            username,
+                            get_hidden_sensitive_string(
+                                key,
+                                crate::constant::util::SensitiveMode::Normal(4)
+                            )
+                        )
+                    }
                     LoginMethod::Pwd => {
                         formatx!(
                             ERR_JENKINS_CLIENT_INVALID_MAY_BE_PWD_INVALID,
@@ -109,9 +145,29 @@ impl Display for VfpFrontError {
             }
             VfpFrontError::JenkinsClientInvalid => ERR_JENKINS_CLIENT_INVALID.to_string(),
             VfpFrontError::JenkinsTimeout => ERR_JENKINS_TIMEOUT.to_string(),
+            VfpFrontError::Jenkins {
+                context: Some(context),
+                kind,
+            } => formatx!(*context, kind.to_string()).unwrap_or_default(),
+            VfpFrontError::Jenkins {
+                context: None,
+                kind,
+            } => kind.to_string(),
             VfpFrontError::MissingParam(param) => {
                 formatx!(ERR_NEED_PARAM, param).unwrap_or_default()
             }
+            VfpFrontError::RunTaskBuildFailed {
+                build_number,
+                job_name,
+                failed_stage: Some(stage),
+                ..
+            } => formatx!(
+                WATCHING_RUN_TASK_FAILURE_AT_STAGE,
+                build_number,
+                job_name,
+                stage
+            )
+            .unwrap_or_default(),
             VfpFrontError::RunTaskBuildFailed {
                 build_number,
                 job_name,
@@ -133,6 +189,12 @@ impl Display for VfpFrontError {
             VfpFrontError::DistributeError(msg) => {
                 formatx!(ERR_DISTRIBUTE_FAILED, msg).unwrap_or_default()
             }
+            VfpFrontError::BuildParamInvalid(errors) => {
+                std::iter::once(ERR_BUILD_PARAM_INVALID.to_string())
+                    .chain(errors.iter().map(|e| e.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
         };
         write!(f, "{}", str)
     }
@@ -165,6 +227,20 @@ impl VfpFrontError {
                         .as_str(),
                 );
             }
+            VfpFrontError::BuildParamInvalid(_) => {
+                colored_println(stdout, ThemeColor::Error, self.to_string().as_str());
+                colored_println(stdout, ThemeColor::Second, HINT_USE_FORCE_PARAM);
+            }
+            VfpFrontError::Jenkins { kind, .. } => {
+                colored_println(stdout, ThemeColor::Error, self.to_string().as_str());
+                if let Some(hint) = kind.get_hint() {
+                    colored_println(stdout, ThemeColor::Second, hint);
+                }
+            }
+            VfpFrontError::JobConfigMissingPermission => {
+                colored_println(stdout, ThemeColor::Error, self.to_string().as_str());
+                colored_println(stdout, ThemeColor::Second, HINT_JOB_CONFIG_JSON_FALLBACK);
+            }
             _ => {
                 colored_println(stdout, ThemeColor::Error, self.to_string().as_str());
             }